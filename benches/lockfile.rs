@@ -0,0 +1,63 @@
+// 大型锁文件的解析与索引基准：生成带有大量 snapshots 条目的 v9 锁文件，防止大锁文件路径的性能回退
+
+use criterion::{BenchmarkId, Criterion, Throughput, black_box, criterion_group, criterion_main};
+use std::fmt::Write;
+
+use npm_package_check::lockfile::Lockfile;
+
+/// 生成包含 count 个包的 v9 锁文件，每个 snapshot 依赖其后的两个包
+fn synthetic_lockfile(count: usize) -> String {
+    let mut content = String::from("lockfileVersion: '9.0'\n\nimporters:\n\n  .:\n    dependencies:\n");
+    for i in 0..count.min(100) {
+        let _ = write!(content, "      pkg-{i}:\n        specifier: ^1.0.{i}\n        version: 1.0.{i}\n");
+    }
+
+    content.push_str("\npackages:\n\n");
+    for i in 0..count {
+        let _ = write!(content, "  pkg-{i}@1.0.{i}:\n    resolution: {{integrity: sha512-{i:0>16}}}\n\n");
+    }
+
+    content.push_str("snapshots:\n\n");
+    for i in 0..count {
+        let _ = writeln!(content, "  pkg-{i}@1.0.{i}:");
+        let deps: Vec<usize> = [i + 1, i + 2].into_iter().filter(|&d| d < count).collect();
+        if !deps.is_empty() {
+            content.push_str("    dependencies:\n");
+            for d in deps {
+                let _ = writeln!(content, "      pkg-{d}: 1.0.{d}");
+            }
+        }
+        content.push('\n');
+    }
+    content
+}
+
+fn bench_lockfile(c: &mut Criterion) {
+    let mut group = c.benchmark_group("lockfile");
+    group.sample_size(10);
+
+    for count in [10_000, 100_000] {
+        let content = synthetic_lockfile(count);
+        let lock = Lockfile::parse(&content).expect("生成的锁文件应当可以解析");
+        group.throughput(Throughput::Elements(count as u64));
+
+        group.bench_with_input(BenchmarkId::new("parse", count), &content, |b, content| {
+            b.iter(|| Lockfile::parse(black_box(content)).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("packages", count), &lock, |b, lock| {
+            b.iter(|| lock.packages().count())
+        });
+        group.bench_with_input(BenchmarkId::new("index", count), &lock, |b, lock| {
+            b.iter(|| lock.index().len())
+        });
+        let index = lock.index();
+        group.bench_with_input(BenchmarkId::new("lookup", count), &index, |b, index| {
+            b.iter(|| (0..1000).map(|i| index.get(&format!("pkg-{i}")).len()).sum::<usize>())
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_lockfile);
+criterion_main!(benches);
@@ -0,0 +1,20 @@
+// 批量文件与风险清单解析的模糊测试：cargo fuzz run batch
+//
+// 风险清单可能来自远程地址，任意内容都只能解析失败或跳过无法识别的行，不能 panic
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use npm_package_check::batch;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(content) = std::str::from_utf8(data) else {
+        return;
+    };
+    let Ok(entries) = batch::parse(content) else {
+        return;
+    };
+    for entry in entries {
+        assert!(!entry.name.contains('\t'), "包名中有制表符: {content:?}");
+    }
+});
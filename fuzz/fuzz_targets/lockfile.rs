@@ -0,0 +1,36 @@
+// 锁文件解析的模糊测试：cargo fuzz run lockfile
+//
+// 输入的第一个字节选择锁文件格式，其余部分作为锁文件内容；解析可以失败，但不能 panic，
+// 解析成功的锁文件继续建立索引、遍历包记录与依赖关系，覆盖节点 key 与依赖值的拆分
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use npm_package_check::lockfile::{Lockfile, LockfileFormat};
+
+fuzz_target!(|data: &[u8]| {
+    let Some((&selector, content)) = data.split_first() else {
+        return;
+    };
+    let Ok(content) = std::str::from_utf8(content) else {
+        return;
+    };
+    let format = LockfileFormat::ALL[usize::from(selector) % LockfileFormat::ALL.len()];
+    let _ = LockfileFormat::sniff(content);
+
+    let Ok(lock) = Lockfile::parse_as(content, format) else {
+        return;
+    };
+    let index = lock.index();
+    for record in lock.packages() {
+        let _ = index.get(&record.name);
+        let _ = lock.install_scripts(&record.name, &record.version);
+        let _ = lock.resolution_for(&record.name, &record.version);
+        let _ = lock.override_for(&record.name);
+        let _ = lock.patch_for(&record.name, &record.version);
+    }
+    for key in lock.packages.keys().chain(lock.snapshots.keys()) {
+        let _ = lock.node_dependencies(key);
+        let _ = lock.package_info(key);
+    }
+});
@@ -0,0 +1,38 @@
+// packages/snapshots 节点 key 解析的模糊测试：cargo fuzz run package_key
+//
+// 任意输入都不能 panic 或耗尽栈空间；能拆分的 key 还需满足：
+//   包名与版本号非空，版本号中没有括号
+//   按 v9 的写法（name@version）重新拼接后再次解析，得到相同的包名与版本号
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use npm_package_check::package_key;
+use npm_package_check::snapshot;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(key) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    // 依赖值与节点 key 的写法相同，一并检查
+    let _ = snapshot::parse_dep_value(key);
+
+    let Some(package) = package_key::parse(key) else {
+        return;
+    };
+    assert!(!package.name.is_empty(), "包名为空: {key:?}");
+    assert!(!package.version.is_empty(), "版本号为空: {key:?}");
+    assert!(!package.version.contains(['(', ')']), "版本号中有括号: {key:?}");
+    for (name, version) in &package.peers {
+        assert!(!name.is_empty() && !version.is_empty(), "peer 为空: {key:?}");
+    }
+
+    let canonical = format!("{}@{}", package.name, package.version);
+    let reparsed = package_key::parse(&canonical).unwrap_or_else(|| panic!("无法重新解析 {canonical:?}（来自 {key:?}）"));
+    assert_eq!(
+        (reparsed.name.as_str(), reparsed.version.as_str()),
+        (package.name.as_str(), package.version.as_str()),
+        "重新解析的结果不同: {key:?}"
+    );
+});
@@ -0,0 +1,330 @@
+// 安全公告作为批量输入：从 OSV 格式（https://ossf.github.io/osv-schema/）的公告中读出受影响的包与版本范围
+//
+// 只读取 npm 与 crates.io 生态的 affected 条目。SEMVER / ECOSYSTEM 类型的 range 按事件顺序转换为
+// semver: 前缀的期望版本（introduced 到 fixed 为 >=a <b，到 last_affected 为 >=a <=b，多个区间以 || 连接），
+// 没有 range 时使用 versions 中列出的版本；introduced 为 0 且没有上界时匹配任意版本。
+// fixed 事件的版本作为修复版本，published 作为检测日期，公告 ID 随条目写入报告。已撤回（withdrawn）的公告跳过
+//
+// npm audit --json 的输出同样可以作为批量输入，用于在 pnpm 锁文件中复核在其他仓库中由 npm audit 发现的公告：
+//   npm 7+（auditReportVersion 2）  vulnerabilities 中各条目 via 里的公告对象（字符串只表示经由其他包间接受影响，跳过），
+//                                  range 只有一个上界（<4.17.21）时上界作为修复版本
+//   npm 6                          advisories 中的各条公告，patched_versions 作为修复版本
+// 受影响的版本范围（range、vulnerable_versions）加 semver: 前缀作为期望版本，严重程度作为 Status，
+// 公告 ID 取 GitHub 公告链接中的 GHSA ID，没有时使用 npm 的公告编号
+
+use anyhow::{Context, Result, bail};
+use semver::Version;
+use serde::Deserialize;
+
+use std::collections::{BTreeMap, HashSet};
+
+use crate::batch::{self, BatchEntry};
+
+/// 读取的 OSV 生态名称
+const ECOSYSTEMS: &[&str] = &["npm", "crates.io"];
+
+#[derive(Deserialize)]
+pub(crate) struct Osv {
+    id: String,
+    #[serde(default)]
+    published: Option<String>,
+    #[serde(default)]
+    withdrawn: Option<String>,
+    #[serde(default)]
+    affected: Vec<Affected>,
+}
+
+#[derive(Deserialize)]
+struct Affected {
+    #[serde(default)]
+    package: Option<Package>,
+    #[serde(default)]
+    ranges: Vec<Range>,
+    #[serde(default)]
+    versions: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct Package {
+    ecosystem: String,
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct Range {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    events: Vec<Event>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Event {
+    Introduced(String),
+    Fixed(String),
+    LastAffected(String),
+    Limit(serde::de::IgnoredAny),
+}
+
+/// osv.dev 查询接口的响应：{"vulns": [...]}
+#[derive(Deserialize)]
+struct Response {
+    #[serde(default)]
+    vulns: Vec<Osv>,
+}
+
+/// npm 7+ 的 npm audit --json 输出
+#[derive(Deserialize)]
+struct AuditReport {
+    #[serde(default)]
+    vulnerabilities: BTreeMap<String, Vulnerability>,
+}
+
+#[derive(Deserialize)]
+struct Vulnerability {
+    #[serde(default)]
+    via: Vec<Via>,
+}
+
+/// via 中的字符串为间接受影响时经由的包名
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Via {
+    Advisory(ViaAdvisory),
+    Package(serde::de::IgnoredAny),
+}
+
+#[derive(Deserialize)]
+struct ViaAdvisory {
+    #[serde(default)]
+    source: Option<serde_json::Value>,
+    name: String,
+    #[serde(default)]
+    url: Option<String>,
+    #[serde(default)]
+    severity: Option<String>,
+    #[serde(default)]
+    range: Option<String>,
+}
+
+/// npm 6 的 npm audit --json 输出
+#[derive(Deserialize)]
+struct LegacyAuditReport {
+    #[serde(default)]
+    advisories: BTreeMap<String, LegacyAdvisory>,
+}
+
+#[derive(Deserialize)]
+struct LegacyAdvisory {
+    #[serde(default)]
+    id: Option<serde_json::Value>,
+    module_name: String,
+    #[serde(default)]
+    vulnerable_versions: Option<String>,
+    #[serde(default)]
+    patched_versions: Option<String>,
+    #[serde(default)]
+    severity: Option<String>,
+    #[serde(default)]
+    created: Option<String>,
+    #[serde(default)]
+    url: Option<String>,
+    #[serde(default)]
+    github_advisory_id: Option<String>,
+}
+
+/// 内容以 { 开头的 JSON：单条 OSV 公告、osv.dev 查询接口的响应或 npm audit --json 的输出
+pub(crate) fn parse_object(content: &str) -> Result<Vec<BatchEntry>> {
+    let value: serde_json::Value = serde_json::from_str(content).context("解析 JSON 格式的批量文件失败")?;
+    if let Some(error) = value.get("error") {
+        // npm audit 失败（例如没有锁文件、registry 不可用）时同样以 JSON 输出错误
+        let summary = error.get("summary").and_then(|s| s.as_str()).unwrap_or_default();
+        bail!("批量文件是 npm audit 的错误输出，没有公告: {}", summary);
+    }
+    if value.get("auditReportVersion").is_some() || value.get("vulnerabilities").is_some() {
+        let report: AuditReport = serde_json::from_value(value).context("解析 npm audit 输出失败")?;
+        return Ok(audit_entries(&report));
+    }
+    if value.get("advisories").is_some() {
+        let report: LegacyAuditReport = serde_json::from_value(value).context("解析 npm audit 输出失败")?;
+        return Ok(legacy_audit_entries(&report));
+    }
+    if value.get("vulns").is_some() {
+        let response: Response = serde_json::from_value(value).context("解析 OSV 查询结果失败")?;
+        return Ok(entries(&response.vulns));
+    }
+    if value.get("id").is_none() {
+        bail!("无法识别的 JSON 批量文件：既不是条目数组，也不是 OSV 公告");
+    }
+    let advisory: Osv = serde_json::from_value(value).context("解析 OSV 公告失败")?;
+    Ok(entries(std::slice::from_ref(&advisory)))
+}
+
+/// 每条公告的每个受影响的包生成一个条目
+pub(crate) fn entries(advisories: &[Osv]) -> Vec<BatchEntry> {
+    advisories
+        .iter()
+        .filter(|advisory| advisory.withdrawn.is_none())
+        .flat_map(|advisory| {
+            advisory.affected.iter().filter_map(move |affected| {
+                let package = affected.package.as_ref()?;
+                if !ECOSYSTEMS.contains(&package.ecosystem.as_str()) || !batch::is_package_name(package.name.trim()) {
+                    return None;
+                }
+                let (versions, fixed_in) = expected_versions(affected)?;
+                Some(BatchEntry {
+                    name: package.name.trim().to_string(),
+                    versions,
+                    status: None,
+                    detection_date: advisory.published.as_deref().and_then(|date| date.get(..10)).map(str::to_string),
+                    fixed_in,
+                    advisories: vec![batch::single_line(&advisory.id)],
+                })
+            })
+        })
+        .collect()
+}
+
+/// npm audit 的公告 ID：GitHub 公告链接中的 GHSA ID，没有时为 npm 的公告编号
+fn audit_id(url: Option<&str>, number: Option<&serde_json::Value>) -> Option<String> {
+    let ghsa = url
+        .and_then(|url| url.trim_end_matches('/').rsplit('/').next())
+        .filter(|id| id.starts_with("GHSA-"));
+    match (ghsa, number) {
+        (Some(id), _) => Some(id.to_string()),
+        (None, Some(serde_json::Value::String(number))) => Some(number.clone()),
+        (None, Some(serde_json::Value::Number(number))) => Some(number.to_string()),
+        _ => None,
+    }
+}
+
+/// npm audit 的受影响范围，* 与空白为任意版本
+fn audit_range(range: Option<&str>) -> Vec<String> {
+    match range.map(str::trim) {
+        None | Some("" | "*") => Vec::new(),
+        Some(range) => vec![format!("semver:{}", batch::single_line(range))],
+    }
+}
+
+fn audit_entries(report: &AuditReport) -> Vec<BatchEntry> {
+    // 同一条公告可能出现在多个条目的 via 中
+    let mut seen = HashSet::new();
+    report
+        .vulnerabilities
+        .values()
+        .flat_map(|vulnerability| &vulnerability.via)
+        .filter_map(|via| match via {
+            Via::Advisory(advisory) => Some(advisory),
+            Via::Package(_) => None,
+        })
+        .filter(|advisory| batch::is_package_name(advisory.name.trim()))
+        .filter(|advisory| seen.insert((&advisory.name, &advisory.url, &advisory.range)))
+        .map(|advisory| BatchEntry {
+            name: advisory.name.trim().to_string(),
+            versions: audit_range(advisory.range.as_deref()),
+            status: advisory.severity.as_deref().map(batch::single_line),
+            detection_date: None,
+            // npm 7+ 的输出中没有修复版本；范围只有一个上界（<4.17.21）时上界即为修复版本
+            fixed_in: advisory
+                .range
+                .as_deref()
+                .and_then(|range| range.trim().strip_prefix('<'))
+                .filter(|upper| Version::parse(upper.trim()).is_ok())
+                .map(|upper| vec![upper.trim().to_string()])
+                .unwrap_or_default(),
+            advisories: audit_id(advisory.url.as_deref(), advisory.source.as_ref())
+                .map(|id| batch::single_line(&id))
+                .into_iter()
+                .collect(),
+        })
+        .collect()
+}
+
+fn legacy_audit_entries(report: &LegacyAuditReport) -> Vec<BatchEntry> {
+    report
+        .advisories
+        .values()
+        .filter(|advisory| batch::is_package_name(advisory.module_name.trim()))
+        .map(|advisory| {
+            let id = advisory
+                .github_advisory_id
+                .clone()
+                .or_else(|| audit_id(advisory.url.as_deref(), advisory.id.as_ref()));
+            BatchEntry {
+                name: advisory.module_name.trim().to_string(),
+                versions: audit_range(advisory.vulnerable_versions.as_deref()),
+                status: advisory.severity.as_deref().map(batch::single_line),
+                detection_date: advisory.created.as_deref().and_then(|date| date.get(..10)).map(str::to_string),
+                // 修复范围（>=4.17.21）按 semver 匹配，锁文件中同时存在修复版本时判定为 🩹
+                fixed_in: advisory
+                    .patched_versions
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|range| !range.is_empty() && *range != "<0.0.0")
+                    .map(|range| vec![format!("semver:{}", batch::single_line(range))])
+                    .unwrap_or_default(),
+                advisories: id.map(|id| batch::single_line(&id)).into_iter().collect(),
+            }
+        })
+        .collect()
+}
+
+fn is_version(version: &str) -> bool {
+    version == "0" || Version::parse(version).is_ok()
+}
+
+/// 期望版本与修复版本；范围与版本列表都无法使用时返回 None
+fn expected_versions(affected: &Affected) -> Option<(Vec<String>, Vec<String>)> {
+    let mut intervals = Vec::new();
+    let mut fixed_in = Vec::new();
+    let mut any = false;
+    let mut valid = true;
+    for range in affected.ranges.iter().filter(|range| range.kind == "SEMVER" || range.kind == "ECOSYSTEM") {
+        let mut introduced: Option<&str> = None;
+        for event in &range.events {
+            let (upper, op) = match event {
+                Event::Introduced(version) => {
+                    valid &= is_version(version);
+                    introduced = Some(version);
+                    continue;
+                }
+                Event::Fixed(version) => {
+                    fixed_in.push(version.clone());
+                    (version, "<")
+                }
+                Event::LastAffected(version) => (version, "<="),
+                Event::Limit(_) => continue,
+            };
+            valid &= is_version(upper);
+            if let Some(lower) = introduced.take() {
+                intervals.push(match lower {
+                    "0" => format!("{}{}", op, upper),
+                    lower => format!(">={} {}{}", lower, op, upper),
+                });
+            }
+        }
+        match introduced {
+            Some("0") => any = true,
+            Some(lower) => intervals.push(format!(">={}", lower)),
+            None => {}
+        }
+    }
+
+    if any && valid {
+        return Some((Vec::new(), fixed_in));
+    }
+    if !intervals.is_empty() && valid {
+        return Some((vec![format!("semver:{}", intervals.join(" || "))], fixed_in));
+    }
+    // 没有可用的范围时使用列出的版本，全部是 semver 版本时合为一个期望版本（任一命中即受影响）
+    let versions: Vec<String> = affected.versions.iter().map(|v| v.trim().to_string()).filter(|v| !v.is_empty()).collect();
+    if versions.is_empty() {
+        return None;
+    }
+    if versions.iter().all(|version| Version::parse(version).is_ok()) {
+        Some((vec![format!("semver:{}", versions.join(" || "))], fixed_in))
+    } else {
+        Some((versions, fixed_in))
+    }
+}
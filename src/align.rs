@@ -0,0 +1,130 @@
+// align 子命令：找出不同 importer 对同一个包使用不同版本规格或锁定版本的情况
+
+use anyhow::Result;
+use semver::Version;
+use std::collections::{BTreeMap, BTreeSet};
+
+use npm_package_check::lockfile::{DepKind, Lockfile};
+use npm_package_check::snapshot::{self, DepValue};
+
+use crate::{LockfileContext, collation};
+use crate::matching::{self, MatchMode, version_matches};
+use crate::registry::Registry;
+
+/// 某个 importer 对包的一次引用
+#[derive(Debug)]
+struct Usage {
+    importer: String,
+    kind: DepKind,
+    specifier: String,
+    /// 实际的版本规格，catalog: 规格替换为 catalogs 中的版本规格
+    range: String,
+    version: String,
+}
+
+pub fn run_align(lock_data: &Lockfile, context: &LockfileContext, registry: Option<&Registry>, verbose: bool) -> Result<()> {
+    let mut usages: BTreeMap<&str, Vec<Usage>> = BTreeMap::new();
+    for (path, importer) in &lock_data.importers {
+        for (kind, deps) in [
+            (DepKind::Prod, &importer.dependencies),
+            (DepKind::Dev, &importer.dev_dependencies),
+            (DepKind::Optional, &importer.optional_dependencies),
+        ] {
+            for (name, dep_info) in deps {
+                // workspace 内部链接与本地目录不参与对齐
+                let version = match snapshot::parse_dep_value(&dep_info.version) {
+                    DepValue::Version { version } | DepValue::Alias { version, .. } => version,
+                    DepValue::Link(_) | DepValue::File(_) => continue,
+                };
+                usages.entry(name).or_default().push(Usage {
+                    importer: path.clone(),
+                    kind,
+                    specifier: dep_info.specifier.clone(),
+                    range: lock_data.resolve_specifier(name, &dep_info.specifier).to_string(),
+                    version,
+                });
+            }
+        }
+    }
+
+    let mut drifted: Vec<(&str, Vec<Usage>)> = usages
+        .into_iter()
+        .filter(|(_, usages)| {
+            let importers: BTreeSet<&str> = usages.iter().map(|u| u.importer.as_str()).collect();
+            let specifiers: BTreeSet<&str> = usages.iter().map(|u| u.range.as_str()).collect();
+            let versions: BTreeSet<&str> = usages.iter().map(|u| u.version.as_str()).collect();
+            importers.len() > 1 && (specifiers.len() > 1 || versions.len() > 1)
+        })
+        .collect();
+    collation::sort_by_key(&mut drifted, |(name, _)| name);
+    for (_, usages) in &mut drifted {
+        collation::sort_by_key(usages, |usage| &usage.importer);
+    }
+
+    if drifted.is_empty() {
+        println!("✅ 各 importer 对同一个包的版本规格与锁定版本一致");
+        return Ok(());
+    }
+
+    println!("🔀 {} 个包在不同 importer 中的版本不一致\n", drifted.len());
+    for (name, usages) in &drifted {
+        println!("📦 {}", name);
+        for usage in usages {
+            let importer = context.importer_label(&usage.importer);
+            let specifier = match usage.range == usage.specifier {
+                true => usage.specifier.clone(),
+                false => format!("{} ({})", usage.specifier, usage.range),
+            };
+            println!(
+                "   - {}: {} → {} ({})",
+                importer,
+                specifier,
+                usage.version,
+                usage.kind.field_name()
+            );
+        }
+        if let Some(registry) = registry {
+            match suggest(name, usages, registry, verbose) {
+                Some(version) => println!("   💡 建议统一为 {}", version),
+                None => println!("   ⚠️ 无法给出建议：没有同时满足各版本规格的版本"),
+            }
+        }
+        println!();
+    }
+
+    std::process::exit(1);
+}
+
+/// 同时满足全部版本规格的最高稳定版本：候选为 registry 上已发布的版本，查询失败时使用锁文件中的版本
+fn suggest(name: &str, usages: &[Usage], registry: &Registry, verbose: bool) -> Option<String> {
+    // workspace:、git 地址等不是版本范围的规格（以及找不到 catalogs 条目的 catalog: 规格）无法参与计算，跳过
+    let ranges: Vec<&str> = usages
+        .iter()
+        .map(|u| u.range.as_str())
+        .filter(|spec| !spec.contains(':') && matching::validate(spec, MatchMode::Semver).is_ok())
+        .collect();
+    if ranges.is_empty() {
+        return None;
+    }
+
+    let candidates: Vec<String> = match registry.packument(name) {
+        Ok(Some(packument)) => packument.versions().map(str::to_string).collect(),
+        result => {
+            if verbose && let Err(e) = result {
+                eprintln!("⚠️ 无法获取 {} 的版本列表，改用锁文件中的版本: {:#}", name, e);
+            }
+            usages.iter().map(|u| u.version.clone()).collect()
+        }
+    };
+
+    candidates
+        .iter()
+        .filter_map(|v| Version::parse(v).ok())
+        .filter(|v| v.pre.is_empty())
+        .filter(|v| {
+            let text = v.to_string();
+            ranges.iter().all(|range| version_matches(&text, range, MatchMode::Semver))
+        })
+        .max()
+        .map(|v| v.to_string())
+}
@@ -0,0 +1,170 @@
+// -f 指定压缩包（.tar.gz/.tgz、.tar、.zip）：不解压到磁盘，在内存中读出其中的锁文件逐一检查
+//
+// 适用于归档的发布快照。锁文件的选取规则与在目录中查找时相同：跳过 node_modules 与 --exclude 排除的路径，
+// 同一目录中的 npm-shrinkwrap.json 优先于 package-lock.json；只读取文件名为锁文件名的条目，其余内容不解压。
+// 压缩包中的锁文件旁没有可用的 package.json 与 git 历史，与远程锁文件一样不能使用依赖这些信息的选项。
+// 压缩包可能来自不可信的来源：只读取普通文件条目（符号链接与硬链接条目跳过），含有 .. 的条目路径视为路径穿越并跳过，
+// 解压后超过 MAX_ENTRY_SIZE 的条目（压缩炸弹）读到上限即停止并跳过
+
+use anyhow::{Context, Result, bail};
+use flate2::read::GzDecoder;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use crate::http::Client;
+use crate::multi::{self, Summary};
+use crate::{Args, reject_local_options, scan};
+
+#[derive(Clone, Copy)]
+enum Kind {
+    Tar,
+    TarGz,
+    Zip,
+}
+
+/// 按扩展名判断压缩包类型
+fn kind(path: &str) -> Option<Kind> {
+    let path = path.to_ascii_lowercase();
+    if path.ends_with(".tar.gz") || path.ends_with(".tgz") {
+        Some(Kind::TarGz)
+    } else if path.ends_with(".tar") {
+        Some(Kind::Tar)
+    } else if path.ends_with(".zip") {
+        Some(Kind::Zip)
+    } else {
+        None
+    }
+}
+
+pub fn is_archive(path: &str) -> bool {
+    kind(path).is_some()
+}
+
+/// 单个条目解压后的大小上限，与下载远程文件的上限相同
+pub const MAX_ENTRY_SIZE: u64 = 64 * 1024 * 1024;
+
+/// 读取条目内容，最多读取 MAX_ENTRY_SIZE 字节；超过时给出提示并返回 None。
+/// 不依赖条目头中声明的大小，zip 中央目录与 tar 头中的大小都可能与实际解压出的内容不符
+pub fn read_entry<R: Read>(entry: R, path: &str) -> Result<Option<Vec<u8>>> {
+    let mut content = Vec::new();
+    entry
+        .take(MAX_ENTRY_SIZE + 1)
+        .read_to_end(&mut content)
+        .with_context(|| format!("无法读取 '{}'", path))?;
+    if content.len() as u64 > MAX_ENTRY_SIZE {
+        eprintln!("⚠️ 跳过解压后超过 {} MiB 的条目: {}", MAX_ENTRY_SIZE / 1024 / 1024, path);
+        return Ok(None);
+    }
+    Ok(Some(content))
+}
+
+/// 以文本读取条目（锁文件），超过大小上限时返回 None
+pub fn read_text_entry<R: Read>(entry: R, path: &str) -> Result<Option<String>> {
+    let Some(content) = read_entry(entry, path)? else {
+        return Ok(None);
+    };
+    String::from_utf8(content).map(Some).with_context(|| format!("无法读取 '{}'", path))
+}
+
+fn is_lockfile_name(path: &str) -> bool {
+    Path::new(path)
+        .file_name()
+        .is_some_and(|name| scan::LOCKFILE_NAMES.iter().any(|lockfile| name == *lockfile))
+}
+
+/// 压缩包内的路径：去掉开头的 / 与路径中的 . 和空段，\\ 视为分隔符；含有 .. 的路径返回 None
+pub fn sanitize_path(path: &str) -> Option<String> {
+    let mut parts = Vec::new();
+    for part in path.split(['/', '\\']) {
+        match part {
+            "" | "." => {}
+            ".." => {
+                eprintln!("⚠️ 跳过路径不安全的条目: {}", path);
+                return None;
+            }
+            part => parts.push(part),
+        }
+    }
+    Some(parts.join("/"))
+}
+
+fn read_tar<R: Read>(reader: R) -> Result<HashMap<String, String>> {
+    let mut files = HashMap::new();
+    let mut archive = tar::Archive::new(reader);
+    for entry in archive.entries().context("读取 tar 条目失败")? {
+        let mut entry = entry.context("读取 tar 条目失败")?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let Some(path) = sanitize_path(&entry.path().context("tar 条目路径无效")?.to_string_lossy()) else {
+            continue;
+        };
+        if !is_lockfile_name(&path) {
+            continue;
+        }
+        if let Some(content) = read_text_entry(&mut entry, &path)? {
+            files.insert(path, content);
+        }
+    }
+    Ok(files)
+}
+
+fn read_zip(file: File) -> Result<HashMap<String, String>> {
+    let mut files = HashMap::new();
+    let mut archive = zip::ZipArchive::new(file).context("读取 zip 目录失败")?;
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i).context("读取 zip 条目失败")?;
+        if !entry.is_file() {
+            continue;
+        }
+        let Some(path) = sanitize_path(entry.name()) else {
+            continue;
+        };
+        if !is_lockfile_name(&path) {
+            continue;
+        }
+        if let Some(content) = read_text_entry(&mut entry, &path)? {
+            files.insert(path, content);
+        }
+    }
+    Ok(files)
+}
+
+/// 压缩包中的锁文件，key 为包内路径
+fn read_lockfiles(path: &str) -> Result<HashMap<String, String>> {
+    let Some(kind) = kind(path) else {
+        bail!("不支持的压缩包格式 '{}'", path);
+    };
+    let file = File::open(path).with_context(|| format!("无法读取文件 '{}'", path))?;
+    let files = match kind {
+        Kind::Tar => read_tar(file),
+        Kind::TarGz => read_tar(GzDecoder::new(file)),
+        Kind::Zip => read_zip(file),
+    };
+    files.with_context(|| format!("解压 '{}' 失败", path))
+}
+
+pub fn run_archive(args: &mut Args, client: &Client) -> Result<()> {
+    multi::reject_options(args, "-f <压缩包>")?;
+    reject_local_options(args, "压缩包中的锁文件")?;
+
+    let archive = args.file.clone();
+    let files = read_lockfiles(&archive)?;
+    let paths: Vec<&str> = files.keys().map(String::as_str).collect();
+    let lockfiles = scan::select_lockfiles(&paths, &args.exclude)?;
+    if lockfiles.is_empty() {
+        bail!("压缩包 '{}' 中没有找到锁文件（{}）", archive, scan::LOCKFILE_NAMES.join(", "));
+    }
+    println!("🗜️ {}: 找到 {} 个锁文件\n", archive, lockfiles.len());
+
+    // 报告与使用统计中的锁文件名称
+    let labels: Vec<String> = lockfiles.iter().map(|path| format!("{}:{}", archive, path)).collect();
+    let mut summary = Summary::new(args, labels.clone());
+    for (i, (path, label)) in lockfiles.iter().zip(&labels).enumerate() {
+        println!("📦 [{}/{}] {}", i + 1, lockfiles.len(), path);
+        summary.check(args, client, label, Path::new(path), &files[*path], false)?;
+    }
+    summary.finish()
+}
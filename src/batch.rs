@@ -0,0 +1,480 @@
+// 批量文件（包列表）的解析
+//
+// 两种格式，均以制表符分隔、首行为表头，以 # 开头的行为注释：
+//   version1.txt  Row / Package Name / Version(s)
+//   version2.txt  Package Name / Compromised Version(s) / Detection Date / Status
+// 两种格式都可以在其他列之后追加可选的 Fixed In（或 Fixed Version(s)）列。
+//
+// 以逗号分隔的 CSV（例如 Google Sheets 导出的 CSV）按表头名称读取列，字段可以用双引号包围；
+// 表头与内置格式不同的表格（CSV 或制表符分隔）可以通过 Columns 指定各列的表头名称。
+// 由其他工具生成的清单可以使用 JSON 数组：
+//   [{ "name": "...", "versions": ["..."], "status": "...", "detectionDate": "...", "fixedIn": ["..."] }]
+// 只有 name 必填，versions 省略或为空时匹配任意版本，未知字段忽略。
+// SBOM 与漏洞扫描工具输出的 purl 可以直接使用：每行一个（第一个非注释行以 pkg: 开头），
+// 或作为 JSON 数组中的字符串元素。只读取 npm 与 cargo 类型，其他类型跳过；同一个包的多个 purl 合并为一个条目，
+// 没有版本的 purl 匹配任意版本。
+// OSV 格式的安全公告（单条公告、公告数组或 osv.dev 查询接口的响应）与 npm audit --json 的输出
+// 按受影响的包与版本范围读取，见 advisory 模块。
+// 批量文件与风险清单可能来自外部，列数不足的行直接跳过，任意输入都不会 panic
+
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+
+use crate::advisory::{self, Osv};
+use crate::purl;
+
+/// 批量文件中读取的 purl 类型，与支持的锁文件对应
+const PURL_TYPES: &[&str] = &["npm", "cargo"];
+
+/// 批量文件中的一个条目
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchEntry {
+    pub name: String,
+    pub versions: Vec<String>,
+    /// 安全报告格式的 Status 列
+    pub status: Option<String>,
+    /// 安全报告格式的 Detection Date 列
+    pub detection_date: Option<String>,
+    /// 可选的 Fixed In 列
+    pub fixed_in: Vec<String>,
+    /// 条目来源的公告 ID（OSV 公告等），其他格式为空
+    pub advisories: Vec<String>,
+}
+
+/// 按表头名称读取的列（不区分大小写），未指定的可选列不读取
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Columns {
+    /// 包名
+    pub name: String,
+    /// 受影响版本，一个单元格中的多个版本以逗号、分号或换行分隔
+    pub versions: String,
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default)]
+    pub detection_date: Option<String>,
+    #[serde(default)]
+    pub fixed_in: Option<String>,
+}
+
+impl Columns {
+    /// 未指定 Columns 的 CSV 按内置格式的表头名称读取
+    fn builtin(headers: &csv::StringRecord) -> Self {
+        let present = |name: &str| headers.iter().any(|header| header.trim().eq_ignore_ascii_case(name));
+        let first = |names: &[&str]| names.iter().find(|name| present(name)).map(|name| name.to_string());
+        Columns {
+            name: "Package Name".to_string(),
+            versions: first(&["Compromised Version(s)", "Version(s)"]).unwrap_or_else(|| "Version(s)".to_string()),
+            status: first(&["Status"]),
+            detection_date: first(&["Detection Date"]),
+            fixed_in: first(&["Fixed In", "Fixed Version(s)"]),
+        }
+    }
+}
+
+/// 解析批量文件的内容，空文件返回空列表，表头无法识别时返回错误
+pub fn parse(content: &str) -> Result<Vec<BatchEntry>> {
+    parse_with_columns(content, None)
+}
+
+/// 与 parse 相同，columns 为 Some 时按指定的表头名称读取列；
+/// 表头行含制表符时按制表符分隔，否则按 CSV 解析
+pub fn parse_with_columns(content: &str, columns: Option<&Columns>) -> Result<Vec<BatchEntry>> {
+    let content = content.trim_start_matches('\u{feff}');
+    if content.trim_start().starts_with('[') {
+        return parse_json(content);
+    }
+    if content.trim_start().starts_with('{') {
+        return advisory::parse_object(content);
+    }
+    let header = content.lines().find(|line| !line.trim().is_empty() && !line.trim_start().starts_with('#'));
+    if header.and_then(|line| line.trim_start().get(..4)).is_some_and(|scheme| scheme.eq_ignore_ascii_case("pkg:")) {
+        return Ok(parse_purls(content.lines().filter(|line| !line.trim_start().starts_with('#'))));
+    }
+    let delimiter = if header.is_some_and(|line| line.contains('\t')) { b'\t' } else { b',' };
+    match columns {
+        None if delimiter == b'\t' => parse_builtin(content),
+        None if header.is_none() => Ok(Vec::new()),
+        columns => parse_table(content, delimiter, columns),
+    }
+}
+
+fn parse_builtin(content: &str) -> Result<Vec<BatchEntry>> {
+    let lines: Vec<&str> = content
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .collect();
+    let Some((header, lines)) = lines.split_first() else {
+        return Ok(Vec::new());
+    };
+
+    // 检测文件格式
+    let fixed_column = header.split('\t').position(|column| {
+        let column = column.trim().to_lowercase().replace(' ', "");
+        column == "fixedin" || column.starts_with("fixedversion")
+    });
+    if header.contains("Package Name\tVersion(s)") {
+        Ok(parse_version1_format(lines, fixed_column))
+    } else if header.contains("Package Name\tCompromised Version(s)\tDetection Date\tStatus") {
+        Ok(parse_version2_format(lines, fixed_column))
+    } else {
+        bail!("无法识别的文件格式：{}", header)
+    }
+}
+
+/// 以 ", " 分隔的版本列表，空白为空列表
+fn split_versions(text: &str) -> Vec<String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Vec::new();
+    }
+    text.split(", ").map(|s| s.trim().to_string()).collect()
+}
+
+fn fixed_in(parts: &[&str], fixed_column: Option<usize>) -> Vec<String> {
+    fixed_column
+        .and_then(|i| parts.get(i))
+        .map(|text| split_versions(text))
+        .unwrap_or_default()
+}
+
+fn parse_version1_format(lines: &[&str], fixed_column: Option<usize>) -> Vec<BatchEntry> {
+    lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| {
+            let parts: Vec<&str> = line.split('\t').collect();
+            let [_, name, versions, ..] = parts.as_slice() else {
+                return None;
+            };
+            Some(BatchEntry {
+                name: name.trim().to_string(),
+                versions: split_versions(versions),
+                status: None,
+                detection_date: None,
+                fixed_in: fixed_in(&parts, fixed_column),
+                advisories: Vec::new(),
+            })
+        })
+        .collect()
+}
+
+fn parse_version2_format(lines: &[&str], fixed_column: Option<usize>) -> Vec<BatchEntry> {
+    lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| {
+            let parts: Vec<&str> = line.split('\t').collect();
+            let [name, versions, detection_date, status, ..] = parts.as_slice() else {
+                return None;
+            };
+            Some(BatchEntry {
+                name: name.trim().to_string(),
+                versions: split_versions(versions),
+                status: Some(status.trim().to_string()),
+                detection_date: Some(detection_date.trim().to_string()),
+                fixed_in: fixed_in(&parts, fixed_column),
+                advisories: Vec::new(),
+            })
+        })
+        .collect()
+}
+
+/// JSON 数组中的元素：条目对象、purl 字符串或 OSV 公告
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonItem {
+    Purl(String),
+    Entry(JsonEntry),
+    Osv(Osv),
+}
+
+/// JSON 格式中的一个条目
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonEntry {
+    name: String,
+    #[serde(default)]
+    versions: Vec<String>,
+    #[serde(default)]
+    status: Option<String>,
+    #[serde(default)]
+    detection_date: Option<String>,
+    #[serde(default)]
+    fixed_in: Vec<String>,
+}
+
+fn parse_json(content: &str) -> Result<Vec<BatchEntry>> {
+    let items: Vec<JsonItem> = serde_json::from_str(content).context("解析 JSON 格式的批量文件失败")?;
+    let text = |value: Option<String>| value.map(|v| single_line(&v)).filter(|v| !v.is_empty());
+    let list = |values: Vec<String>| values.iter().map(|v| single_line(v)).filter(|v| !v.is_empty()).collect();
+    let mut purls = Vec::new();
+    let mut advisories = Vec::new();
+    let mut entries = Vec::new();
+    for item in items {
+        match item {
+            JsonItem::Purl(purl) => purls.push(purl),
+            JsonItem::Osv(osv) => advisories.push(osv),
+            JsonItem::Entry(entry) if is_package_name(entry.name.trim()) => entries.push(BatchEntry {
+                name: entry.name.trim().to_string(),
+                versions: list(entry.versions),
+                status: text(entry.status),
+                detection_date: text(entry.detection_date),
+                fixed_in: list(entry.fixed_in),
+                advisories: Vec::new(),
+            }),
+            JsonItem::Entry(_) => {}
+        }
+    }
+    entries.extend(parse_purls(purls.iter().map(String::as_str)));
+    entries.extend(advisory::entries(&advisories));
+    Ok(entries)
+}
+
+/// purl 列表，同一个包的版本合并为一个条目；无法解析的行与不支持的类型跳过
+fn parse_purls<'a>(purls: impl IntoIterator<Item = &'a str>) -> Vec<BatchEntry> {
+    let mut entries: Vec<BatchEntry> = Vec::new();
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    // 出现过没有版本的 purl 的包，匹配任意版本
+    let mut any_version: HashSet<String> = HashSet::new();
+    for text in purls.into_iter().map(str::trim).filter(|text| !text.is_empty()) {
+        let Some(purl) = purl::parse(text) else {
+            continue;
+        };
+        if !PURL_TYPES.contains(&purl.kind.as_str()) || !is_package_name(&purl.name) {
+            continue;
+        }
+        let index = *positions.entry(purl.name.clone()).or_insert_with(|| {
+            entries.push(BatchEntry {
+                name: purl.name.clone(),
+                versions: Vec::new(),
+                status: None,
+                detection_date: None,
+                fixed_in: Vec::new(),
+                advisories: Vec::new(),
+            });
+            entries.len() - 1
+        });
+        let entry = &mut entries[index];
+        match purl.version.map(|version| single_line(&version)).filter(|version| !version.is_empty()) {
+            _ if any_version.contains(&purl.name) => {}
+            Some(version) => {
+                if !entry.versions.contains(&version) {
+                    entry.versions.push(version);
+                }
+            }
+            None => {
+                entry.versions.clear();
+                any_version.insert(purl.name);
+            }
+        }
+    }
+    entries
+}
+
+/// 以逗号、分号或换行分隔的版本列表
+fn split_cell(text: &str) -> Vec<String> {
+    text.split(|c: char| c == ',' || c == ';' || c.is_control())
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// CSV 单元格与 JSON 字符串中可以出现换行与制表符，写入 TSV 报告前替换为空格
+pub(crate) fn single_line(text: &str) -> String {
+    text.trim().replace(|c: char| c.is_control(), " ")
+}
+
+/// 包名不能为空，也不能含有控制字符（否则会打乱 TSV 报告的列）
+pub(crate) fn is_package_name(name: &str) -> bool {
+    !name.is_empty() && !name.contains(|c: char| c.is_control())
+}
+
+/// 按表头名称读取列的表格
+fn parse_table(content: &str, delimiter: u8, columns: Option<&Columns>) -> Result<Vec<BatchEntry>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .comment(Some(b'#'))
+        .flexible(true)
+        .from_reader(content.as_bytes());
+    let headers = reader.headers().context("读取表头失败")?.clone();
+    let columns = columns.cloned().unwrap_or_else(|| Columns::builtin(&headers));
+    let find = |name: &str| {
+        headers
+            .iter()
+            .position(|header| header.trim().eq_ignore_ascii_case(name.trim()))
+            .with_context(|| {
+                let available: Vec<&str> = headers.iter().map(str::trim).collect();
+                format!("表头中没有 '{}' 列（表头: {}）", name, available.join(", "))
+            })
+    };
+    let name = find(&columns.name)?;
+    let versions = find(&columns.versions)?;
+    let status = columns.status.as_deref().map(find).transpose()?;
+    let detection_date = columns.detection_date.as_deref().map(find).transpose()?;
+    let fixed_in = columns.fixed_in.as_deref().map(find).transpose()?;
+
+    let mut entries = Vec::new();
+    for record in reader.records() {
+        let record = record.context("解析表格失败")?;
+        // 与制表符分隔的格式一致，缺少版本列的行（例如被截断的行）直接跳过，而不是当作匹配任意版本
+        if record.get(versions).is_none() {
+            continue;
+        }
+        let cell = |column: Option<usize>| column.and_then(|i| record.get(i)).map(str::trim).filter(|text| !text.is_empty());
+        let Some(package) = cell(Some(name)).filter(|name| is_package_name(name)) else {
+            continue;
+        };
+        entries.push(BatchEntry {
+            name: package.to_string(),
+            versions: cell(Some(versions)).map(split_cell).unwrap_or_default(),
+            status: cell(status).map(single_line),
+            detection_date: cell(detection_date).map(single_line),
+            fixed_in: cell(fixed_in).map(split_cell).unwrap_or_default(),
+            advisories: Vec::new(),
+        });
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// (输入, 期望的 (包名, 版本) 列表)
+    type Case<'a> = (&'a str, &'a [(&'a str, &'a [&'a str])]);
+
+    /// (包名, 版本) 列表，便于表格形式的断言
+    fn names(content: &str) -> Vec<(String, Vec<String>)> {
+        parse(content).unwrap().into_iter().map(|entry| (entry.name, entry.versions)).collect()
+    }
+
+    fn expect(content: &str, expected: &[(&str, &[&str])]) {
+        let expected: Vec<(String, Vec<String>)> = expected
+            .iter()
+            .map(|(name, versions)| (name.to_string(), versions.iter().map(|v| v.to_string()).collect()))
+            .collect();
+        assert_eq!(names(content), expected, "{content}");
+    }
+
+    #[test]
+    fn csv_header_aliases() {
+        let cases: &[Case] = &[
+            ("Package Name,Version(s)\nlodash,4.17.20\n", &[("lodash", &["4.17.20"])]),
+            ("Package Name,Compromised Version(s)\nlodash,4.17.20\n", &[("lodash", &["4.17.20"])]),
+            // 表头不区分大小写，两侧的空白忽略
+            (" package name , VERSION(S) \nlodash,4.17.20\n", &[("lodash", &["4.17.20"])]),
+            // 列的顺序与内置格式不同
+            ("Status,Version(s),Package Name\nactive,1.0.0,chalk\n", &[("chalk", &["1.0.0"])]),
+        ];
+        for (content, expected) in cases {
+            expect(content, expected);
+        }
+
+        let entries = parse(
+            "Package Name,Compromised Version(s),Detection Date,Status,Fixed Version(s)\nchalk,5.6.1,2025-09-08,active,5.6.2\n",
+        )
+        .unwrap();
+        assert_eq!(entries[0].status.as_deref(), Some("active"));
+        assert_eq!(entries[0].detection_date.as_deref(), Some("2025-09-08"));
+        assert_eq!(entries[0].fixed_in, vec!["5.6.2"]);
+
+        assert!(parse("Name,Version\nlodash,4.17.20\n").is_err());
+    }
+
+    #[test]
+    fn csv_quoted_fields() {
+        let cases: &[Case] = &[
+            ("Package Name,Version(s)\n\"lodash\",\"4.17.20, 4.17.21\"\n", &[("lodash", &["4.17.20", "4.17.21"])]),
+            ("Package Name,Version(s)\n\"@scope/name\",\"1.0.0;2.0.0\"\n", &[("@scope/name", &["1.0.0", "2.0.0"])]),
+            // 单元格中的换行分隔版本
+            ("Package Name,Version(s)\nchalk,\"5.6.1\n5.6.2\"\n", &[("chalk", &["5.6.1", "5.6.2"])]),
+            // 空的版本单元格匹配任意版本
+            ("Package Name,Version(s)\nchalk,\"\"\n", &[("chalk", &[])]),
+        ];
+        for (content, expected) in cases {
+            expect(content, expected);
+        }
+
+        let entries = parse("Package Name,Version(s),Status\nchalk,5.6.1,\"under\nreview\"\n").unwrap();
+        assert_eq!(entries[0].status.as_deref(), Some("under review"));
+    }
+
+    #[test]
+    fn truncated_rows_skipped() {
+        let cases: &[Case] = &[
+            ("Package Name,Version(s)\nlodash\nchalk,5.6.1\n", &[("chalk", &["5.6.1"])]),
+            // 包名为空的行
+            ("Package Name,Version(s)\n,1.0.0\nchalk,5.6.1\n", &[("chalk", &["5.6.1"])]),
+            ("Row\tPackage Name\tVersion(s)\n1\tlodash\n2\tchalk\t5.6.1\n", &[("chalk", &["5.6.1"])]),
+            (
+                "Package Name\tCompromised Version(s)\tDetection Date\tStatus\nlodash\t4.17.20\t2025-09-08\nchalk\t5.6.1\t2025-09-08\tactive\n",
+                &[("chalk", &["5.6.1"])],
+            ),
+            // 注释行与空行
+            ("# 导出自表格\nPackage Name,Version(s)\n\n# lodash,4.17.20\nchalk,5.6.1\n", &[("chalk", &["5.6.1"])]),
+        ];
+        for (content, expected) in cases {
+            expect(content, expected);
+        }
+    }
+
+    #[test]
+    fn json_entries() {
+        let cases: &[Case] = &[
+            (r#"[{"name": "lodash", "versions": ["4.17.20"]}]"#, &[("lodash", &["4.17.20"])]),
+            (r#"[{"name": "lodash"}, {"name": "chalk", "versions": []}]"#, &[("lodash", &[]), ("chalk", &[])]),
+            // 未知字段忽略
+            (r#"[{"name": "lodash", "versions": ["4.17.20"], "source": "feed"}]"#, &[("lodash", &["4.17.20"])]),
+            // 空的包名与含控制字符的包名跳过
+            (r#"[{"name": " "}, {"name": "a\tb"}, {"name": "chalk"}]"#, &[("chalk", &[])]),
+            // purl 字符串与条目对象混合
+            (r#"["pkg:npm/chalk@5.6.1", {"name": "lodash"}]"#, &[("lodash", &[]), ("chalk", &["5.6.1"])]),
+            ("\u{feff}[]", &[]),
+        ];
+        for (content, expected) in cases {
+            expect(content, expected);
+        }
+
+        let entries =
+            parse(r#"[{"name": "chalk", "status": "active", "detectionDate": "2025-09-08", "fixedIn": ["5.6.2"]}]"#).unwrap();
+        assert_eq!(entries[0].status.as_deref(), Some("active"));
+        assert_eq!(entries[0].detection_date.as_deref(), Some("2025-09-08"));
+        assert_eq!(entries[0].fixed_in, vec!["5.6.2"]);
+    }
+
+    #[test]
+    fn malformed_json() {
+        for content in [
+            "[",
+            r#"[{"name": "lodash",}]"#,
+            r#"[{"versions": ["1.0.0"]}]"#,
+            r#"[{"name": "lodash", "versions": "4.17.20"}]"#,
+            "[1, 2]",
+        ] {
+            assert!(parse(content).is_err(), "{content}");
+        }
+    }
+
+    #[test]
+    fn purl_lists() {
+        let cases: &[Case] = &[
+            ("pkg:npm/lodash@4.17.20\n", &[("lodash", &["4.17.20"])]),
+            ("pkg:npm/%40scope/name@1.0.0\n", &[("@scope/name", &["1.0.0"])]),
+            ("PKG:npm/lodash@4.17.20\n", &[("lodash", &["4.17.20"])]),
+            // 同一个包的版本合并，没有版本的 purl 匹配任意版本
+            ("pkg:npm/lodash@4.17.20\npkg:npm/lodash@4.17.21\npkg:npm/lodash@4.17.20\n", &[("lodash", &["4.17.20", "4.17.21"])]),
+            ("pkg:npm/lodash@4.17.20\npkg:npm/lodash\npkg:npm/lodash@4.17.21\n", &[("lodash", &[])]),
+            // 注释、空行、无法解析的行与不支持的类型跳过
+            (
+                "# SBOM\npkg:npm/chalk@5.6.1\n\nnot a purl\npkg:\npkg:pypi/requests@2.0.0\npkg:cargo/serde@1.0.0\n",
+                &[("chalk", &["5.6.1"]), ("serde", &["1.0.0"])],
+            ),
+        ];
+        for (content, expected) in cases {
+            expect(content, expected);
+        }
+    }
+}
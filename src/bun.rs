@@ -0,0 +1,351 @@
+// bun.lock（Bun 1.2 起的文本锁文件）转换为与 pnpm 相同的锁文件模型
+//
+// bun.lock 是允许注释与尾随逗号的 JSON（JSONC）：
+//   workspaces  根项目（""）与各 workspace 声明的依赖     → importers（"" 记为 "."）
+//   packages    以安装路径为 key 的数组，第一项为 name@版本或来源  → packages 节点中以 name@version 为 key 的条目
+// 安装路径与 npm 的 node_modules 嵌套相同，只是以 / 连接包名，例如 ui/lodash 是 workspace ui 下的 lodash；
+// 依赖范围按 Node 的模块查找规则逐级向上解析为实际安装的版本。
+//
+// packages 中数组的写法：
+//   registry 包   ["name@1.0.0", "<registry，默认为空>", { dependencies... }, "sha512-..."]
+//   git / github  ["name@github:owner/repo#ref", { dependencies... }, "<commit>"]
+//   tarball/本地  ["name@https://...tgz" 或 "name@file:path", { dependencies... }]
+//   workspace     ["name@workspace:packages/ui"]
+
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+use crate::ecosystem::{Ecosystem, has_top_level_key};
+use crate::lockfile::{DependencyInfo, Importer, Lockfile, PackageInfo, Resolution};
+
+#[derive(Deserialize)]
+struct RawBunLock {
+    #[serde(rename = "lockfileVersion")]
+    lockfile_version: u32,
+
+    #[serde(default)]
+    workspaces: BTreeMap<String, RawWorkspace>,
+
+    #[serde(default)]
+    packages: BTreeMap<String, Vec<serde_json::Value>>,
+}
+
+#[derive(Deserialize)]
+struct RawWorkspace {
+    #[serde(default)]
+    name: Option<String>,
+
+    #[serde(default)]
+    dependencies: BTreeMap<String, String>,
+
+    #[serde(default)]
+    #[serde(rename = "devDependencies")]
+    dev_dependencies: BTreeMap<String, String>,
+
+    #[serde(default)]
+    #[serde(rename = "optionalDependencies")]
+    optional_dependencies: BTreeMap<String, String>,
+}
+
+#[derive(Default, Deserialize)]
+struct RawInfo {
+    #[serde(default)]
+    dependencies: BTreeMap<String, String>,
+
+    #[serde(default)]
+    #[serde(rename = "optionalDependencies")]
+    optional_dependencies: BTreeMap<String, String>,
+
+    #[serde(default)]
+    #[serde(rename = "peerDependencies")]
+    peer_dependencies: BTreeMap<String, String>,
+}
+
+/// packages 中的一个安装位置
+struct Entry {
+    name: String,
+    /// name@ 之后的部分：版本号、workspace:路径、git/tarball 地址等
+    spec: String,
+    info: RawInfo,
+    resolution: Option<Resolution>,
+}
+
+/// Bun 1.2 起的文本锁文件 bun.lock
+pub struct Bun;
+
+impl Ecosystem for Bun {
+    fn name(&self) -> &'static str {
+        "bun"
+    }
+
+    fn file_names(&self) -> &'static [&'static str] {
+        &["bun.lock"]
+    }
+
+    fn sniff(&self, content: &str) -> bool {
+        is_bun_lock(content)
+    }
+
+    fn parse(&self, content: &str) -> Result<Lockfile> {
+        parse(content)
+    }
+}
+
+/// 内容是否为 bun.lock：JSON 顶层带有 workspaces 节点（package-lock.json 的 workspaces 只出现在根项目条目中）
+pub fn is_bun_lock(content: &str) -> bool {
+    skip_leading_comments(content).starts_with('{') && has_top_level_key(content, "workspaces")
+}
+
+fn skip_leading_comments(content: &str) -> &str {
+    let mut rest = content.trim_start();
+    loop {
+        if rest.starts_with("//") {
+            rest = rest.split_once('\n').map_or("", |(_, after)| after).trim_start();
+        } else if let Some(comment) = rest.strip_prefix("/*") {
+            rest = comment.split_once("*/").map_or("", |(_, after)| after).trim_start();
+        } else {
+            return rest;
+        }
+    }
+}
+
+pub fn parse(content: &str) -> Result<Lockfile> {
+    let raw: RawBunLock = serde_json::from_str(&to_json(content)).context("解析 bun.lock 文件失败")?;
+
+    let mut entries = BTreeMap::new();
+    for (path, value) in &raw.packages {
+        let entry = parse_entry(value).with_context(|| format!("bun.lock 中 packages.\"{}\" 的格式无法识别", path))?;
+        entries.insert(path.as_str(), entry);
+    }
+
+    let mut importers = BTreeMap::new();
+    for (path, workspace) in &raw.workspaces {
+        // workspace 下嵌套安装的包以 workspace 的包名为前缀
+        let base = if path.is_empty() { "" } else { workspace.name.as_deref().unwrap_or(path) };
+        let importer = Importer {
+            dependencies: resolve_importer_deps(&entries, base, &workspace.dependencies),
+            dev_dependencies: resolve_importer_deps(&entries, base, &workspace.dev_dependencies),
+            optional_dependencies: resolve_importer_deps(&entries, base, &workspace.optional_dependencies),
+        };
+        let path = if path.is_empty() { "." } else { path.as_str() };
+        importers.insert(path.to_string(), importer);
+    }
+
+    let mut packages = BTreeMap::new();
+    for (path, entry) in &entries {
+        let Some(ref resolution) = entry.resolution else {
+            continue;
+        };
+        packages.entry(format!("{}@{}", entry.name, entry.spec)).or_insert_with(|| PackageInfo {
+            resolution: resolution.clone(),
+            name: Some(entry.name.clone()),
+            version: Some(entry.spec.clone()),
+            peer_dependencies: entry.info.peer_dependencies.clone(),
+            dependencies: resolve_deps(&entries, path, &entry.info.dependencies),
+            optional_dependencies: resolve_deps(&entries, path, &entry.info.optional_dependencies),
+            dev_dependencies: BTreeMap::new(),
+            requires_build: None,
+        });
+    }
+
+    Ok(Lockfile::new(format!("bun {}", raw.lockfile_version), importers, packages, BTreeMap::new()))
+}
+
+fn parse_entry(value: &[serde_json::Value]) -> Result<Entry> {
+    let Some(ident) = value.first().and_then(|v| v.as_str()) else {
+        bail!("缺少 name@版本");
+    };
+    let scoped = usize::from(ident.starts_with('@'));
+    let Some(pos) = ident[scoped..].find('@').map(|pos| pos + scoped) else {
+        bail!("'{}' 不是 name@版本 的形式", ident);
+    };
+    let (name, spec) = (&ident[..pos], &ident[pos + 1..]);
+
+    // 依赖信息是数组中第一个对象，registry 包位于第三项，其他来源位于第二项
+    let info = value
+        .iter()
+        .find(|v| v.is_object())
+        .map(|v| serde_json::from_value(v.clone()))
+        .transpose()?
+        .unwrap_or_default();
+    let text = |index: usize| value.get(index).and_then(|v| v.as_str()).filter(|s| !s.is_empty());
+
+    let resolution = if spec.starts_with("workspace:") || spec.starts_with("link:") {
+        None
+    } else if let Some(directory) = spec.strip_prefix("file:") {
+        Some(Resolution::Directory { directory: directory.to_string() })
+    } else if let Some(repo) = spec.strip_prefix("github:") {
+        let (repo, reference) = repo.split_once('#').unwrap_or((repo, ""));
+        // 第三项形如 owner-repo-<commit>
+        let commit = text(2).and_then(|resolved| resolved.rsplit('-').next());
+        Some(Resolution::Git {
+            repo: format!("https://github.com/{}", repo),
+            commit: commit.unwrap_or(reference).to_string(),
+        })
+    } else if let Some(repo) = spec.strip_prefix("git+") {
+        let (repo, reference) = repo.split_once('#').unwrap_or((repo, ""));
+        Some(Resolution::Git {
+            repo: repo.to_string(),
+            commit: text(2).unwrap_or(reference).to_string(),
+        })
+    } else if spec.starts_with("http://") || spec.starts_with("https://") {
+        Some(Resolution::Tarball { tarball: spec.to_string(), integrity: None })
+    } else {
+        let integrity = text(3).map(str::to_string);
+        match text(1) {
+            None => Some(Resolution::Registry { integrity: integrity.unwrap_or_default() }),
+            Some(registry) => {
+                let basename = name.rsplit('/').next().unwrap_or(name);
+                Some(Resolution::Tarball {
+                    tarball: format!("{}/{}/-/{}-{}.tgz", registry.trim_end_matches('/'), name, basename, spec),
+                    integrity,
+                })
+            }
+        }
+    };
+
+    Ok(Entry {
+        name: name.to_string(),
+        spec: spec.to_string(),
+        info,
+        resolution,
+    })
+}
+
+/// 安装路径的上一级：ui/@scope/a → ui，顶层的包返回空字符串
+fn parent_path(path: &str) -> &str {
+    let Some(pos) = path.rfind('/') else {
+        return "";
+    };
+    // 作用域包名本身带有 /，需要再向前跳过一级
+    match path[..pos].rfind('/') {
+        Some(start) if path[start + 1..].starts_with('@') => &path[..start],
+        None if path.starts_with('@') => "",
+        _ => &path[..pos],
+    }
+}
+
+/// 从 base 开始逐级向上查找依赖 name 的安装位置，与 Node 的 require 查找顺序一致
+fn lookup<'a>(entries: &'a BTreeMap<&str, Entry>, base: &str, name: &str) -> Option<&'a Entry> {
+    let mut base = base;
+    loop {
+        let candidate = if base.is_empty() { name.to_string() } else { format!("{}/{}", base, name) };
+        if let Some(entry) = entries.get(candidate.as_str()) {
+            return Some(entry);
+        }
+        if base.is_empty() {
+            return None;
+        }
+        base = parent_path(base);
+    }
+}
+
+/// 依赖解析后的值，写法与 pnpm 锁文件一致：版本号、别名 name@version 或 link:路径；
+/// 可选依赖在当前平台未安装时返回 None
+fn resolved_value(entries: &BTreeMap<&str, Entry>, base: &str, name: &str) -> Option<String> {
+    let entry = lookup(entries, base, name)?;
+    if let Some(path) = entry.spec.strip_prefix("workspace:").or_else(|| entry.spec.strip_prefix("link:")) {
+        return Some(format!("link:{}", path));
+    }
+    if entry.name != name {
+        return Some(format!("{}@{}", entry.name, entry.spec));
+    }
+    Some(entry.spec.clone())
+}
+
+fn resolve_deps(entries: &BTreeMap<&str, Entry>, base: &str, deps: &BTreeMap<String, String>) -> BTreeMap<String, String> {
+    deps.keys()
+        .filter_map(|name| Some((name.clone(), resolved_value(entries, base, name)?)))
+        .collect()
+}
+
+fn resolve_importer_deps(
+    entries: &BTreeMap<&str, Entry>,
+    base: &str,
+    deps: &BTreeMap<String, String>,
+) -> BTreeMap<String, DependencyInfo> {
+    deps.iter()
+        .filter_map(|(name, specifier)| {
+            let version = resolved_value(entries, base, name)?;
+            Some((name.clone(), DependencyInfo { specifier: specifier.clone(), version }))
+        })
+        .collect()
+}
+
+/// JSONC 转换为标准 JSON：去掉字符串以外的 // 与 /* */ 注释，以及 } 或 ] 之前的尾随逗号
+pub fn to_json(content: &str) -> String {
+    let chars: Vec<char> = content.chars().collect();
+    let mut out = String::with_capacity(content.len());
+    let mut i = 0;
+    let mut in_string = false;
+    while i < chars.len() {
+        let c = chars[i];
+        if in_string {
+            out.push(c);
+            match c {
+                '\\' if i + 1 < chars.len() => {
+                    out.push(chars[i + 1]);
+                    i += 1;
+                }
+                '"' => in_string = false,
+                _ => {}
+            }
+            i += 1;
+            continue;
+        }
+        match (c, chars.get(i + 1)) {
+            ('"', _) => {
+                in_string = true;
+                out.push(c);
+                i += 1;
+            }
+            ('/', Some('/')) => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            ('/', Some('*')) => {
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    i += 1;
+                }
+                i += 2;
+            }
+            (',', _) => {
+                // 逗号之后（跳过空白与注释）紧跟 } 或 ] 时为尾随逗号
+                if !matches!(next_significant(&chars, i + 1), Some('}' | ']')) {
+                    out.push(c);
+                }
+                i += 1;
+            }
+            _ => {
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+    out
+}
+
+fn next_significant(chars: &[char], mut i: usize) -> Option<char> {
+    while i < chars.len() {
+        match (chars[i], chars.get(i + 1)) {
+            (c, _) if c.is_whitespace() => i += 1,
+            ('/', Some('/')) => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            ('/', Some('*')) => {
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    i += 1;
+                }
+                i += 2;
+            }
+            (c, _) => return Some(c),
+        }
+    }
+    None
+}
@@ -0,0 +1,87 @@
+// bundle 子命令：预先拉取检查所需的远程输入写入离线包，在无网络的机器上复现相同的检查
+
+use anyhow::Result;
+use std::collections::BTreeSet;
+
+use npm_package_check::lockfile::Lockfile;
+
+use crate::http::{self, Bundle, Client};
+use crate::registry::{DownloadsApi, Registry};
+use crate::rekor::Rekor;
+
+pub struct CreateOptions<'a> {
+    pub lockfile: &'a str,
+    pub output: &'a str,
+    /// 一并拉取的风险清单地址
+    pub feeds: &'a [String],
+    /// 下载风险清单时以 Bearer 发送的 token
+    pub feed_token: Option<&'a str>,
+    pub registry: &'a str,
+    /// 为 Some 时拉取下载量
+    pub downloads_api: Option<&'a str>,
+    /// 为 Some 时查询 Rekor 记录
+    pub rekor_url: Option<&'a str>,
+    pub verbose: bool,
+}
+
+pub fn run_create(lock_data: &Lockfile, client: &Client, options: &CreateOptions) -> Result<()> {
+    let mut client = client.clone();
+    let recorder = client.record(Bundle {
+        created_at: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        lockfile: options.lockfile.to_string(),
+        ..Bundle::default()
+    });
+
+    // 包不存在（404）等结果同样会被记录，只有网络请求本身失败的条目在离线时不可用
+    let mut failed = Vec::new();
+    let mut check = |label: &str, result: Result<()>| {
+        if let Err(e) = result
+            && e.chain().any(|cause| cause.is::<ureq::Error>())
+        {
+            failed.push(format!("{}: {:#}", label, e));
+        }
+    };
+
+    for feed in options.feeds {
+        check(feed, http::get_text_with(&client, feed, &http::bearer(options.feed_token)).map(drop));
+    }
+
+    // 离线检查可能用到的 registry 元数据：packument（发布状态、健康度）与 dist-tags
+    let names = crate::all_package_names(lock_data);
+    let registry = Registry::new(options.registry, &client);
+    let downloads = options.downloads_api.map(|api| DownloadsApi::new(api, &client));
+    println!("📦 拉取 {} 个包的 registry 元数据", names.len());
+    for name in &names {
+        if options.verbose {
+            println!("   查询: {}", name);
+        }
+        check(name, registry.packument(name).map(drop));
+        check(name, registry.dist_tags(name).map(drop));
+        if let Some(ref downloads) = downloads {
+            check(name, downloads.weekly_downloads(name).map(drop));
+        }
+    }
+
+    if let Some(rekor_url) = options.rekor_url {
+        let rekor = Rekor::new(rekor_url, &client);
+        let integrities: BTreeSet<String> = lock_data
+            .packages()
+            .filter_map(|record| record.resolution?.integrity().map(str::to_string))
+            .collect();
+        println!("🔏 查询 {} 个 integrity 在 Rekor 中的记录", integrities.len());
+        for integrity in &integrities {
+            check(integrity, rekor.entries_for(integrity).map(drop));
+        }
+    }
+
+    let bundle = recorder.lock().unwrap();
+    bundle.write(options.output)?;
+    println!("✅ 离线包已写入: {}（{} 条响应）", options.output, bundle.responses.len());
+    if !failed.is_empty() {
+        println!("⚠️ 以下请求失败，离线检查时将无法使用 ({}):", failed.len());
+        for item in &failed {
+            println!("   - {}", item);
+        }
+    }
+    Ok(())
+}
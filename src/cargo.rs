@@ -0,0 +1,176 @@
+// Cargo.lock 转换为与 pnpm 相同的锁文件模型，Rust 项目可以沿用同一份风险清单与检查流程
+//
+// Cargo.lock 是 TOML，每个 [[package]] 记录一个锁定的 crate：
+//   name / version   包名与版本号
+//   source           来源：registry+<索引地址>、sparse+<索引地址> 或 git+<仓库地址>?<branch|tag|rev>#<提交>，
+//                    workspace 成员与 path 依赖没有 source
+//   checksum         registry 包的 SHA-256（十六进制），version 1 的锁文件记录在 [metadata] 中，不做读取
+//   dependencies     依赖列表，写作 "name"、"name version" 或 "name version (source)"，
+//                    同名 crate 只有一个版本时只写名称
+// 转换规则：
+//   没有 source 的包            → importers，以 crate 名为 key（锁文件不记录路径）
+//   其他包                      → packages 节点中以 name@version 为 key 的条目
+//   依赖列表                    → 依赖名到锁定版本的映射，依赖 workspace 成员时写作 link:<crate 名>
+// Cargo.lock 不区分普通依赖、dev-dependencies 与 build-dependencies，全部记为 dependencies；
+// 同一个包依赖同名 crate 的多个版本（Cargo.toml 中改名引入）时，锁文件不记录改名后的名称，
+// 第一个版本之外的依赖以 "name version" 为依赖名、按别名（name@version）记录
+
+use anyhow::{Context, Result};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::ecosystem::Ecosystem;
+use crate::lockfile::{DependencyInfo, Importer, Lockfile, PackageInfo, Resolution};
+
+/// crates.io 的索引地址（git 索引与 sparse 索引），视为默认 registry
+const CRATES_IO: &[&str] = &[
+    "registry+https://github.com/rust-lang/crates.io-index",
+    "sparse+https://index.crates.io/",
+];
+
+#[derive(Deserialize)]
+struct RawCargoLock {
+    #[serde(default)]
+    version: Option<u32>,
+
+    #[serde(default)]
+    package: Vec<RawPackage>,
+}
+
+#[derive(Deserialize)]
+struct RawPackage {
+    name: String,
+    version: String,
+
+    #[serde(default)]
+    source: Option<String>,
+
+    #[serde(default)]
+    checksum: Option<String>,
+
+    #[serde(default)]
+    dependencies: Vec<String>,
+}
+
+/// Cargo.lock
+pub struct Cargo;
+
+impl Ecosystem for Cargo {
+    fn name(&self) -> &'static str {
+        "cargo"
+    }
+
+    fn file_names(&self) -> &'static [&'static str] {
+        &["Cargo.lock"]
+    }
+
+    fn sniff(&self, content: &str) -> bool {
+        is_cargo_lock(content)
+    }
+
+    fn parse(&self, content: &str) -> Result<Lockfile> {
+        parse(content)
+    }
+}
+
+/// 内容是否为 Cargo.lock：带有 [[package]] 表的 TOML
+pub fn is_cargo_lock(content: &str) -> bool {
+    content.lines().any(|line| line.trim_end() == "[[package]]")
+}
+
+pub fn parse(content: &str) -> Result<Lockfile> {
+    let raw: RawCargoLock = toml::from_str(content).context("解析 Cargo.lock 文件失败")?;
+
+    let mut by_name: HashMap<&str, Vec<&RawPackage>> = HashMap::new();
+    for package in &raw.package {
+        by_name.entry(package.name.as_str()).or_default().push(package);
+    }
+    // 依赖列表中的一项对应的 (包名, 锁定版本)，workspace 成员的版本写作 link:<crate 名>
+    let resolve = |dep: &str| -> Option<(String, String)> {
+        let mut parts = dep.split_whitespace();
+        let name = parts.next()?;
+        let candidates = by_name.get(name)?;
+        let target = match parts.next() {
+            Some(version) => candidates.iter().find(|package| package.version == version)?,
+            None => candidates.first()?,
+        };
+        let version = match target.source {
+            Some(_) => target.version.clone(),
+            None => format!("link:{}", target.name),
+        };
+        Some((name.to_string(), version))
+    };
+    let dependencies = |package: &RawPackage| -> BTreeMap<String, String> {
+        let mut deps = BTreeMap::new();
+        for (name, version) in package.dependencies.iter().filter_map(|dep| resolve(dep)) {
+            if deps.contains_key(&name) {
+                deps.insert(format!("{} {}", name, version), format!("{}@{}", name, version));
+            } else {
+                deps.insert(name, version);
+            }
+        }
+        deps
+    };
+
+    let mut importers = BTreeMap::new();
+    let mut packages = BTreeMap::new();
+    for package in &raw.package {
+        let Some(ref source) = package.source else {
+            let dependencies = dependencies(package)
+                .into_iter()
+                .map(|(name, version)| (name, DependencyInfo { specifier: version.clone(), version }))
+                .collect();
+            let importer = Importer {
+                dependencies,
+                dev_dependencies: BTreeMap::new(),
+                optional_dependencies: BTreeMap::new(),
+            };
+            importers.entry(package.name.clone()).or_insert(importer);
+            continue;
+        };
+        packages
+            .entry(format!("{}@{}", package.name, package.version))
+            .or_insert_with(|| PackageInfo {
+                resolution: resolution(source, package.checksum.as_deref()),
+                name: Some(package.name.clone()),
+                version: Some(package.version.clone()),
+                peer_dependencies: BTreeMap::new(),
+                dependencies: dependencies(package),
+                optional_dependencies: BTreeMap::new(),
+                dev_dependencies: BTreeMap::new(),
+                requires_build: None,
+            });
+    }
+
+    let version = format!("cargo {}", raw.version.unwrap_or(1));
+    Ok(Lockfile::new(version, importers, packages, BTreeMap::new()))
+}
+
+/// source 对应的来源：crates.io 为默认 registry，其他 registry 记录索引地址
+fn resolution(source: &str, checksum: Option<&str>) -> Resolution {
+    let integrity = checksum.map(integrity);
+    if CRATES_IO.contains(&source) {
+        return Resolution::Registry { integrity: integrity.unwrap_or_default() };
+    }
+    if let Some(git) = source.strip_prefix("git+") {
+        let (url, commit) = git.split_once('#').unwrap_or((git, ""));
+        let repo = url.split('?').next().unwrap_or(url);
+        return Resolution::Git { repo: repo.to_string(), commit: commit.to_string() };
+    }
+    let index = source.split_once('+').map_or(source, |(_, url)| url);
+    Resolution::Tarball { tarball: index.to_string(), integrity }
+}
+
+/// 十六进制的 SHA-256 转换为 sha256-<base64> 形式的完整性校验值，无法解码时原样返回
+fn integrity(checksum: &str) -> String {
+    let bytes: Option<Vec<u8>> = (0..checksum.len())
+        .step_by(2)
+        .map(|i| checksum.get(i..i + 2).and_then(|pair| u8::from_str_radix(pair, 16).ok()))
+        .collect();
+    match bytes {
+        Some(bytes) if !bytes.is_empty() => format!("sha256-{}", STANDARD.encode(bytes)),
+        _ => checksum.to_string(),
+    }
+}
@@ -0,0 +1,50 @@
+// 输出排序：控制台输出与报告中的包名、importer 路径、项目名等按 Unicode 排序算法（CLDR 排序规则）排序，
+// 而不是按字节，作用域包名、importer 路径中的中文项目名与 emoji 的顺序在各平台上一致，只差大小写的名称相邻
+//
+// 默认使用与语言无关的 CLDR 根排序规则；--sort-locale 指定语言区域（BCP 47），例如 zh 按拼音、
+// zh-u-co-stroke 按笔画、sv 按瑞典语字母表。排序规则认为相等的文本再按字节比较，保证顺序确定。
+// 版本号、摘要等 ASCII 标识不经过这里，仍按原有方式排序
+
+use anyhow::{Result, anyhow};
+use icu_collator::{Collator, CollatorOptions, Strength};
+use icu_provider::DataLocale;
+use std::cmp::Ordering;
+use std::path::Path;
+use std::sync::OnceLock;
+
+static COLLATOR: OnceLock<Collator> = OnceLock::new();
+
+fn new_collator(locale: &DataLocale) -> Result<Collator> {
+    let mut options = CollatorOptions::new();
+    options.strength = Some(Strength::Tertiary);
+    Collator::try_new(locale, options).map_err(|e| anyhow!("无法加载语言区域 '{}' 的排序规则: {}", locale, e))
+}
+
+/// 按 --sort-locale 设置排序规则，未指定时使用根排序规则；应在产生任何输出之前调用
+pub fn init(locale: Option<&str>) -> Result<()> {
+    let Some(locale) = locale else {
+        return Ok(());
+    };
+    let parsed: DataLocale = locale
+        .parse()
+        .map_err(|_| anyhow!("无效的语言区域 '{}'，应为 BCP 47 格式（例如 zh、zh-u-co-stroke、en-US）", locale))?;
+    let _ = COLLATOR.set(new_collator(&parsed)?);
+    Ok(())
+}
+
+fn collator() -> &'static Collator {
+    COLLATOR.get_or_init(|| new_collator(&DataLocale::default()).expect("内置的根排序规则应当可以加载"))
+}
+
+pub fn compare(a: &str, b: &str) -> Ordering {
+    collator().compare(a, b).then_with(|| a.cmp(b))
+}
+
+/// 按 key 取出的文本排序（稳定排序）
+pub fn sort_by_key<T>(items: &mut [T], key: impl Fn(&T) -> &str) {
+    items.sort_by(|a, b| compare(key(a), key(b)));
+}
+
+pub fn sort_paths<P: AsRef<Path>>(paths: &mut [P]) {
+    paths.sort_by(|a, b| compare(&a.as_ref().to_string_lossy(), &b.as_ref().to_string_lossy()));
+}
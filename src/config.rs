@@ -0,0 +1,62 @@
+// 配置文件：为命令行参数提供默认值
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+
+use npm_package_check::batch::Columns;
+use npm_package_check::lockfile::LockfileFormat;
+
+use crate::VerdictMode;
+use crate::matching::MatchMode;
+use crate::watch::NotifyMode;
+
+pub const DEFAULT_CONFIG_FILE: &str = "npm_package_check.yaml";
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub file: Option<String>,
+    pub lockfile_format: Option<LockfileFormat>,
+    pub batch: Option<String>,
+    /// 批量文件（CSV 或制表符分隔）各列的表头名称
+    pub batch_columns: Option<Columns>,
+    pub output: Option<String>,
+    pub verdict_mode: Option<VerdictMode>,
+    pub match_mode: Option<MatchMode>,
+    pub policy: Option<String>,
+    /// 本地使用统计文件
+    pub usage_stats: Option<String>,
+    /// 输出排序使用的语言区域
+    pub sort_locale: Option<String>,
+    /// mirrors 子命令允许的镜像
+    pub allowed_mirrors: Vec<String>,
+    /// watch-feed 使用的风险清单地址
+    pub feed: Option<String>,
+    /// watch-feed 检查的锁文件或目录
+    pub lockfiles: Vec<String>,
+    /// watch-feed 与 --webhook 新增命中时通知的 webhook 地址
+    pub webhook: Option<String>,
+    /// watch-feed 与 --webhook 的通知粒度
+    pub notify: Option<NotifyMode>,
+    /// watch-feed 与 --notify-state 已通知命中的记录文件
+    pub state: Option<String>,
+}
+
+impl Config {
+    /// 读取显式指定的配置文件；未指定时尝试当前目录下的默认配置文件
+    pub fn load(explicit: Option<&str>) -> Result<Option<Self>> {
+        let path = match explicit {
+            Some(path) => path,
+            None if Path::new(DEFAULT_CONFIG_FILE).exists() => DEFAULT_CONFIG_FILE,
+            None => return Ok(None),
+        };
+
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("无法读取配置文件 '{}'", path))?;
+        let config = serde_yaml::from_str(&content)
+            .with_context(|| format!("解析配置文件 '{}' 失败", path))?;
+        Ok(Some(config))
+    }
+}
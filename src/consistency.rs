@@ -0,0 +1,68 @@
+// consistency 子命令：检查 v9 锁文件中 snapshots 与 packages 节点的条目是否一一对应
+
+use anyhow::Result;
+use std::collections::BTreeSet;
+
+use npm_package_check::lockfile::Lockfile;
+use npm_package_check::package_key;
+
+/// snapshot key 去掉 peer 后缀即为 packages 节点的 key：react-dom@18.3.1(react@18.3.1) → react-dom@18.3.1，
+/// 按语法拆分后重新拼接，无法拆分的 key 原样返回
+fn packages_key(snapshot_key: &str) -> String {
+    package_key::parse(snapshot_key).map_or_else(|| snapshot_key.to_string(), |package| package.id())
+}
+
+pub fn run_consistency(lock_data: &Lockfile, verbose: bool) -> Result<()> {
+    // v5/v6 的依赖关系记录在 packages 节点中，没有 snapshots 节点
+    if lock_data.snapshots.is_empty() {
+        println!(
+            "ℹ️ lockfileVersion {} 的锁文件没有 snapshots 节点，无需检查",
+            lock_data.lockfile_version
+        );
+        return Ok(());
+    }
+
+    let snapshot_only: Vec<&str> = lock_data
+        .snapshots
+        .keys()
+        .filter(|key| !lock_data.packages.contains_key(&packages_key(key)))
+        .map(String::as_str)
+        .collect();
+    let referenced: BTreeSet<String> = lock_data.snapshots.keys().map(|key| packages_key(key)).collect();
+    let packages_only: Vec<&str> = lock_data
+        .packages
+        .keys()
+        .map(String::as_str)
+        .filter(|key| !referenced.contains(*key))
+        .collect();
+
+    println!(
+        "🔍 snapshots 节点 {} 个条目，packages 节点 {} 个条目\n",
+        lock_data.snapshots.len(),
+        lock_data.packages.len()
+    );
+    print_section("❓ 只在 snapshots 中出现（缺少 packages 条目，安装时无法获取 resolution）", &snapshot_only, verbose);
+    print_section("❓ 只在 packages 中出现（没有任何 snapshot 引用）", &packages_only, verbose);
+
+    if snapshot_only.is_empty() && packages_only.is_empty() {
+        println!("✅ snapshots 与 packages 节点一一对应");
+        return Ok(());
+    }
+    println!("⚠️ 锁文件不一致会导致安装失败，也可能是锁文件被手动篡改，建议重新生成并核对变更来源");
+    std::process::exit(1);
+}
+
+fn print_section(title: &str, keys: &[&str], verbose: bool) {
+    if keys.is_empty() {
+        return;
+    }
+    println!("{} ({}):", title, keys.len());
+    let shown = if verbose { keys.len() } else { keys.len().min(20) };
+    for key in &keys[..shown] {
+        println!("   - {}", key);
+    }
+    if shown < keys.len() {
+        println!("   … 另有 {} 条，使用 --verbose 查看全部", keys.len() - shown);
+    }
+    println!();
+}
@@ -0,0 +1,70 @@
+// 日期规范化：上游清单中的 Detection Date 格式不一，统一为 YYYY-MM-DD 后再写入报告
+
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, NaiveDateTime, Offset, Utc};
+
+// 能够无歧义识别的日期格式；01/02/2025 这类日月顺序不确定的写法不做转换
+const DATE_FORMATS: &[&str] = &[
+    "%Y-%m-%d",
+    "%Y/%m/%d",
+    "%Y.%m.%d",
+    "%Y%m%d",
+    "%d.%m.%Y",
+    "%b %d, %Y",
+    "%B %d, %Y",
+    "%d %b %Y",
+    "%d %B %Y",
+];
+const DATETIME_FORMATS: &[&str] = &["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S", "%Y/%m/%d %H:%M:%S"];
+
+/// 解析 --timezone：UTC、local 或 +08:00 / -0500 形式的偏移
+pub fn parse_timezone(value: &str) -> Result<FixedOffset, String> {
+    match value.to_ascii_lowercase().as_str() {
+        "utc" | "z" => return Ok(Utc.fix()),
+        "local" => return Ok(Local::now().offset().fix()),
+        _ => {}
+    }
+    let invalid = || format!("无效的时区 '{}'，应为 UTC、local 或 +08:00 形式的偏移", value);
+    let (sign, rest) = match value.as_bytes().first() {
+        Some(b'+') => (1, &value[1..]),
+        Some(b'-') => (-1, &value[1..]),
+        _ => return Err(invalid()),
+    };
+    let digits: String = rest.chars().filter(|c| *c != ':').collect();
+    let (hours, minutes) = match digits.len() {
+        2 => (digits.parse::<i32>().ok(), Some(0)),
+        4 => (digits[..2].parse::<i32>().ok(), digits[2..].parse::<i32>().ok()),
+        _ => (None, None),
+    };
+    let (Some(hours), Some(minutes)) = (hours, minutes) else {
+        return Err(invalid());
+    };
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60)).ok_or_else(invalid)
+}
+
+/// 将各种写法的日期规范化为 YYYY-MM-DD；带时间的写法按 timezone 换算后取日期
+///（不带偏移的时间视为 UTC），无法识别时原样返回
+pub fn normalize_date(raw: &str, timezone: Option<FixedOffset>) -> String {
+    let raw = raw.trim();
+    let timezone = timezone.unwrap_or(Utc.fix());
+    let date = DateTime::parse_from_rfc3339(raw)
+        .or_else(|_| DateTime::parse_from_rfc2822(raw))
+        .map(|d| d.with_timezone(&timezone).date_naive())
+        .ok()
+        .or_else(|| {
+            DATETIME_FORMATS
+                .iter()
+                .find_map(|format| NaiveDateTime::parse_from_str(raw, format).ok())
+                .map(|d| d.and_utc().with_timezone(&timezone).date_naive())
+        })
+        .or_else(|| DATE_FORMATS.iter().find_map(|format| NaiveDate::parse_from_str(raw, format).ok()));
+    match date {
+        Some(date) => date.format("%Y-%m-%d").to_string(),
+        None => raw.to_string(),
+    }
+}
+
+/// 报告中的时间戳：指定时区时带偏移输出，否则为 UTC
+pub fn timestamp(timezone: Option<FixedOffset>) -> String {
+    let now = Utc::now().with_timezone(&timezone.unwrap_or(Utc.fix()));
+    now.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+}
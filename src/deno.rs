@@ -0,0 +1,251 @@
+// deno.lock 中的 npm 依赖转换为与 pnpm 相同的锁文件模型
+//
+// deno.lock 是 JSON，npm 包的记录位置随版本不同：
+//   version 3   packages.specifiers 与 packages.npm，依赖写作 {名称: "name@版本"}
+//   version 4+  顶层的 specifiers 与 npm，依赖写作 ["名称"]，同名包有多个版本时写作 "name@版本"
+// specifiers 把导入时的规格（npm:react@^18.2.0）映射为锁定的版本，npm 中以 name@版本为 key 记录每个包，
+// 带 peer 依赖的包在版本后以 _ 连接 peer 组合，例如 @ant-design/icons@4.8.3_react@18.2.0，
+// peer 的作用域包名中的 / 写作 +。
+// workspace.dependencies（以及 packageJson.dependencies）中的 npm: 规格为根项目的直接依赖，
+// workspace.members 中的各成员对应各自的 importer；没有 workspace 节点时，全部 npm: 规格都视为根项目的直接依赖。
+// jsr: 与远程模块不属于 npm 包，不参与检查。
+
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::ecosystem::{Ecosystem, has_top_level_key};
+use crate::lockfile::{DependencyInfo, Importer, Lockfile, PackageInfo, Resolution};
+
+#[derive(Deserialize)]
+struct RawVersion {
+    version: String,
+}
+
+#[derive(Deserialize)]
+struct RawDenoLock {
+    /// version 3 的 specifiers 与 npm 位于 packages 之下
+    #[serde(default)]
+    packages: Option<RawPackages>,
+
+    #[serde(flatten)]
+    top: RawPackages,
+
+    #[serde(default)]
+    workspace: Option<RawMember>,
+}
+
+#[derive(Default, Deserialize)]
+struct RawPackages {
+    #[serde(default)]
+    specifiers: BTreeMap<String, String>,
+
+    #[serde(default)]
+    npm: BTreeMap<String, RawNpmEntry>,
+}
+
+#[derive(Default, Deserialize)]
+struct RawMember {
+    #[serde(default)]
+    dependencies: Vec<String>,
+
+    #[serde(default)]
+    #[serde(rename = "packageJson")]
+    package_json: Option<RawPackageJson>,
+
+    #[serde(default)]
+    members: BTreeMap<String, RawMember>,
+}
+
+#[derive(Default, Deserialize)]
+struct RawPackageJson {
+    #[serde(default)]
+    dependencies: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct RawNpmEntry {
+    #[serde(default)]
+    integrity: Option<String>,
+
+    /// 非默认 registry 的包（version 5）
+    #[serde(default)]
+    tarball: Option<String>,
+
+    #[serde(default)]
+    dependencies: RawDeps,
+
+    #[serde(default)]
+    #[serde(rename = "optionalDependencies")]
+    optional_dependencies: RawDeps,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawDeps {
+    /// version 3：{名称: "name@版本"}
+    Map(BTreeMap<String, String>),
+    /// version 4+：["名称"] 或 ["name@版本"]
+    List(Vec<String>),
+}
+
+impl Default for RawDeps {
+    fn default() -> Self {
+        RawDeps::List(Vec::new())
+    }
+}
+
+/// deno.lock 中的 npm 依赖
+pub struct Deno;
+
+impl Ecosystem for Deno {
+    fn name(&self) -> &'static str {
+        "deno"
+    }
+
+    fn file_names(&self) -> &'static [&'static str] {
+        &["deno.lock"]
+    }
+
+    fn sniff(&self, content: &str) -> bool {
+        is_deno_lock(content)
+    }
+
+    fn parse(&self, content: &str) -> Result<Lockfile> {
+        parse(content)
+    }
+}
+
+/// 内容是否为 deno.lock：JSON 顶层有 version 而没有 lockfileVersion
+pub fn is_deno_lock(content: &str) -> bool {
+    content.trim_start().starts_with('{')
+        && has_top_level_key(content, "version")
+        && !has_top_level_key(content, "lockfileVersion")
+}
+
+pub fn parse(content: &str) -> Result<Lockfile> {
+    let RawVersion { version } = serde_json::from_str(content).context("解析 deno.lock 文件失败")?;
+    if version.parse::<u32>().is_ok_and(|version| version < 3) {
+        bail!("deno.lock (version {}) 的格式过旧，请使用 Deno 1.40 及以上版本重新生成", version);
+    }
+    let raw: RawDenoLock = serde_json::from_str(content).context("解析 deno.lock 文件失败")?;
+    let RawPackages { specifiers, npm } = match raw.packages {
+        Some(packages) if raw.top.npm.is_empty() && raw.top.specifiers.is_empty() => packages,
+        _ => raw.top,
+    };
+
+    // 依赖只写名称时按名称查找唯一的版本
+    let versions: HashMap<&str, &str> = npm.keys().filter_map(|key| split_key(key)).collect();
+    let mut packages = BTreeMap::new();
+    for (key, entry) in &npm {
+        let (name, version) = split_key(key).with_context(|| format!("deno.lock 中 npm.\"{}\" 不是 name@版本 的形式", key))?;
+        let resolution = match entry.tarball {
+            Some(ref tarball) => Resolution::Tarball { tarball: tarball.clone(), integrity: entry.integrity.clone() },
+            None => Resolution::Registry { integrity: entry.integrity.clone().unwrap_or_default() },
+        };
+        packages.insert(
+            key.clone(),
+            PackageInfo {
+                resolution,
+                name: Some(name.to_string()),
+                version: Some(strip_peers(version).to_string()),
+                peer_dependencies: BTreeMap::new(),
+                dependencies: resolve_deps(&versions, &entry.dependencies),
+                optional_dependencies: resolve_deps(&versions, &entry.optional_dependencies),
+                dev_dependencies: BTreeMap::new(),
+                requires_build: None,
+            },
+        );
+    }
+
+    let mut importers = BTreeMap::new();
+    match raw.workspace {
+        Some(workspace) => {
+            importers.insert(".".to_string(), importer(&specifiers, &workspace));
+            for (path, member) in &workspace.members {
+                importers.insert(path.clone(), importer(&specifiers, member));
+            }
+        }
+        None => {
+            let all = RawMember { dependencies: specifiers.keys().cloned().collect(), ..RawMember::default() };
+            importers.insert(".".to_string(), importer(&specifiers, &all));
+        }
+    }
+    importers.retain(|_, importer| !importer.dependencies.is_empty());
+
+    Ok(Lockfile::new(format!("deno {}", version), importers, packages, BTreeMap::new()))
+}
+
+/// 拆分 name@版本，作用域包名开头的 @ 不作为分隔符
+fn split_key(key: &str) -> Option<(&str, &str)> {
+    let scoped = usize::from(key.starts_with('@'));
+    let pos = key[scoped..].find('@')? + scoped;
+    Some((&key[..pos], &key[pos + 1..]))
+}
+
+/// 去掉版本号后以 _ 连接的 peer 组合
+fn strip_peers(version: &str) -> &str {
+    version.split_once('_').map_or(version, |(version, _)| version)
+}
+
+/// 依赖值的写法与 pnpm 锁文件一致：版本号（保留 peer 组合以对应 npm 中的 key），别名为 name@版本
+fn dep_value(dep_name: &str, name: &str, version: &str) -> String {
+    if dep_name == name { version.to_string() } else { format!("{}@{}", name, version) }
+}
+
+fn resolve_deps(versions: &HashMap<&str, &str>, deps: &RawDeps) -> BTreeMap<String, String> {
+    match deps {
+        RawDeps::Map(deps) => deps
+            .iter()
+            .filter_map(|(dep_name, id)| {
+                let (name, version) = split_key(id)?;
+                Some((dep_name.clone(), dep_value(dep_name, name, version)))
+            })
+            .collect(),
+        RawDeps::List(deps) => deps
+            .iter()
+            .filter_map(|dep| {
+                // 别名写作 alias@npm:name@版本
+                if let Some((alias, target)) = dep.split_once("@npm:") {
+                    let (name, version) = split_key(target)?;
+                    return Some((alias.to_string(), dep_value(alias, name, version)));
+                }
+                if let Some((name, version)) = split_key(dep) {
+                    return Some((name.to_string(), version.to_string()));
+                }
+                // 只有名称时同名包只有一个版本
+                Some((dep.clone(), versions.get(dep.as_str())?.to_string()))
+            })
+            .collect(),
+    }
+}
+
+/// 由 npm: 规格生成 importer，deno.lock 不区分开发依赖，全部记为 dependencies
+fn importer(specifiers: &BTreeMap<String, String>, member: &RawMember) -> Importer {
+    let package_json = member.package_json.as_ref().map(|p| p.dependencies.as_slice()).unwrap_or_default();
+    let dependencies = member
+        .dependencies
+        .iter()
+        .chain(package_json)
+        .filter_map(|spec| {
+            let rest = spec.strip_prefix("npm:")?;
+            let (name, specifier) = split_key(rest).unwrap_or((rest, ""));
+            // version 3 中锁定的值为 npm:name@版本，version 4+ 为版本号
+            let locked = specifiers.get(spec)?;
+            let version = match locked.strip_prefix("npm:") {
+                Some(id) => {
+                    let (real, version) = split_key(id)?;
+                    dep_value(name, real, version)
+                }
+                None => locked.clone(),
+            };
+            Some((name.to_string(), DependencyInfo { specifier: specifier.to_string(), version }))
+        })
+        .collect();
+
+    Importer {
+        dependencies,
+        dev_dependencies: BTreeMap::new(),
+        optional_dependencies: BTreeMap::new(),
+    }
+}
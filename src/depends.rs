@@ -0,0 +1,99 @@
+// depends-on 子命令：按依赖关系反查，列出声明了满足条件的依赖的包，用于规划生态范围的升级
+
+use anyhow::{Context, Result};
+use std::collections::BTreeSet;
+
+use npm_package_check::lockfile::{self, Lockfile, Section};
+
+use crate::{LockfileContext, collation};
+use crate::matching::{self, MatchMode, version_matches};
+
+/// 拆分查询表达式 name@range，作用域包名以 @ 开头；不带版本范围时匹配任意版本
+pub(crate) fn parse_query(query: &str) -> (&str, Option<&str>) {
+    let scoped = query.starts_with('@');
+    match query[usize::from(scoped)..].find('@') {
+        Some(pos) => {
+            let pos = pos + usize::from(scoped);
+            (&query[..pos], Some(&query[pos + 1..]).filter(|range| !range.is_empty()))
+        }
+        None => (query, None),
+    }
+}
+
+/// 声明依赖的一方：importer 或 packages/snapshots 中的包
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Dependent {
+    Importer(String),
+    Package(String),
+}
+
+impl Dependent {
+    /// 控制台中的显示名称，importer 按锁文件的上下文标注包名
+    fn label(&self, context: &LockfileContext) -> String {
+        match self {
+            Dependent::Importer(path) => context.importer_label(path),
+            Dependent::Package(package) => package.clone(),
+        }
+    }
+}
+
+pub fn run_depends_on(lock_data: &Lockfile, context: &LockfileContext, query: &str, verbose: bool) -> Result<()> {
+    let (name, range) = parse_query(query);
+    if let Some(range) = range {
+        matching::validate(range, MatchMode::Semver).with_context(|| format!("无效的查询 '{}'", query))?;
+    }
+
+    // (声明方, 依赖版本, 依赖类型)，同一个包的不同 peer 组合合并为一条
+    let mut matches = BTreeSet::new();
+    let index = lock_data.index();
+    for record in index.get(name) {
+        let Some(kind) = record.dep_kind else {
+            continue;
+        };
+        if range.is_some_and(|range| !version_matches(&record.version, range, MatchMode::Semver)) {
+            continue;
+        }
+        let dependent = match record.section {
+            Section::Importers => Dependent::Importer(record.importer.unwrap_or(".").to_string()),
+            Section::Packages | Section::Snapshots => {
+                let parent = record.parent.unwrap_or_default();
+                let package = match lockfile::parse_package_key(parent) {
+                    Some((name, version)) => format!("{}@{}", name, version),
+                    None => parent.to_string(),
+                };
+                Dependent::Package(package)
+            }
+        };
+        matches.insert((dependent, record.version.clone(), kind.field_name()));
+    }
+
+    if matches.is_empty() {
+        println!("✅ 没有包依赖 {}", query);
+        return Ok(());
+    }
+
+    // importer 在前，同类按排序规则排序，同一个依赖方的多个版本保持原有顺序
+    let mut matches: Vec<_> = matches.into_iter().collect();
+    matches.sort_by(|(a, ..), (b, ..)| match (a, b) {
+        (Dependent::Importer(a), Dependent::Importer(b)) | (Dependent::Package(a), Dependent::Package(b)) => {
+            collation::compare(a, b)
+        }
+        _ => a.cmp(b),
+    });
+    let dependents: BTreeSet<&Dependent> = matches.iter().map(|(dependent, _, _)| dependent).collect();
+    println!("🔎 {} 个 importer 或包依赖 {}\n", dependents.len(), query);
+    for (dependent, version, kind) in &matches {
+        let label = match dependent {
+            Dependent::Importer(_) => "importer",
+            Dependent::Package(_) => "包",
+        };
+        let dependent = dependent.label(context);
+        if verbose {
+            println!("   - [{}] {} → {}@{} ({})", label, dependent, name, version, kind);
+        } else {
+            println!("   - [{}] {} → {}@{}", label, dependent, name, version);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+// --dir：递归查找目录中的全部锁文件（monorepo 中的多个项目、多个仓库的检出目录），逐一执行单包查询或批量检查
+//
+// 锁文件的查找规则与 -f 指定目录时相同：遵循 .gitignore，跳过 node_modules，支持 --exclude 排除，
+// 同一目录中的 npm-shrinkwrap.json 优先于 package-lock.json。目录树在后台线程中并行遍历，每找到一个锁文件就立即检查，
+// 网络文件系统上的大型目录不必等整个目录树遍历完才有输出；各锁文件按找到的顺序单独输出检查结果与结论行，
+// 无法读取或解析的锁文件按出错计入汇总后继续；最后按第一级目录与各锁文件汇总，任一项目未通过时以其中最大的退出码结束
+
+use anyhow::{Context, Result, bail};
+use std::fs;
+use std::path::Path;
+use std::sync::mpsc;
+use std::thread;
+
+use crate::http::Client;
+use crate::multi::{self, Summary};
+use crate::{Args, scan};
+
+pub fn run_dir(args: &mut Args, dir: &Path, client: &Client) -> Result<()> {
+    if !dir.is_dir() {
+        bail!("目录 '{}' 不存在", dir.display());
+    }
+    multi::reject_options(args, "--dir")?;
+    println!("📁 {}: 查找锁文件，找到后立即检查\n", dir.display());
+
+    let (excludes, follow_symlinks) = (args.exclude.clone(), args.follow_symlinks);
+    let (sender, receiver) = mpsc::channel();
+    let mut summary = Summary::streaming();
+    let mut unsupported = Vec::new();
+    thread::scope(|scope| -> Result<()> {
+        // 遍历结束时 sender 随线程释放，下面的循环随之结束
+        let walker = scope.spawn(move || {
+            scan::for_each_lockfile(dir, &excludes, follow_symlinks, |path| {
+                let _ = sender.send(path);
+            })
+        });
+        for path in receiver {
+            if scan::skip_unsupported(&path) {
+                unsupported.push(path);
+                continue;
+            }
+            let name = path.display().to_string();
+            summary.discovered(args, &name);
+            println!("📦 [{}] {}", summary.len() + 1, name);
+            // 无法读取或解析的锁文件按出错计入汇总，继续检查其他锁文件
+            let checked = fs::read_to_string(&path)
+                .with_context(|| format!("无法读取文件 '{}'", name))
+                .and_then(|content| summary.check(args, client, &name, &path, &content, true));
+            if let Err(e) = checked {
+                println!("⚠️ {}: {:#}\n", name, e);
+                summary.record_error(&name);
+            }
+        }
+        walker.join().expect("目录遍历线程异常退出")
+    })?;
+
+    if summary.is_empty() {
+        scan::drop_unsupported(unsupported)?;
+        bail!("目录 '{}' 中没有找到锁文件（{}）", dir.display(), scan::LOCKFILE_NAMES.join(", "));
+    }
+    println!("📁 {}: 共检查 {} 个锁文件\n", dir.display(), summary.len());
+    summary.print_directories(dir);
+    summary.finish()
+}
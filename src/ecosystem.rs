@@ -0,0 +1,65 @@
+// 锁文件格式的扩展点：每种包管理器的锁文件实现 Ecosystem，解析为与 pnpm 相同的锁文件模型（见 lockfile.rs），
+// 之后的检查、报告与各子命令不再区分格式
+//
+// 新增一种锁文件（例如 composer.lock、poetry.lock）需要：
+//   1. 新建模块实现 Ecosystem：默认文件名、按内容识别与解析
+//   2. 在 LockfileFormat 中增加对应的值，并在 LockfileFormat::ecosystem 中返回该实现；
+//      按内容识别时依次尝试 LockfileFormat::SNIFF_ORDER 中的格式，特征越明确的格式越靠前
+//   3. 在 Cargo.toml 中增加对应的 feature（加入 default），模块与 LockfileFormat::ecosystem 中的实现按 feature 编译，
+//      未启用时返回 Unavailable，并在 LockfileFormat::feature / is_enabled 中登记
+//
+// 转换为统一模型时的约定：
+//   importers  项目自身（根项目与 workspace 成员）的直接依赖，版本号为锁定的版本
+//   packages   以 name@version 为 key 的锁定包，dependencies 记录依赖名到锁定版本的映射
+//   resolution 包的来源，默认 registry 之外的来源会在检查结果中单独标注
+
+use anyhow::Result;
+
+use crate::lockfile::Lockfile;
+
+/// 一种锁文件格式
+pub trait Ecosystem: Sync {
+    /// --lockfile-format 与输出中使用的名称
+    fn name(&self) -> &'static str;
+
+    /// 包管理器生成的默认文件名
+    fn file_names(&self) -> &'static [&'static str];
+
+    /// 内容是否为该格式，文件名不是默认名称时（例如改名后的备份、CI 产物）按此判断
+    fn sniff(&self, content: &str) -> bool;
+
+    /// 解析为统一的锁文件模型
+    fn parse(&self, content: &str) -> Result<Lockfile>;
+}
+
+/// JSON 顶层对象中是否有指定的 key，只识别字符串与嵌套层级，不做完整解析
+#[cfg(any(feature = "bun", feature = "deno"))]
+pub(crate) fn has_top_level_key(content: &str, key: &str) -> bool {
+    let target = format!("\"{}\"", key);
+    let bytes = content.as_bytes();
+    let mut depth = 0usize;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' => {
+                let start = i;
+                i += 1;
+                while i < bytes.len() && bytes[i] != b'"' {
+                    if bytes[i] == b'\\' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+                let is_key = content.get(i + 1..).is_some_and(|rest| rest.trim_start().starts_with(':'));
+                if depth == 1 && is_key && content.get(start..=i) == Some(target.as_str()) {
+                    return true;
+                }
+            }
+            b'{' | b'[' => depth += 1,
+            b'}' | b']' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+        i += 1;
+    }
+    false
+}
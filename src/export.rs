@@ -0,0 +1,119 @@
+// export 子命令：导出规范化的包记录，供跨仓库的依赖分析流水线使用
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use serde::Serialize;
+use std::fs;
+use std::io::Write;
+
+use npm_package_check::lockfile::Lockfile;
+
+/// 导出格式
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum ExportFormat {
+    /// 每行一条记录的 JSON（JSON Lines）
+    Jsonl,
+    /// Apache Parquet 列式文件，需要 --output
+    #[cfg(feature = "parquet")]
+    Parquet,
+}
+
+/// 一条规范化的包记录，可选字段为空表示该节点没有对应信息
+#[derive(Debug, Serialize)]
+struct ExportRecord {
+    lockfile: String,
+    name: String,
+    version: String,
+    section: &'static str,
+    dep_kind: Option<&'static str>,
+    importer: Option<String>,
+    parent: Option<String>,
+    specifier: Option<String>,
+    resolution: Option<&'static str>,
+    integrity: Option<String>,
+}
+
+pub fn run_export(lock_data: &Lockfile, lockfile: &str, format: ExportFormat, output: Option<&str>) -> Result<()> {
+    let records: Vec<ExportRecord> = lock_data
+        .packages()
+        .map(|record| ExportRecord {
+            lockfile: lockfile.to_string(),
+            name: record.name,
+            version: record.version,
+            section: record.section.name(),
+            dep_kind: record.dep_kind.map(|kind| kind.field_name()),
+            importer: record.importer.map(str::to_string),
+            parent: record.parent.map(str::to_string),
+            specifier: record.specifier.map(str::to_string),
+            resolution: record.resolution.as_ref().map(|r| r.kind()),
+            integrity: record.resolution.as_ref().and_then(|r| r.integrity()).map(str::to_string),
+        })
+        .collect();
+
+    match format {
+        ExportFormat::Jsonl => {
+            let mut text = String::new();
+            for record in &records {
+                text.push_str(&crate::report::canonical_json(record, false)?);
+                text.push('\n');
+            }
+            match output {
+                Some(path) => fs::write(path, text).with_context(|| format!("无法写入文件 '{}'", path))?,
+                None => std::io::stdout().write_all(text.as_bytes())?,
+            }
+        }
+        #[cfg(feature = "parquet")]
+        ExportFormat::Parquet => {
+            let Some(path) = output else {
+                anyhow::bail!("--format parquet 需要通过 --output 指定输出文件");
+            };
+            write_parquet(&records, path)?;
+        }
+    }
+
+    if let Some(path) = output {
+        eprintln!("📦 已导出 {} 条记录: {}", records.len(), path);
+    }
+    Ok(())
+}
+
+#[cfg(feature = "parquet")]
+fn write_parquet(records: &[ExportRecord], path: &str) -> Result<()> {
+    use arrow_array::{ArrayRef, RecordBatch, StringArray};
+    use arrow_schema::{DataType, Field, Schema};
+    use parquet::arrow::ArrowWriter;
+    use std::sync::Arc;
+
+    // 列名、是否可为空、取值
+    type Column<'a> = (&'a str, bool, Box<dyn Fn(&ExportRecord) -> Option<&str>>);
+    let columns: Vec<Column> = vec![
+        ("lockfile", false, Box::new(|r| Some(r.lockfile.as_str()))),
+        ("name", false, Box::new(|r| Some(r.name.as_str()))),
+        ("version", false, Box::new(|r| Some(r.version.as_str()))),
+        ("section", false, Box::new(|r| Some(r.section))),
+        ("dep_kind", true, Box::new(|r| r.dep_kind)),
+        ("importer", true, Box::new(|r| r.importer.as_deref())),
+        ("parent", true, Box::new(|r| r.parent.as_deref())),
+        ("specifier", true, Box::new(|r| r.specifier.as_deref())),
+        ("resolution", true, Box::new(|r| r.resolution)),
+        ("integrity", true, Box::new(|r| r.integrity.as_deref())),
+    ];
+
+    let schema = Arc::new(Schema::new(
+        columns
+            .iter()
+            .map(|(name, nullable, _)| Field::new(*name, DataType::Utf8, *nullable))
+            .collect::<Vec<_>>(),
+    ));
+    let arrays: Vec<ArrayRef> = columns
+        .iter()
+        .map(|(_, _, value)| Arc::new(records.iter().map(value).collect::<StringArray>()) as ArrayRef)
+        .collect();
+    let batch = RecordBatch::try_new(schema.clone(), arrays).context("构建 Arrow 记录批次失败")?;
+
+    let file = fs::File::create(path).with_context(|| format!("无法创建文件 '{}'", path))?;
+    let mut writer = ArrowWriter::try_new(file, schema, None).context("创建 Parquet 写入器失败")?;
+    writer.write(&batch).context("写入 Parquet 文件失败")?;
+    writer.close().context("写入 Parquet 文件失败")?;
+    Ok(())
+}
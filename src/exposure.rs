@@ -0,0 +1,318 @@
+// 基于锁文件的 git 历史计算受影响版本的暴露窗口
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, FixedOffset, NaiveDate, Utc};
+use std::path::Path;
+use std::process::Command;
+
+use npm_package_check::lockfile::{Lockfile, LockfileFormat};
+
+use crate::find_package_in_lock;
+use crate::matching::{MatchMode, version_matches};
+
+#[derive(Debug)]
+struct Revision {
+    commit: String,
+    date: DateTime<FixedOffset>,
+    lock: RevisionLock,
+}
+
+/// 某个提交中的锁文件
+#[derive(Debug)]
+pub enum RevisionLock {
+    /// 锁文件在该提交中被删除，视为不包含任何包
+    Deleted,
+    /// 无法解析（例如旧格式），计算时跳过
+    Unparsed,
+    Parsed(Box<Lockfile>),
+}
+
+#[derive(Debug, Clone)]
+pub struct ExposureWindow {
+    pub version: String,
+    pub introduced: DateTime<FixedOffset>,
+    pub introduced_commit: String,
+    // 为 None 表示至今仍存在
+    pub removed: Option<DateTime<FixedOffset>>,
+    pub removed_commit: Option<String>,
+}
+
+impl ExposureWindow {
+    pub fn days(&self) -> i64 {
+        let end = self
+            .removed
+            .map(|d| d.with_timezone(&Utc))
+            .unwrap_or_else(Utc::now);
+        (end - self.introduced.with_timezone(&Utc)).num_days()
+    }
+
+    /// 检测日期之前已经存在的天数，检测日期无法解析或晚于移除时间时返回 None
+    pub fn days_before_detection(&self, detection_date: &str) -> Option<i64> {
+        let detected = NaiveDate::parse_from_str(detection_date.trim(), "%Y-%m-%d").ok()?;
+        let introduced = self.introduced.date_naive();
+        if detected < introduced {
+            return Some(0);
+        }
+        if let Some(removed) = self.removed
+            && removed.date_naive() < detected
+        {
+            return None;
+        }
+        Some((detected - introduced).num_days())
+    }
+
+    pub fn summary(&self) -> String {
+        let end = match (&self.removed, &self.removed_commit) {
+            (Some(date), Some(commit)) => format!("{} ({})", date.format("%Y-%m-%d"), short(commit)),
+            _ => "至今".to_string(),
+        };
+        format!(
+            "{}: {} ({}) → {}, 共 {} 天",
+            self.version,
+            self.introduced.format("%Y-%m-%d"),
+            short(&self.introduced_commit),
+            end,
+            self.days()
+        )
+    }
+
+    pub fn report_text(&self) -> String {
+        let end = self
+            .removed
+            .map(|d| d.format("%Y-%m-%d").to_string())
+            .unwrap_or_else(|| "present".to_string());
+        format!(
+            "{} {}..{} ({}d)",
+            self.version,
+            self.introduced.format("%Y-%m-%d"),
+            end,
+            self.days()
+        )
+    }
+}
+
+/// 锁文件在 git 中的全部历史版本（由旧到新）
+#[derive(Debug)]
+pub struct LockHistory {
+    revisions: Vec<Revision>,
+}
+
+impl LockHistory {
+    /// timezone 指定时，提交时间换算到该时区，否则保留提交者的时区
+    pub fn load(lock_path: &Path, timezone: Option<FixedOffset>) -> Result<Self> {
+        let dir = match lock_path.parent() {
+            Some(p) if !p.as_os_str().is_empty() => p,
+            _ => Path::new("."),
+        };
+        let file_name = lock_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .with_context(|| format!("无效的锁文件路径 '{}'", lock_path.display()))?;
+
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["log", "--reverse", "--format=%H%x09%cI", "--", file_name])
+            .output()
+            .context("无法执行 git 命令")?;
+        if !output.status.success() {
+            return Err(anyhow::anyhow!(
+                "读取锁文件 git 历史失败: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+
+        let mut revisions = Vec::new();
+        for line in String::from_utf8_lossy(&output.stdout).lines() {
+            let Some((commit, date)) = line.split_once('\t') else {
+                continue;
+            };
+            let mut date = DateTime::parse_from_rfc3339(date)
+                .with_context(|| format!("无法解析提交时间 '{}'", date))?;
+            if let Some(timezone) = timezone {
+                date = date.with_timezone(&timezone);
+            }
+
+            let content = Command::new("git")
+                .arg("-C")
+                .arg(dir)
+                .arg("show")
+                .arg(format!("{}:./{}", commit, file_name))
+                .output()
+                .context("无法执行 git 命令")?;
+            // git show 失败说明锁文件在该提交中被删除
+            let lock = if content.status.success() {
+                let content = String::from_utf8_lossy(&content.stdout);
+                match Lockfile::parse_as(&content, LockfileFormat::detect(lock_path, &content)) {
+                    Ok(lock) => RevisionLock::Parsed(Box::new(lock)),
+                    Err(_) => RevisionLock::Unparsed,
+                }
+            } else {
+                RevisionLock::Deleted
+            };
+
+            revisions.push(Revision {
+                commit: commit.to_string(),
+                date,
+                lock,
+            });
+        }
+
+        if revisions.is_empty() {
+            return Err(anyhow::anyhow!(
+                "锁文件 '{}' 没有 git 提交记录",
+                lock_path.display()
+            ));
+        }
+
+        Ok(Self { revisions })
+    }
+
+    pub fn revision_count(&self) -> usize {
+        self.revisions.len()
+    }
+
+    /// 各历史版本的提交、提交时间与锁文件（由旧到新）
+    pub fn revisions(&self) -> impl Iterator<Item = (&str, DateTime<FixedOffset>, &RevisionLock)> {
+        self.revisions
+            .iter()
+            .map(|revision| (revision.commit.as_str(), revision.date, &revision.lock))
+    }
+
+    /// 计算指定包的各个版本在历史中连续存在的时间窗口
+    pub fn windows(&self, package_name: &str, versions: &[String], match_mode: MatchMode) -> Vec<ExposureWindow> {
+        let mut windows = Vec::new();
+        let indexes: Vec<_> = self
+            .revisions
+            .iter()
+            .map(|revision| match &revision.lock {
+                RevisionLock::Parsed(lock) => Some(lock.index()),
+                RevisionLock::Deleted | RevisionLock::Unparsed => None,
+            })
+            .collect();
+
+        for version in versions {
+            let mut current: Option<ExposureWindow> = None;
+
+            for (revision, index) in self.revisions.iter().zip(&indexes) {
+                let present = match (&revision.lock, index) {
+                    (RevisionLock::Unparsed, _) => continue,
+                    (_, Some(index)) => find_package_in_lock(index, package_name)
+                        .iter()
+                        .any(|p| version_matches(&p.version, version, match_mode)),
+                    // 锁文件被删除，仍存在的版本在这个提交中移除
+                    (_, None) => false,
+                };
+
+                match (present, current.is_some()) {
+                    (true, false) => {
+                        current = Some(ExposureWindow {
+                            version: version.clone(),
+                            introduced: revision.date,
+                            introduced_commit: revision.commit.clone(),
+                            removed: None,
+                            removed_commit: None,
+                        });
+                    }
+                    (false, true) => {
+                        if let Some(mut window) = current.take() {
+                            window.removed = Some(revision.date);
+                            window.removed_commit = Some(revision.commit.clone());
+                            windows.push(window);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+
+            if let Some(window) = current {
+                windows.push(window);
+            }
+        }
+
+        windows
+    }
+}
+
+pub fn short(commit: &str) -> &str {
+    &commit[..commit.len().min(8)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+
+    const LOCKFILE: &str = "lockfileVersion: '9.0'\n\nimporters:\n  .:\n    dependencies:\n      lodash:\n        specifier: 4.17.20\n        version: 4.17.20\n";
+
+    /// 临时 git 仓库，依次提交锁文件的各个版本（None 表示删除锁文件）
+    fn repo(name: &str, revisions: &[(&str, Option<&str>)]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("npc-exposure-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let git = |args: &[&str], date: &str| {
+            let status = Command::new("git")
+                .arg("-C")
+                .arg(&dir)
+                .args(["-c", "user.name=test", "-c", "user.email=test@example.com", "-c", "commit.gpgsign=false"])
+                .args(args)
+                .env("GIT_AUTHOR_DATE", date)
+                .env("GIT_COMMITTER_DATE", date)
+                .output()
+                .unwrap();
+            assert!(status.status.success(), "git {:?}: {}", args, String::from_utf8_lossy(&status.stderr));
+        };
+        git(&["init", "-q"], "2024-01-01T00:00:00Z");
+        for (date, content) in revisions {
+            match content {
+                Some(content) => fs::write(dir.join("pnpm-lock.yaml"), content).unwrap(),
+                None => fs::remove_file(dir.join("pnpm-lock.yaml")).unwrap(),
+            }
+            git(&["add", "-A"], date);
+            git(&["commit", "-q", "-m", date], date);
+        }
+        dir
+    }
+
+    #[test]
+    fn deleted_lockfile_closes_window() {
+        let dir = repo(
+            "deleted",
+            &[
+                ("2024-01-01T00:00:00Z", Some(LOCKFILE)),
+                // 无法解析的版本跳过，不影响窗口
+                ("2024-01-05T00:00:00Z", Some("lockfileVersion: [\n")),
+                ("2024-01-11T00:00:00Z", None),
+            ],
+        );
+        let history = LockHistory::load(&dir.join("pnpm-lock.yaml"), None).unwrap();
+        let windows = history.windows("lodash", &["4.17.20".to_string()], MatchMode::Exact);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(history.revision_count(), 3);
+        assert_eq!(windows.len(), 1);
+        let window = &windows[0];
+        assert_eq!(window.introduced.format("%Y-%m-%d").to_string(), "2024-01-01");
+        assert_eq!(window.removed.map(|d| d.format("%Y-%m-%d").to_string()).as_deref(), Some("2024-01-11"));
+        assert_eq!(window.days(), 10);
+    }
+
+    #[test]
+    fn unparsed_lockfile_keeps_window_open() {
+        let dir = repo(
+            "unparsed",
+            &[
+                ("2024-01-01T00:00:00Z", Some(LOCKFILE)),
+                ("2024-01-05T00:00:00Z", Some("lockfileVersion: [\n")),
+            ],
+        );
+        let history = LockHistory::load(&dir.join("pnpm-lock.yaml"), None).unwrap();
+        let windows = history.windows("lodash", &["4.17.20".to_string()], MatchMode::Exact);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(windows.len(), 1);
+        assert!(windows[0].removed.is_none());
+        assert!(windows[0].summary().ends_with(&format!("至今, 共 {} 天", windows[0].days())));
+    }
+}
@@ -0,0 +1,189 @@
+// extract 子命令：从 pnpm-lock.yaml 中提取重现问题所需的最小片段，便于附在 issue 中
+//
+// 保留的条目：
+//   lockfileVersion、settings
+//   catalogs 中被保留的直接依赖对应的条目
+//   各 importer 中通向目标包的直接依赖（v5/v6 单项目锁文件为顶层的 dependencies 等节点）
+//   依赖链上的 packages 与 snapshots 条目，其中的依赖只保留同样被保留的条目
+// 片段中的依赖引用都能找到对应条目，可以被本工具（以及 pnpm 的解析器）正常读取
+
+use anyhow::{Context, Result, bail};
+use serde_yaml::{Mapping, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+
+use npm_package_check::lockfile::{LockedPackage, Lockfile, LockfileFormat};
+
+use crate::why;
+
+// importer 中记录依赖的字段，specifiers 为 v5 的版本规格
+const IMPORTER_DEP_FIELDS: &[&str] = &["dependencies", "devDependencies", "optionalDependencies", "specifiers"];
+// packages/snapshots 条目中引用其他条目的字段
+const NODE_DEP_FIELDS: &[&str] = &["dependencies", "devDependencies", "optionalDependencies"];
+
+/// 需要保留的条目
+#[derive(Default)]
+struct Selection {
+    /// importer 路径 → 保留的直接依赖名
+    importers: BTreeMap<String, BTreeSet<String>>,
+    /// snapshots/packages 节点 key
+    nodes: BTreeSet<String>,
+    /// packages 节点 key（v9 中不含 peer 后缀）
+    packages: BTreeSet<String>,
+}
+
+fn select(lock: &Lockfile, query: &str) -> Result<Selection> {
+    let targets = why::find_targets(lock, query)?;
+    if targets.is_empty() {
+        bail!("锁文件中没有 {}", query);
+    }
+
+    let mut selection = Selection::default();
+    for keys in targets.values() {
+        selection.nodes.extend(keys.iter().cloned());
+    }
+    for (path, importer) in &lock.importers {
+        let chains = why::shortest_chains(lock, path, true);
+        for keys in targets.values() {
+            let Some(chain) = keys.iter().filter_map(|key| chains.get(key)).min_by_key(|chain| chain.len()) else {
+                continue;
+            };
+            selection.nodes.extend(chain.iter().map(|(_, key)| key.clone()));
+            let first = &chain[0].1;
+            let deps = [&importer.dependencies, &importer.dev_dependencies, &importer.optional_dependencies];
+            for (name, dep_info) in deps.into_iter().flatten() {
+                if lock.node_key(name, &dep_info.version).as_ref() == Some(first) {
+                    selection.importers.entry(path.clone()).or_default().insert(name.clone());
+                }
+            }
+        }
+    }
+    selection.packages = selection
+        .nodes
+        .iter()
+        .filter_map(|key| match lock.packages.contains_key(key) {
+            true => Some(key.clone()),
+            false => lock.locked.get(key).map(LockedPackage::id),
+        })
+        .filter(|key| lock.packages.contains_key(key))
+        .collect();
+    Ok(selection)
+}
+
+/// 只保留 fields 中名称在 keep 里的依赖
+fn retain_deps(entry: &mut Value, fields: &[&str], keep: impl Fn(&str, &str) -> bool) {
+    let Some(entry) = entry.as_mapping_mut() else {
+        return;
+    };
+    for field in fields {
+        if let Some(deps) = entry.get_mut(*field).and_then(Value::as_mapping_mut) {
+            deps.retain(|name, value| {
+                let value = match value {
+                    // importers 中 v6/v9 的写法为 {specifier, version}
+                    Value::Mapping(info) => info.get("version").and_then(Value::as_str).unwrap_or_default(),
+                    other => other.as_str().unwrap_or_default(),
+                };
+                name.as_str().is_some_and(|name| keep(name, value))
+            });
+        }
+    }
+    // 过滤后为空的依赖字段一并去掉
+    entry.retain(|field, deps| {
+        let is_dep_field = field.as_str().is_some_and(|field| fields.contains(&field));
+        !(is_dep_field && deps.as_mapping().is_some_and(Mapping::is_empty))
+    });
+}
+
+fn retain_entries(section: &mut Value, keep: &BTreeSet<String>) {
+    if let Some(entries) = section.as_mapping_mut() {
+        entries.retain(|key, _| key.as_str().is_some_and(|key| keep.contains(key)));
+    }
+}
+
+pub fn run_extract(content: &str, format: LockfileFormat, query: &str, output: Option<&str>) -> Result<()> {
+    if format != LockfileFormat::Pnpm {
+        bail!("extract 只支持 pnpm-lock.yaml，{} 锁文件请直接附上相关的条目", format.name());
+    }
+    let lock = Lockfile::parse_as(content, format)?;
+    let selection = select(&lock, query)?;
+
+    let raw: Mapping = serde_yaml::from_str(content).context("锁文件顶层不是映射")?;
+    let keep_node =
+        |name: &str, value: &str| lock.node_key(name, value).is_some_and(|key| selection.nodes.contains(&key));
+    let root_deps = selection.importers.get(".");
+    let mut fragment = Mapping::new();
+    for (key, mut value) in raw {
+        match key.as_str().unwrap_or_default() {
+            "lockfileVersion" | "settings" => {}
+            "importers" => {
+                let paths: BTreeSet<String> = selection.importers.keys().cloned().collect();
+                retain_entries(&mut value, &paths);
+                if let Some(importers) = value.as_mapping_mut() {
+                    for (path, importer) in importers.iter_mut() {
+                        let names = path.as_str().and_then(|path| selection.importers.get(path));
+                        retain_deps(importer, IMPORTER_DEP_FIELDS, |name, _| names.is_some_and(|n| n.contains(name)));
+                    }
+                }
+            }
+            // v5/v6 单项目锁文件的直接依赖写在顶层
+            "dependencies" | "devDependencies" | "optionalDependencies" | "specifiers" => {
+                if let Some(deps) = value.as_mapping_mut() {
+                    deps.retain(|name, _| {
+                        name.as_str().is_some_and(|name| root_deps.is_some_and(|names| names.contains(name)))
+                    });
+                }
+                if value.as_mapping().is_some_and(Mapping::is_empty) {
+                    continue;
+                }
+            }
+            "packages" => {
+                retain_entries(&mut value, &selection.packages);
+                if let Some(entries) = value.as_mapping_mut() {
+                    for (_, entry) in entries.iter_mut() {
+                        retain_deps(entry, NODE_DEP_FIELDS, keep_node);
+                    }
+                }
+            }
+            "snapshots" => {
+                retain_entries(&mut value, &selection.nodes);
+                if let Some(entries) = value.as_mapping_mut() {
+                    for (_, entry) in entries.iter_mut() {
+                        retain_deps(entry, NODE_DEP_FIELDS, keep_node);
+                    }
+                }
+            }
+            // importers 中的 catalog: 规格指向 catalogs 中的条目，只保留被保留的直接依赖对应的条目
+            "catalogs" => {
+                let names: BTreeSet<String> = selection.importers.values().flatten().cloned().collect();
+                if let Some(catalogs) = value.as_mapping_mut() {
+                    for (_, catalog) in catalogs.iter_mut() {
+                        retain_entries(catalog, &names);
+                    }
+                    catalogs.retain(|_, catalog| !catalog.as_mapping().is_some_and(Mapping::is_empty));
+                }
+                if value.as_mapping().is_some_and(Mapping::is_empty) {
+                    continue;
+                }
+            }
+            // overrides 等与依赖链无关的节点不保留
+            _ => continue,
+        }
+        fragment.insert(key, value);
+    }
+
+    let mut text = format!("# npm_package_check extract {}：只保留重现问题所需的条目\n", query);
+    text.push_str(&serde_yaml::to_string(&fragment).context("生成锁文件片段失败")?);
+    match output {
+        Some(path) => {
+            fs::write(path, text).with_context(|| format!("无法写入文件 '{}'", path))?;
+            eprintln!(
+                "📦 锁文件片段已写入: {}（{} 个 importer，{} 个包条目）",
+                path,
+                selection.importers.len(),
+                selection.nodes.len()
+            );
+        }
+        None => print!("{}", text),
+    }
+    Ok(())
+}
@@ -0,0 +1,112 @@
+// 多次指定 -f 或使用 glob 模式：一次运行检查多个锁文件，逐一输出结果后汇总
+//
+//   -f "packages/*/pnpm-lock.yaml" -f backend/pnpm-lock.yaml
+//
+// glob 模式按文件系统展开（* 不跨越目录，** 匹配任意层级），没有匹配时报错而不是静默跳过；
+// 展开结果中的符号链接与目录扫描的规则相同（默认不跟随，--follow-symlinks 只跟随指向模式所在目录内部的链接）；
+// 指定目录时检查其中的全部锁文件（规则同 --dir），http(s) 地址下载后检查。
+// 同一个锁文件只检查一次，按指定的顺序输出；无法读取或解析的锁文件计入汇总后继续检查其他锁文件
+
+use anyhow::{Context, Result, bail};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::collation;
+use crate::http::{self, Client};
+use crate::multi::{self, Summary};
+use crate::{Args, STDIN_LOCKFILE, archive, is_remote, reject_local_options, remote_file_name, scan};
+
+/// 是否为 glob 模式；http(s) 地址中的 ? 是查询参数，不视为通配符
+pub fn is_glob(file: &str) -> bool {
+    !is_remote(file) && file.contains(['*', '?', '['])
+}
+
+/// glob 模式中第一个通配符之前的目录，展开的结果都在这个目录之下
+fn glob_root(pattern: &str) -> PathBuf {
+    let root: PathBuf = Path::new(pattern)
+        .components()
+        .take_while(|component| !is_glob(&component.as_os_str().to_string_lossy()))
+        .collect();
+    if root.as_os_str().is_empty() { PathBuf::from(".") } else { root }
+}
+
+/// 展开 -f 的全部取值，本地路径指定目录时展开为其中的锁文件
+fn expand(files: &[String], excludes: &[String], follow_symlinks: bool) -> Result<Vec<String>> {
+    let mut expanded: Vec<String> = Vec::new();
+    let mut push = |path: &Path| -> Result<()> {
+        if path.is_dir() {
+            for lockfile in scan::find_lockfiles(path, excludes, follow_symlinks)? {
+                expanded.push(lockfile.display().to_string());
+            }
+        } else {
+            expanded.push(path.display().to_string());
+        }
+        Ok(())
+    };
+    for file in files {
+        if file == STDIN_LOCKFILE {
+            bail!("从标准输入读取锁文件（-f -）时不能再指定其他锁文件");
+        }
+        if archive::is_archive(file) {
+            bail!("压缩包 '{}' 需要单独通过 -f 指定", file);
+        }
+        if is_remote(file) {
+            push(Path::new(file))?;
+        } else if is_glob(file) {
+            let mut matched = glob::glob(file)
+                .with_context(|| format!("无效的 glob 模式 '{}'", file))?
+                .collect::<Result<Vec<_>, _>>()
+                .with_context(|| format!("展开 '{}' 失败", file))?;
+            if matched.is_empty() {
+                bail!("没有与 '{}' 匹配的锁文件", file);
+            }
+            collation::sort_paths(&mut matched);
+            let root = glob_root(file);
+            for path in matched.iter().filter(|path| scan::glob_path_allowed(path, &root, follow_symlinks)) {
+                push(path)?;
+            }
+        } else {
+            if !Path::new(file).exists() {
+                bail!("文件 '{}' 不存在", file);
+            }
+            push(Path::new(file))?;
+        }
+    }
+
+    let mut seen = HashSet::new();
+    expanded.retain(|file| seen.insert(file.clone()));
+    Ok(expanded)
+}
+
+pub fn run_files(args: &mut Args, client: &Client) -> Result<()> {
+    multi::reject_options(args, "多个锁文件")?;
+    if args.files.iter().any(|file| is_remote(file)) {
+        reject_local_options(args, "远程锁文件")?;
+    }
+
+    let lockfiles = expand(&args.files, &args.exclude, args.follow_symlinks)?;
+    if lockfiles.is_empty() {
+        bail!("没有找到锁文件（{}）", scan::LOCKFILE_NAMES.join(", "));
+    }
+    println!("🗂️ 共 {} 个锁文件\n", lockfiles.len());
+
+    let mut summary = Summary::new(args, lockfiles.clone());
+    for (i, file) in lockfiles.iter().enumerate() {
+        println!("📦 [{}/{}] {}", i + 1, lockfiles.len(), file);
+        // 无法下载、读取或解析的锁文件按出错计入汇总，继续检查其他锁文件
+        let checked = if is_remote(file) {
+            http::get_text(client, file)
+                .and_then(|content| summary.check(args, client, file, Path::new(remote_file_name(file)), &content, false))
+        } else {
+            fs::read_to_string(file)
+                .with_context(|| format!("无法读取文件 '{}'", file))
+                .and_then(|content| summary.check(args, client, file, Path::new(file), &content, true))
+        };
+        if let Err(e) = checked {
+            println!("⚠️ {}: {:#}\n", file, e);
+            summary.record_error(file);
+        }
+    }
+    summary.finish()
+}
@@ -0,0 +1,246 @@
+// 锁文件兼容性自检：内置的锁文件样本与解析覆盖率统计
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+
+use crate::lockfile::{self, LockedPackage, Lockfile};
+use crate::snapshot::{self, DepValue};
+#[cfg(feature = "bun")]
+use crate::bun;
+#[cfg(feature = "cargo-lock")]
+use crate::cargo;
+#[cfg(feature = "yarn")]
+use crate::yarn;
+
+/// 一份待自检的锁文件
+#[derive(Debug, Clone)]
+pub struct Fixture {
+    pub name: String,
+    pub content: String,
+}
+
+/// 随程序发布的锁文件样本
+pub fn bundled() -> Vec<Fixture> {
+    [
+        ("pnpm-v5.yaml", include_str!("../fixtures/pnpm-v5.yaml")),
+        ("pnpm-v5-workspace.yaml", include_str!("../fixtures/pnpm-v5-workspace.yaml")),
+        ("pnpm-v6.yaml", include_str!("../fixtures/pnpm-v6.yaml")),
+        ("pnpm-v9.yaml", include_str!("../fixtures/pnpm-v9.yaml")),
+        ("npm-v3.json", include_str!("../fixtures/npm-v3.json")),
+        ("yarn-v1.lock", include_str!("../fixtures/yarn-v1.lock")),
+        ("bun-v1.lock", include_str!("../fixtures/bun-v1.lock")),
+        ("deno-v4.lock", include_str!("../fixtures/deno-v4.lock")),
+        ("cargo-v4.lock", include_str!("../fixtures/cargo-v4.lock")),
+    ]
+    .into_iter()
+    .map(|(name, content)| Fixture {
+        name: name.to_string(),
+        content: content.to_string(),
+    })
+    .collect()
+}
+
+/// 核对节点 key 解析样本，返回样本数与不符的条目（格式为 "key: 期望 …，实际 …"）
+pub fn check_package_keys() -> (usize, Vec<String>) {
+    let corpus = include_str!("../fixtures/package-keys.tsv");
+    let mut total = 0;
+    let mut mismatches = Vec::new();
+    for line in corpus.lines().filter(|line| !line.is_empty() && !line.starts_with('#')) {
+        let Some((key, expected)) = line.split_once('\t') else {
+            continue;
+        };
+        total += 1;
+        let actual = LockedPackage::parse(key).map_or_else(|| "-".to_string(), |package| package.label());
+        if actual != expected {
+            mismatches.push(format!("{}: 期望 {}，实际 {}", key, expected, actual));
+        }
+    }
+    (total, mismatches)
+}
+
+/// 加载用户提供的锁文件：文件直接读取，目录读取其中的全部 .yaml/.yml/.json/.lock 文件
+pub fn load(path: &Path) -> Result<Vec<Fixture>> {
+    let mut paths = Vec::new();
+    if path.is_dir() {
+        for entry in fs::read_dir(path).with_context(|| format!("无法读取目录 '{}'", path.display()))? {
+            let entry_path = entry?.path();
+            let is_yaml = entry_path
+                .extension()
+                .is_some_and(|ext| ["yaml", "yml", "json", "lock"].iter().any(|e| ext == *e));
+            if entry_path.is_file() && is_yaml {
+                paths.push(entry_path);
+            }
+        }
+        paths.sort();
+    } else {
+        paths.push(path.to_path_buf());
+    }
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let content = fs::read_to_string(&path)
+                .with_context(|| format!("无法读取文件 '{}'", path.display()))?;
+            Ok(Fixture {
+                name: path.display().to_string(),
+                content,
+            })
+        })
+        .collect()
+}
+
+/// 解析覆盖率：锁文件中的条目有多少被拆分为包名与版本号
+#[derive(Debug)]
+pub struct Coverage {
+    pub lockfile_version: String,
+    pub entries: usize,
+    pub understood: usize,
+    /// 未能识别的条目，格式为 "节点: 条目"
+    pub unparsed: Vec<String>,
+    /// 未识别的顶层节点
+    pub unknown_sections: Vec<String>,
+}
+
+impl Coverage {
+    pub fn is_complete(&self) -> bool {
+        self.understood == self.entries && self.unknown_sections.is_empty()
+    }
+
+    pub fn percent(&self) -> f64 {
+        if self.entries == 0 {
+            return 100.0;
+        }
+        self.understood as f64 * 100.0 / self.entries as f64
+    }
+}
+
+// 已知的顶层节点，其余节点说明锁文件格式超出了当前的解析范围
+const KNOWN_SECTIONS: &[&str] = &[
+    "lockfileVersion",
+    "settings",
+    "overrides",
+    "packageExtensionsChecksum",
+    "patchedDependencies",
+    "pnpmfileChecksum",
+    "catalogs",
+    "time",
+    "neverBuiltDependencies",
+    "onlyBuiltDependencies",
+    "ignoredOptionalDependencies",
+    "importers",
+    "packages",
+    "snapshots",
+    "specifiers",
+    "dependencies",
+    "devDependencies",
+    "optionalDependencies",
+    // package-lock.json
+    "name",
+    "version",
+    "requires",
+    // bun.lock
+    "workspaces",
+    "configVersion",
+    "trustedDependencies",
+    "catalog",
+    // deno.lock
+    "npm",
+    "jsr",
+    "remote",
+    "redirects",
+    "workspace",
+    // Cargo.lock
+    "package",
+    "metadata",
+    "patch",
+];
+
+/// 锁文件的顶层节点。yarn.lock 不是 YAML，没有顶层节点可以检查；bun.lock 去掉注释与尾随逗号后是 JSON，Cargo.lock 是 TOML
+fn top_level_sections(content: &str) -> Result<serde_yaml::Mapping> {
+    #[cfg(feature = "yarn")]
+    if yarn::is_yarn_lock(content) {
+        return Ok(serde_yaml::Mapping::new());
+    }
+    #[cfg(feature = "cargo-lock")]
+    if cargo::is_cargo_lock(content) {
+        let table: toml::Table = toml::from_str(content).context("锁文件顶层不是表")?;
+        return Ok(table.keys().map(|key| (serde_yaml::Value::from(key.as_str()), serde_yaml::Value::Null)).collect());
+    }
+    #[cfg(feature = "bun")]
+    if bun::is_bun_lock(content) {
+        return serde_yaml::from_str(&bun::to_json(content)).context("锁文件顶层不是映射");
+    }
+    serde_yaml::from_str(content).context("锁文件顶层不是映射")
+}
+
+pub fn coverage(fixture: &Fixture) -> Result<Coverage> {
+    let lock = Lockfile::parse(&fixture.content)?;
+    let raw = top_level_sections(&fixture.content)?;
+
+    let mut unknown_sections: Vec<String> = raw
+        .keys()
+        .filter_map(|key| key.as_str())
+        .filter(|key| !KNOWN_SECTIONS.contains(key))
+        .map(str::to_string)
+        .collect();
+    unknown_sections.sort();
+
+    let mut entries = 0;
+    let mut unparsed = Vec::new();
+    let mut check = |understood: bool, label: String| {
+        entries += 1;
+        if !understood {
+            unparsed.push(label);
+        }
+    };
+
+    for (path, importer) in &lock.importers {
+        for deps in [&importer.dependencies, &importer.dev_dependencies, &importer.optional_dependencies] {
+            for (name, dep_info) in deps {
+                check(
+                    dep_value_understood(&dep_info.version),
+                    format!("importers[{}]: {} {}", path, name, dep_info.version),
+                );
+            }
+        }
+    }
+    for (key, package_info) in &lock.packages {
+        let understood = (package_info.name.is_some() && package_info.version.is_some())
+            || lockfile::parse_package_key(key).is_some();
+        check(understood, format!("packages: {}", key));
+        for deps in [
+            &package_info.dependencies,
+            &package_info.optional_dependencies,
+            &package_info.dev_dependencies,
+        ] {
+            for (name, value) in deps {
+                check(dep_value_understood(value), format!("packages[{}]: {} {}", key, name, value));
+            }
+        }
+    }
+    for (key, snapshot_info) in &lock.snapshots {
+        check(lockfile::parse_package_key(key).is_some(), format!("snapshots: {}", key));
+        for deps in [&snapshot_info.dependencies, &snapshot_info.optional_dependencies] {
+            for (name, value) in deps {
+                check(dep_value_understood(value), format!("snapshots[{}]: {} {}", key, name, value));
+            }
+        }
+    }
+    unparsed.sort();
+
+    Ok(Coverage {
+        lockfile_version: lock.lockfile_version,
+        understood: entries - unparsed.len(),
+        entries,
+        unparsed,
+        unknown_sections,
+    })
+}
+
+fn dep_value_understood(value: &str) -> bool {
+    match snapshot::parse_dep_value(value) {
+        DepValue::Version { version } => !version.is_empty(),
+        DepValue::Alias { .. } | DepValue::Link(_) | DepValue::File(_) => true,
+    }
+}
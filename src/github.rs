@@ -0,0 +1,158 @@
+// GitHub API 查询
+
+use anyhow::{Context, Result, bail};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use serde::Deserialize;
+
+use crate::http::{self, Client};
+
+pub const DEFAULT_GITHUB_API: &str = "https://api.github.com";
+
+#[derive(Debug, Deserialize)]
+pub struct RepoInfo {
+    #[serde(default)]
+    pub archived: bool,
+    #[serde(default)]
+    pub default_branch: String,
+}
+
+/// 组织或用户名下的仓库
+#[derive(Debug, Deserialize)]
+pub struct OrgRepo {
+    pub full_name: String,
+    #[serde(default)]
+    pub archived: bool,
+    #[serde(default)]
+    pub fork: bool,
+    #[serde(default)]
+    pub default_branch: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Commit {
+    sha: String,
+}
+
+/// 提交的完整目录树
+#[derive(Debug, Deserialize)]
+pub struct Tree {
+    pub tree: Vec<TreeEntry>,
+    /// 条目过多时 GitHub 只返回部分目录树
+    #[serde(default)]
+    pub truncated: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TreeEntry {
+    pub path: String,
+    /// blob（文件）、tree（目录）或 commit（子模块）
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub sha: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Blob {
+    content: String,
+    encoding: String,
+}
+
+pub struct GitHub {
+    api: String,
+    token: Option<String>,
+    client: Client,
+}
+
+impl GitHub {
+    pub fn new(api: &str, token: Option<String>, client: &Client) -> Self {
+        Self {
+            api: api.trim_end_matches('/').to_string(),
+            token,
+            client: client.clone(),
+        }
+    }
+
+    fn get_json<T: serde::de::DeserializeOwned>(&self, path: &str) -> Result<Option<T>> {
+        let url = format!("{}{}", self.api, path);
+        let auth = self.token.as_ref().map(|t| format!("Bearer {}", t));
+        let mut headers = vec![("Accept", "application/vnd.github+json")];
+        if let Some(ref auth) = auth {
+            headers.push(("Authorization", auth));
+        }
+        http::get_json(&self.client, &url, &headers)
+    }
+
+    pub fn repo(&self, owner: &str, repo: &str) -> Result<RepoInfo> {
+        self.get_json(&format!("/repos/{}/{}", owner, repo))?
+            .with_context(|| format!("GitHub 仓库 {}/{} 不存在", owner, repo))
+    }
+
+    /// 组织的全部仓库（私有仓库需要有权限的 token）；不是组织时按用户名列出
+    pub fn org_repos(&self, org: &str) -> Result<Vec<OrgRepo>> {
+        for base in [format!("/orgs/{}/repos?type=all", org), format!("/users/{}/repos?type=owner", org)] {
+            let mut repos = Vec::new();
+            for page in 1.. {
+                let Some(batch) = self.get_json::<Vec<OrgRepo>>(&format!("{}&per_page=100&page={}", base, page))? else {
+                    break;
+                };
+                let done = batch.len() < 100;
+                repos.extend(batch);
+                if done {
+                    return Ok(repos);
+                }
+            }
+        }
+        bail!("GitHub 组织或用户 '{}' 不存在", org)
+    }
+
+    /// 分支、标签或提交对应的提交 SHA
+    pub fn commit_sha(&self, owner: &str, repo: &str, git_ref: &str) -> Result<String> {
+        let commit: Commit = self
+            .get_json(&format!("/repos/{}/{}/commits/{}", owner, repo, git_ref))?
+            .with_context(|| format!("GitHub 仓库 {}/{} 中没有 '{}'", owner, repo, git_ref))?;
+        Ok(commit.sha)
+    }
+
+    /// 提交的完整目录树（递归）
+    pub fn tree(&self, owner: &str, repo: &str, sha: &str) -> Result<Tree> {
+        self.get_json(&format!("/repos/{}/{}/git/trees/{}?recursive=1", owner, repo, sha))?
+            .with_context(|| format!("GitHub 仓库 {}/{} 中没有提交 {}", owner, repo, sha))
+    }
+
+    /// 按 SHA 读取文件内容，blob 接口支持最大 100 MB 的文件
+    pub fn blob_text(&self, owner: &str, repo: &str, sha: &str) -> Result<String> {
+        let blob: Blob = self
+            .get_json(&format!("/repos/{}/{}/git/blobs/{}", owner, repo, sha))?
+            .with_context(|| format!("GitHub 仓库 {}/{} 中没有 blob {}", owner, repo, sha))?;
+        if blob.encoding != "base64" {
+            bail!("不支持的 blob 编码: {}", blob.encoding);
+        }
+        let content: String = blob.content.split_whitespace().collect();
+        let bytes = STANDARD.decode(content).context("解码 blob 内容失败")?;
+        String::from_utf8(bytes).context("文件内容不是 UTF-8 文本")
+    }
+}
+
+/// 从 package.json 的 repository 字段中解析 GitHub 的 owner/repo
+pub fn parse_repo_url(url: &str) -> Option<(String, String)> {
+    let rest = if let Some(rest) = url.strip_prefix("github:") {
+        rest
+    } else if let Some(pos) = url.find("github.com") {
+        url[pos + "github.com".len()..].trim_start_matches([':', '/'])
+    } else if !url.contains(':') && url.matches('/').count() == 1 {
+        // 简写形式 owner/repo
+        url
+    } else {
+        return None;
+    };
+
+    let mut parts = rest.split('/');
+    let owner = parts.next()?.trim();
+    let repo = parts.next()?.trim().trim_end_matches(".git");
+    let repo = repo.split(['#', '?']).next().unwrap_or(repo);
+    if owner.is_empty() || repo.is_empty() {
+        return None;
+    }
+    Some((owner.to_string(), repo.to_string()))
+}
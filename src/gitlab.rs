@@ -0,0 +1,126 @@
+// GitLab API 查询（REST API v4）
+
+use anyhow::{Context, Result, bail};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use serde::Deserialize;
+
+use crate::http::{self, Client};
+
+pub const DEFAULT_GITLAB_API: &str = "https://gitlab.com/api/v4";
+
+/// 分页接口每页的条目数（GitLab 允许的最大值）
+const PER_PAGE: usize = 100;
+
+#[derive(Debug, Deserialize)]
+pub struct Project {
+    pub id: u64,
+    pub path_with_namespace: String,
+    /// 空仓库没有默认分支
+    #[serde(default)]
+    pub default_branch: Option<String>,
+    #[serde(default)]
+    pub archived: bool,
+    #[serde(default)]
+    pub forked_from_project: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Commit {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TreeEntry {
+    pub path: String,
+    /// blob（文件）、tree（目录）或 commit（子模块）
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Blob {
+    content: String,
+    encoding: String,
+}
+
+/// 路径参数中的 / 需要编码（group/subgroup、分支名 feature/x）
+fn encode(value: &str) -> String {
+    value.replace('%', "%25").replace('/', "%2F").replace('#', "%23").replace('?', "%3F")
+}
+
+pub struct GitLab {
+    api: String,
+    token: Option<String>,
+    client: Client,
+}
+
+impl GitLab {
+    pub fn new(api: &str, token: Option<String>, client: &Client) -> Self {
+        Self {
+            api: api.trim_end_matches('/').to_string(),
+            token,
+            client: client.clone(),
+        }
+    }
+
+    fn get_json<T: serde::de::DeserializeOwned>(&self, path: &str) -> Result<Option<T>> {
+        let url = format!("{}{}", self.api, path);
+        let mut headers = Vec::new();
+        if let Some(ref token) = self.token {
+            headers.push(("PRIVATE-TOKEN", token.as_str()));
+        }
+        http::get_json(&self.client, &url, &headers)
+    }
+
+    /// 逐页读取列表接口，直到某一页不足 PER_PAGE 条
+    fn get_pages<T: serde::de::DeserializeOwned>(&self, path: &str) -> Result<Option<Vec<T>>> {
+        let separator = if path.contains('?') { '&' } else { '?' };
+        let mut items = Vec::new();
+        for page in 1.. {
+            let Some(batch) = self.get_json::<Vec<T>>(&format!("{}{}per_page={}&page={}", path, separator, PER_PAGE, page))? else {
+                return Ok(None);
+            };
+            let done = batch.len() < PER_PAGE;
+            items.extend(batch);
+            if done {
+                break;
+            }
+        }
+        Ok(Some(items))
+    }
+
+    /// group 及其全部子 group 中的项目
+    pub fn group_projects(&self, group: &str) -> Result<Vec<Project>> {
+        self.get_pages(&format!("/groups/{}/projects?include_subgroups=true&order_by=path&sort=asc", encode(group)))?
+            .with_context(|| format!("GitLab group '{}' 不存在或没有访问权限（私有 group 需要 --gitlab-token）", group))
+    }
+
+    /// 分支、标签或提交对应的提交 SHA
+    pub fn commit_sha(&self, project: &Project, git_ref: &str) -> Result<String> {
+        let commit: Commit = self
+            .get_json(&format!("/projects/{}/repository/commits/{}", project.id, encode(git_ref)))?
+            .with_context(|| format!("GitLab 项目 {} 中没有 '{}'", project.path_with_namespace, git_ref))?;
+        Ok(commit.id)
+    }
+
+    /// 提交的完整目录树（递归）
+    pub fn tree(&self, project: &Project, sha: &str) -> Result<Vec<TreeEntry>> {
+        self.get_pages(&format!("/projects/{}/repository/tree?recursive=true&ref={}", project.id, sha))?
+            .with_context(|| format!("GitLab 项目 {} 中没有提交 {}", project.path_with_namespace, sha))
+    }
+
+    /// 按 SHA 读取文件内容
+    pub fn blob_text(&self, project: &Project, sha: &str) -> Result<String> {
+        let blob: Blob = self
+            .get_json(&format!("/projects/{}/repository/blobs/{}", project.id, sha))?
+            .with_context(|| format!("GitLab 项目 {} 中没有 blob {}", project.path_with_namespace, sha))?;
+        if blob.encoding != "base64" {
+            bail!("不支持的 blob 编码: {}", blob.encoding);
+        }
+        let content: String = blob.content.split_whitespace().collect();
+        let bytes = STANDARD.decode(content).context("解码 blob 内容失败")?;
+        String::from_utf8(bytes).context("文件内容不是 UTF-8 文本")
+    }
+}
@@ -0,0 +1,98 @@
+// health 子命令：基于 registry 与 GitHub 元数据的依赖健康度报告
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+
+use npm_package_check::lockfile::Lockfile;
+
+use crate::github::{self, GitHub};
+use crate::registry::Registry;
+
+#[derive(Debug, Default)]
+struct HealthReport {
+    stale: Vec<(String, DateTime<Utc>)>,
+    archived: Vec<(String, String)>,
+    single_maintainer: Vec<String>,
+    failed: Vec<(String, String)>,
+}
+
+pub fn run_health(
+    lock_data: &Lockfile,
+    registry: &Registry,
+    github: Option<&GitHub>,
+    years: u32,
+    verbose: bool,
+) -> Result<()> {
+    let names = crate::all_package_names(lock_data);
+    let threshold = Utc::now() - chrono::Duration::days(365 * i64::from(years));
+    let mut report = HealthReport::default();
+
+    println!("🩺 依赖健康检查: {} 个包", names.len());
+
+    for name in &names {
+        if verbose {
+            println!("   查询: {}", name);
+        }
+
+        let packument = match registry.packument(name) {
+            Ok(Some(p)) => p,
+            Ok(None) => {
+                report.failed.push((name.clone(), "registry 中不存在该包".to_string()));
+                continue;
+            }
+            Err(e) => {
+                report.failed.push((name.clone(), format!("{:#}", e)));
+                continue;
+            }
+        };
+
+        if let Some(last) = packument.last_release()
+            && last < threshold
+        {
+            report.stale.push((name.clone(), last));
+        }
+
+        if packument.maintainer_count() == 1 {
+            report.single_maintainer.push(name.clone());
+        }
+
+        if let Some(github) = github
+            && let Some((owner, repo)) = packument.repository_url().and_then(github::parse_repo_url)
+        {
+            match github.repo(&owner, &repo) {
+                Ok(info) if info.archived => {
+                    report.archived.push((name.clone(), format!("{}/{}", owner, repo)));
+                }
+                Ok(_) => {}
+                Err(e) => report.failed.push((name.clone(), format!("{:#}", e))),
+            }
+        }
+    }
+
+    println!();
+    println!("⏳ 超过 {} 年未发布新版本 ({}):", years, report.stale.len());
+    for (name, last) in &report.stale {
+        println!("   - {} (最后发布: {})", name, last.format("%Y-%m-%d"));
+    }
+
+    if github.is_some() {
+        println!("📦 源码仓库已归档 ({}):", report.archived.len());
+        for (name, repo) in &report.archived {
+            println!("   - {} ({})", name, repo);
+        }
+    }
+
+    println!("👤 仅有单一维护者 ({}):", report.single_maintainer.len());
+    for name in &report.single_maintainer {
+        println!("   - {}", name);
+    }
+
+    if !report.failed.is_empty() {
+        println!("⚠️ 查询失败 ({}):", report.failed.len());
+        for (name, reason) in &report.failed {
+            println!("   - {}: {}", name, reason);
+        }
+    }
+
+    Ok(())
+}
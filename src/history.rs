@@ -0,0 +1,153 @@
+// history 子命令：沿锁文件的全部 git 历史逐个提交执行检查，报告每个受影响版本首次与最后一次出现的提交，
+// 用于确认事件期间仓库是否曾经引入过受影响版本，即使当前的锁文件中已经没有
+//
+// 每个提交的判定与批量检查相同：清单中的同名条目合并期望版本，按 --verdict-mode 判定，命中时记录实际命中的版本。
+// 删除锁文件的提交视为不包含任何包，此前出现的版本记为在该提交中移除；无法解析的提交（例如更早的锁文件格式）跳过
+
+use anyhow::{Result, bail};
+use chrono::{DateTime, FixedOffset};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use crate::exposure::{LockHistory, RevisionLock, short};
+use crate::http::Client;
+use crate::{
+    Args, BatchPackage, STDIN_LOCKFILE, archive, evaluate_status, find_package_in_lock, is_remote, load_batch_packages,
+    matched_in, matching, merge_expected_versions, resolve_lockfile_in_dir,
+};
+
+type Commit<'a> = (&'a str, DateTime<FixedOffset>);
+
+/// 一个受影响版本在历史中的出现情况
+struct Occurrence<'a> {
+    first: Commit<'a>,
+    last: Commit<'a>,
+    /// 最后一次出现之后第一个不再包含它的提交，为 None 表示仍存在于最新的提交
+    removed: Option<Commit<'a>>,
+    /// 包含它的提交数
+    commits: usize,
+}
+
+/// package 为 None 时检查 -b/--batch 指定的清单
+pub fn run_history(args: &Args, client: &Client, package: Option<&str>, version: Option<&str>) -> Result<i32> {
+    let file = &args.file;
+    if file == STDIN_LOCKFILE || is_remote(file) || archive::is_archive(file) {
+        bail!("history 需要读取锁文件的 git 历史，只能用于本地锁文件");
+    }
+    // 锁文件在最新的提交中被删除时工作区中已经没有这个文件，仍按 git 历史检查
+    let lockfile = match Path::new(file) {
+        path if path.is_dir() => resolve_lockfile_in_dir(path, &args.exclude, args.follow_symlinks)?,
+        _ => file.clone(),
+    };
+
+    let packages = match (package, &args.batch) {
+        (Some(name), _) => {
+            if let Some(version) = version {
+                matching::validate(version, args.match_mode)?;
+            }
+            vec![BatchPackage {
+                name: name.to_string(),
+                versions: version.into_iter().map(str::to_string).collect(),
+                status: None,
+                detection_date: None,
+                fixed_in: Vec::new(),
+                advisories: Vec::new(),
+                match_mode: args.match_mode,
+            }]
+        }
+        (None, Some(batch_file)) => load_batch_packages(args, client, batch_file)?,
+        (None, None) => bail!("必须指定包名或使用批量模式(-b/--batch)"),
+    };
+    let merged = merge_expected_versions(&packages);
+
+    let history = LockHistory::load(Path::new(&lockfile), args.timezone)?;
+    let mut occurrences: BTreeMap<(String, String), Occurrence> = BTreeMap::new();
+    let mut previous: BTreeSet<(String, String)> = BTreeSet::new();
+    let mut skipped = 0;
+    let total = history.revision_count();
+    println!("🕰️ 锁文件历史: {}，共 {} 个提交", lockfile, total);
+
+    for (commit, date, lock) in history.revisions() {
+        let index = match lock {
+            RevisionLock::Parsed(lock) => Some(lock.index()),
+            // 锁文件被删除时不包含任何包，present 为空
+            RevisionLock::Deleted => None,
+            RevisionLock::Unparsed => {
+                skipped += 1;
+                continue;
+            }
+        };
+        let mut present = BTreeSet::new();
+        for package in &packages {
+            let found = match &index {
+                Some(index) => find_package_in_lock(index, &package.name),
+                None => Vec::new(),
+            };
+            let merged_package;
+            let package = match merged.get(package.name.as_str()) {
+                Some(versions) => {
+                    merged_package = BatchPackage { versions: versions.clone(), ..package.clone() };
+                    &merged_package
+                }
+                None => package,
+            };
+            if evaluate_status(&found, package, args.verdict_mode).is_hit() {
+                for version in matched_in(&found, package) {
+                    present.insert((package.name.clone(), version));
+                }
+            }
+        }
+        if args.verbose {
+            println!("   {} {}: {} 个受影响版本", short(commit), date.format("%Y-%m-%d"), present.len());
+        }
+
+        for key in &present {
+            occurrences
+                .entry(key.clone())
+                .and_modify(|occurrence| {
+                    occurrence.last = (commit, date);
+                    occurrence.removed = None;
+                    occurrence.commits += 1;
+                })
+                .or_insert(Occurrence { first: (commit, date), last: (commit, date), removed: None, commits: 1 });
+        }
+        for key in previous.difference(&present) {
+            if let Some(occurrence) = occurrences.get_mut(key) {
+                occurrence.removed = Some((commit, date));
+            }
+        }
+        previous = present;
+    }
+
+    if skipped > 0 {
+        println!("⚠️ {} 个提交中的锁文件无法解析，已跳过", skipped);
+    }
+
+    if occurrences.is_empty() {
+        println!("\n✅ 锁文件的历史中从未出现过受影响版本");
+        return Ok(0);
+    }
+
+    for ((name, version), occurrence) in &occurrences {
+        println!("\n⚠️ {}@{}", name, version);
+        println!("   首次出现: {}", describe(occurrence.first));
+        println!("   最后出现: {}", describe(occurrence.last));
+        match occurrence.removed {
+            Some(removed) => println!("   移除于: {}", describe(removed)),
+            None => println!("   🔴 仍存在于最新的提交"),
+        }
+        println!("   出现在 {}/{} 个提交中", occurrence.commits, total - skipped);
+    }
+
+    let remaining = occurrences.values().filter(|occurrence| occurrence.removed.is_none()).count();
+    println!(
+        "\n📋 共 {} 个受影响版本曾出现在锁文件的历史中，其中 {} 个仍存在于最新的提交",
+        occurrences.len(),
+        remaining
+    );
+    Ok(1)
+}
+
+fn describe((commit, date): Commit) -> String {
+    format!("{} ({})", date.format("%Y-%m-%d %H:%M"), short(commit))
+}
@@ -0,0 +1,246 @@
+// 网络功能共用的 HTTP 客户端，支持从离线包回放或记录请求
+
+use anyhow::{Context, Result, bail};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use ureq::Agent;
+use ureq::tls::{Certificate, PemItem, RootCerts, TlsConfig};
+
+/// 离线包：远程输入的响应，key 为 "GET url" 或 "POST url 请求体"，值为 None 表示 404
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Bundle {
+    pub created_at: String,
+    pub lockfile: String,
+    pub responses: BTreeMap<String, Option<String>>,
+}
+
+impl Bundle {
+    pub fn load(path: &str) -> Result<Self> {
+        let content = fs::read_to_string(path).with_context(|| format!("无法读取离线包 '{}'", path))?;
+        serde_json::from_str(&content).with_context(|| format!("解析离线包 '{}' 失败", path))
+    }
+
+    pub fn write(&self, path: &str) -> Result<()> {
+        let json = crate::report::canonical_json(self, true)?;
+        fs::write(path, json).with_context(|| format!("无法写入文件 '{}'", path))
+    }
+}
+
+#[derive(Clone)]
+pub struct Client {
+    agent: Agent,
+    // 使用离线包时所有请求从中读取，不访问网络
+    offline: Option<Arc<Bundle>>,
+    // 创建离线包时记录每个请求的响应
+    recorder: Option<Arc<Mutex<Bundle>>>,
+}
+
+impl Client {
+    /// proxy 为 http(s):// 或 socks5:// 地址，未指定时读取 HTTPS_PROXY 等环境变量（同时遵循 NO_PROXY）；
+    /// cacert 为 PEM 格式的 CA 证书文件，指定后与系统的 CA 证书一起信任（见 system_certificates）
+    pub fn new(proxy: Option<&str>, cacert: Option<&str>) -> Result<Self> {
+        let mut config = Agent::config_builder()
+            .timeout_global(Some(Duration::from_secs(30)))
+            .user_agent(concat!("npm_package_check/", env!("CARGO_PKG_VERSION")));
+        if let Some(proxy) = proxy {
+            let proxy = ureq::Proxy::new(proxy).with_context(|| format!("无效的代理地址 '{}'", proxy))?;
+            config = config.proxy(Some(proxy));
+        }
+        if let Some(path) = cacert {
+            let mut certs = system_certificates()?;
+            if certs.is_empty() {
+                eprintln!("⚠️ 没有找到系统的 CA 证书包（可用 SSL_CERT_FILE 指定），只信任 '{}' 中的证书", path);
+            }
+            certs.extend(load_certificates(path)?);
+            config = config.tls_config(TlsConfig::builder().root_certs(RootCerts::new_with_certs(&certs)).build());
+        }
+        Ok(Self {
+            agent: config.build().into(),
+            offline: None,
+            recorder: None,
+        })
+    }
+
+    /// 只从离线包读取响应的客户端
+    pub fn offline(bundle: Bundle) -> Self {
+        Self {
+            agent: Agent::new_with_defaults(),
+            offline: Some(Arc::new(bundle)),
+            recorder: None,
+        }
+    }
+
+    /// 开始记录响应，返回记录用的离线包
+    pub fn record(&mut self, bundle: Bundle) -> Arc<Mutex<Bundle>> {
+        let recorder = Arc::new(Mutex::new(bundle));
+        self.recorder = Some(recorder.clone());
+        recorder
+    }
+
+    /// 按 key 查找离线包或发起请求，记录并返回响应文本，404 时返回 None
+    fn fetch(&self, key: String, request: impl FnOnce() -> Result<Option<String>>) -> Result<Option<String>> {
+        if let Some(ref bundle) = self.offline {
+            return match bundle.responses.get(&key) {
+                Some(response) => Ok(response.clone()),
+                None => bail!("离线包中没有该请求的记录: {}", key),
+            };
+        }
+        let response = request()?;
+        if let Some(ref recorder) = self.recorder {
+            recorder.lock().unwrap().responses.insert(key, response.clone());
+        }
+        Ok(response)
+    }
+}
+
+// 系统 CA 证书包的常见位置：Debian/Ubuntu/Alpine/Arch、Fedora/RHEL、openSUSE、macOS/BSD
+const SYSTEM_CA_BUNDLES: &[&str] = &[
+    "/etc/ssl/certs/ca-certificates.crt",
+    "/etc/pki/tls/certs/ca-bundle.crt",
+    "/etc/ssl/ca-bundle.pem",
+    "/etc/ssl/cert.pem",
+];
+
+/// 系统的 CA 证书：SSL_CERT_FILE 指定的文件，否则为常见位置中第一个存在的证书包；都不存在时为空。
+/// 内置的根证书不能与额外的证书合并，--cacert 以系统证书为基础追加，代理之外的主机仍能正常验证
+fn system_certificates() -> Result<Vec<Certificate<'static>>> {
+    if let Some(path) = std::env::var("SSL_CERT_FILE").ok().filter(|path| !path.is_empty()) {
+        return load_certificates(&path);
+    }
+    match SYSTEM_CA_BUNDLES.iter().find(|path| Path::new(path).is_file()) {
+        Some(path) => load_certificates(path),
+        None => Ok(Vec::new()),
+    }
+}
+
+fn load_certificates(path: &str) -> Result<Vec<Certificate<'static>>> {
+    let pem = fs::read(path).with_context(|| format!("无法读取 CA 证书文件 '{}'", path))?;
+    let mut certs = Vec::new();
+    for item in ureq::tls::parse_pem(&pem) {
+        if let PemItem::Certificate(cert) = item.with_context(|| format!("解析 CA 证书文件 '{}' 失败", path))? {
+            certs.push(cert);
+        }
+    }
+    if certs.is_empty() {
+        bail!("CA 证书文件 '{}' 中没有证书", path);
+    }
+    Ok(certs)
+}
+
+fn read_body(response: ureq::http::Response<ureq::Body>, url: &str) -> Result<String> {
+    response
+        .into_body()
+        .with_config()
+        .limit(64 * 1024 * 1024)
+        .read_to_string()
+        .with_context(|| format!("读取响应失败: {}", url))
+}
+
+/// GET 并解析 JSON，404 时返回 None
+pub fn get_json<T: DeserializeOwned>(
+    client: &Client,
+    url: &str,
+    headers: &[(&str, &str)],
+) -> Result<Option<T>> {
+    let body = client.fetch(format!("GET {}", url), || {
+        let mut request = client.agent.get(url);
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+        match request.call() {
+            Ok(response) => read_body(response, url).map(Some),
+            Err(ureq::Error::StatusCode(404)) => Ok(None),
+            Err(e) => Err(e).with_context(|| format!("请求失败: {}", url)),
+        }
+    })?;
+    body.map(|body| serde_json::from_str(&body).with_context(|| format!("解析响应失败: {}", url)))
+        .transpose()
+}
+
+// 下载大文件时不限制总耗时，但连接、等待响应头与读取响应体各有上限，registry 停止响应时不会一直等待；
+// ureq 没有按每次读取计算的空闲超时，读取响应体以较宽松的总时长代替
+const DOWNLOAD_CONNECT_TIMEOUT: Duration = Duration::from_secs(30);
+const DOWNLOAD_RESPONSE_TIMEOUT: Duration = Duration::from_secs(60);
+const DOWNLOAD_BODY_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// GET 原始响应：4xx/5xx 不视为错误，由调用方按状态码与响应头处理（例如 OCI registry 的 Bearer 认证）。
+/// 用于下载镜像层等大文件，不限制总耗时（连接与读取仍有超时），也不经过离线包的记录与回放
+pub fn get_response(client: &Client, url: &str, headers: &[(&str, &str)]) -> Result<ureq::http::Response<ureq::Body>> {
+    if client.offline.is_some() {
+        bail!("使用离线包时无法下载: {}", url);
+    }
+    let mut request = client
+        .agent
+        .get(url)
+        .config()
+        .http_status_as_error(false)
+        .timeout_global(None)
+        .timeout_connect(Some(DOWNLOAD_CONNECT_TIMEOUT))
+        .timeout_send_request(Some(DOWNLOAD_CONNECT_TIMEOUT))
+        .timeout_recv_response(Some(DOWNLOAD_RESPONSE_TIMEOUT))
+        .timeout_recv_body(Some(DOWNLOAD_BODY_TIMEOUT))
+        .build();
+    for (name, value) in headers {
+        request = request.header(*name, *value);
+    }
+    request.call().with_context(|| format!("请求失败: {}", url))
+}
+
+/// GET 文本内容
+pub fn get_text(client: &Client, url: &str) -> Result<String> {
+    get_text_with(client, url, &[])
+}
+
+/// 附带请求头 GET 文本内容；请求头不计入离线包中记录的请求，认证信息不会写入离线包
+pub fn get_text_with(client: &Client, url: &str, headers: &[(&str, String)]) -> Result<String> {
+    client
+        .fetch(format!("GET {}", url), || {
+            let mut request = client.agent.get(url);
+            for (name, value) in headers {
+                request = request.header(*name, value.as_str());
+            }
+            let response = request.call().with_context(|| format!("请求失败: {}", url))?;
+            read_body(response, url).map(Some)
+        })?
+        .with_context(|| format!("请求失败: {}", url))
+}
+
+/// 有 token 时的 Authorization: Bearer 请求头
+pub fn bearer(token: Option<&str>) -> Vec<(&'static str, String)> {
+    token.map(|token| ("Authorization", format!("Bearer {}", token))).into_iter().collect()
+}
+
+/// POST JSON 请求体并解析 JSON 响应
+pub fn post_json<B: Serialize, T: DeserializeOwned>(client: &Client, url: &str, body: &B) -> Result<T> {
+    let payload = serde_json::to_string(body)?;
+    let response = client
+        .fetch(format!("POST {} {}", url, payload), || {
+            let response = client
+                .agent
+                .post(url)
+                .content_type("application/json")
+                .send(payload.as_str())
+                .with_context(|| format!("请求失败: {}", url))?;
+            read_body(response, url).map(Some)
+        })?
+        .with_context(|| format!("请求失败: {}", url))?;
+    serde_json::from_str(&response).with_context(|| format!("解析响应失败: {}", url))
+}
+
+/// POST JSON 请求体，忽略响应内容；用于发送通知，不会被记录或回放
+pub fn post(client: &Client, url: &str, body: &impl Serialize) -> Result<()> {
+    if client.offline.is_some() {
+        bail!("使用离线包时无法发送请求: {}", url);
+    }
+    client
+        .agent
+        .post(url)
+        .send_json(body)
+        .with_context(|| format!("请求失败: {}", url))?;
+    Ok(())
+}
@@ -0,0 +1,64 @@
+// identical 子命令：按 integrity 找出以不同包名发布、tarball 内容却逐字节相同的包，
+// 这类包通常是 fork 后原样重新发布或抢注的包名，需要确认实际使用的是哪一个
+//
+// integrity 为 SRI 格式，可能同时列出多个摘要（sha512-... sha1-...），优先按 sha512 分组，否则取第一个；
+// 同一个包名的多个版本内容相同（重新发布）不算在内
+
+use anyhow::Result;
+use std::collections::{BTreeMap, BTreeSet};
+
+use npm_package_check::lockfile::Lockfile;
+
+use crate::collation;
+
+/// 分组使用的摘要
+fn digest(integrity: &str) -> Option<&str> {
+    let mut hashes = integrity.split_whitespace();
+    let first = hashes.clone().next()?;
+    Some(hashes.find(|hash| hash.starts_with("sha512-")).unwrap_or(first))
+}
+
+/// 控制台中截短的摘要
+fn short(digest: &str) -> String {
+    match digest.char_indices().nth(24) {
+        Some((end, _)) => format!("{}…", &digest[..end]),
+        None => digest.to_string(),
+    }
+}
+
+pub fn run_identical(lock_data: &Lockfile, verbose: bool) -> Result<()> {
+    let mut groups: BTreeMap<&str, BTreeSet<(&str, &str)>> = BTreeMap::new();
+    for (key, package_info) in &lock_data.packages {
+        let (Some(package), Some(digest)) = (lock_data.locked.get(key), package_info.resolution.integrity().and_then(digest))
+        else {
+            continue;
+        };
+        groups.entry(digest).or_default().insert((&package.name, &package.version));
+    }
+    let total: usize = groups.values().map(BTreeSet::len).sum();
+    println!("🔍 检查 {} 个带 integrity 的包\n", total);
+
+    let duplicated: Vec<_> = groups
+        .iter()
+        .filter(|(_, packages)| packages.iter().map(|(name, _)| name).collect::<BTreeSet<_>>().len() > 1)
+        .collect();
+    if duplicated.is_empty() {
+        println!("✅ 没有以不同包名发布的相同 tarball");
+        return Ok(());
+    }
+
+    for (digest, packages) in &duplicated {
+        println!("⚠️ {}", if verbose { digest.to_string() } else { short(digest) });
+        let mut packages: Vec<_> = packages.iter().collect();
+        collation::sort_by_key(&mut packages, |(name, _)| name);
+        for (name, version) in packages {
+            println!("   - {}@{}", name, version);
+        }
+        println!();
+    }
+    println!(
+        "⚠️ {} 组 tarball 内容相同但包名不同，可能是 fork 后原样发布或抢注的包名，请确认实际需要的是哪一个",
+        duplicated.len()
+    );
+    std::process::exit(1);
+}
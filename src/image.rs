@@ -0,0 +1,533 @@
+// image 子命令：检查容器镜像中实际发布的依赖，而不是仓库中的锁文件
+//
+// 镜像来源：
+//   本地文件        docker save / podman save 导出的 tar（manifest.json）或 OCI 镜像布局的 tar（index.json），可以是 .tar.gz
+//   镜像引用        [registry/]repository[:tag|@digest]，通过 registry HTTP API v2 拉取，默认 registry 为 Docker Hub；
+//                   需要登录时按 WWW-Authenticate 获取 Bearer token（--registry-auth 提供用户名与密码），
+//                   多平台镜像按 --platform 选择
+// 镜像层按顺序叠加（处理 .wh. 删除标记与 .wh..wh..opq 目录覆盖），只在内存中保留锁文件与 node_modules 中各包的
+// package.json，其余内容不解压；含有 .. 的条目路径视为路径穿越并跳过。最终文件系统中的锁文件与每个 node_modules 目录（由已安装的包生成锁文件模型）
+// 分别执行单包查询或批量检查，最后汇总。镜像中没有可用的 package.json 与 git 历史，不能使用依赖这些信息的选项
+
+use anyhow::{Context, Result, bail};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use flate2::read::GzDecoder;
+use serde::Deserialize;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::Path;
+
+use crate::http::{self, Client};
+use crate::multi::{self, Summary};
+use crate::node_modules::{installed_lockfile, is_installed_manifest};
+use crate::{Args, archive, reject_local_options, scan};
+
+const MANIFEST_TYPES: &str = "application/vnd.oci.image.index.v1+json, \
+     application/vnd.docker.distribution.manifest.list.v2+json, \
+     application/vnd.oci.image.manifest.v1+json, \
+     application/vnd.docker.distribution.manifest.v2+json";
+
+/// 本地镜像 tar 中小于该大小的条目在第一遍读取时保留在内存中（清单、配置与较小的镜像层）
+const SMALL_ENTRY: u64 = 4 * 1024 * 1024;
+
+/// 镜像文件系统中保留的文件
+enum Entry {
+    Lockfile(String),
+    /// node_modules 中已安装的包 (包名, 版本)
+    Installed(String, String),
+}
+
+/// 一个镜像层的变更
+#[derive(Default)]
+struct LayerDelta {
+    files: Vec<(String, Entry)>,
+    /// .wh.<name> 删除的路径
+    whiteouts: Vec<String>,
+    /// .wh..wh..opq 清空的目录
+    opaque: Vec<String>,
+}
+
+impl LayerDelta {
+    /// 叠加到下层的文件系统上：删除标记只作用于下层，再写入本层的文件
+    fn apply(self, files: &mut BTreeMap<String, Entry>) {
+        for dir in &self.opaque {
+            files.retain(|path, _| !path.starts_with(&format!("{}/", dir)));
+        }
+        for removed in &self.whiteouts {
+            files.retain(|path, _| path != removed && !path.starts_with(&format!("{}/", removed)));
+        }
+        files.extend(self.files);
+    }
+}
+
+#[derive(Deserialize)]
+struct InstalledManifest {
+    name: Option<String>,
+    version: Option<String>,
+}
+
+fn is_lockfile(path: &str) -> bool {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    scan::LOCKFILE_NAMES.contains(&name) && !path.split('/').any(|part| part == "node_modules")
+}
+
+/// 按内容判断压缩方式，返回解压后的读取器
+fn decompress<'a, R: Read + 'a>(reader: R) -> Result<Box<dyn Read + 'a>> {
+    let mut reader = BufReader::new(reader);
+    let magic = reader.fill_buf().context("读取镜像层失败")?;
+    if magic.starts_with(&[0x1f, 0x8b]) {
+        Ok(Box::new(GzDecoder::new(reader)))
+    } else if magic.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+        bail!("不支持 zstd 压缩的镜像层，请先用 docker save 导出为 tar 后再检查")
+    } else {
+        Ok(Box::new(reader))
+    }
+}
+
+/// 读取一个镜像层（tar，可以是 gzip 压缩的）中的锁文件、已安装包的 package.json 与删除标记
+fn read_layer<R: Read>(reader: R) -> Result<LayerDelta> {
+    let mut delta = LayerDelta::default();
+    let mut archive = tar::Archive::new(decompress(reader)?);
+    for entry in archive.entries().context("读取镜像层失败")? {
+        let mut entry = entry.context("读取镜像层失败")?;
+        let Some(path) = archive::sanitize_path(&entry.path().context("镜像层中的路径无效")?.to_string_lossy()) else {
+            continue;
+        };
+        let (dir, name) = match path.rsplit_once('/') {
+            Some((dir, name)) => (dir, name),
+            None => ("", path.as_str()),
+        };
+        let join = |name: &str| if dir.is_empty() { name.to_string() } else { format!("{}/{}", dir, name) };
+        if name == ".wh..wh..opq" {
+            delta.opaque.push(dir.to_string());
+            continue;
+        }
+        if let Some(removed) = name.strip_prefix(".wh.") {
+            delta.whiteouts.push(join(removed));
+            continue;
+        }
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        // 镜像同样可能来自不可信的来源，条目内容按压缩包的大小上限读取
+        if is_lockfile(&path) {
+            if let Some(content) = archive::read_text_entry(&mut entry, &path)? {
+                delta.files.push((path, Entry::Lockfile(content)));
+            }
+        } else if is_installed_manifest(&path)
+            && let Some(content) = archive::read_entry(&mut entry, &path)?
+            && let Ok(InstalledManifest { name: Some(name), version: Some(version) }) = serde_json::from_slice(&content)
+        {
+            delta.files.push((path, Entry::Installed(name, version)));
+        }
+    }
+    Ok(delta)
+}
+
+#[derive(Deserialize)]
+struct SavedManifest {
+    #[serde(rename = "Layers")]
+    layers: Vec<String>,
+}
+
+/// OCI 镜像索引或 docker manifest list
+#[derive(Deserialize)]
+struct Index {
+    manifests: Vec<Descriptor>,
+}
+
+/// OCI 镜像清单或 docker manifest v2
+#[derive(Deserialize)]
+struct ImageManifest {
+    layers: Vec<Descriptor>,
+}
+
+#[derive(Deserialize)]
+struct Descriptor {
+    #[serde(rename = "mediaType", default)]
+    media_type: String,
+    digest: String,
+    #[serde(default)]
+    size: u64,
+    #[serde(default)]
+    platform: Option<Platform>,
+}
+
+#[derive(Deserialize)]
+struct Platform {
+    os: String,
+    architecture: String,
+    #[serde(default)]
+    variant: Option<String>,
+}
+
+/// 按 os/architecture[/variant] 选择多平台镜像中的一个清单
+fn select_platform<'a>(index: &'a Index, platform: &str) -> Result<&'a Descriptor> {
+    let wanted: Vec<&str> = platform.split('/').collect();
+    index
+        .manifests
+        .iter()
+        .find(|descriptor| {
+            descriptor.platform.as_ref().is_some_and(|p| match wanted.as_slice() {
+                [os, arch] => p.os == *os && p.architecture == *arch,
+                [os, arch, variant] => p.os == *os && p.architecture == *arch && p.variant.as_deref() == Some(*variant),
+                _ => false,
+            })
+        })
+        .with_context(|| {
+            let available: Vec<String> = index
+                .manifests
+                .iter()
+                .filter_map(|d| d.platform.as_ref())
+                .map(|p| match p.variant {
+                    Some(ref variant) => format!("{}/{}/{}", p.os, p.architecture, variant),
+                    None => format!("{}/{}", p.os, p.architecture),
+                })
+                .collect();
+            format!("镜像中没有 {} 平台的清单（可用: {}）", platform, available.join(", "))
+        })
+}
+
+fn check_layer_type(descriptor: &Descriptor) -> Result<()> {
+    if descriptor.media_type.contains("zstd") {
+        bail!("不支持 zstd 压缩的镜像层 {}，请先用 docker save 导出为 tar 后再检查", descriptor.digest);
+    }
+    Ok(())
+}
+
+fn blob_path(digest: &str) -> String {
+    format!("blobs/{}", digest.replacen(':', "/", 1))
+}
+
+/// 读取本地镜像 tar 的全部条目，small 中保留较小的条目，visit 处理其余条目
+fn scan_saved(path: &str, mut visit: impl FnMut(&str, &mut dyn Read) -> Result<()>) -> Result<HashMap<String, Vec<u8>>> {
+    let file = File::open(path).with_context(|| format!("无法读取文件 '{}'", path))?;
+    let mut archive = tar::Archive::new(decompress(file)?);
+    let mut small = HashMap::new();
+    for entry in archive.entries().context("读取镜像 tar 失败")? {
+        let mut entry = entry.context("读取镜像 tar 失败")?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let Some(name) = archive::sanitize_path(&entry.path().context("镜像 tar 中的路径无效")?.to_string_lossy()) else {
+            continue;
+        };
+        if entry.size() <= SMALL_ENTRY {
+            let mut content = Vec::new();
+            entry.read_to_end(&mut content).with_context(|| format!("无法读取 '{}'", name))?;
+            small.insert(name, content);
+        } else {
+            visit(&name, &mut entry)?;
+        }
+    }
+    Ok(small)
+}
+
+/// docker save 或 OCI 镜像布局导出的 tar，返回按顺序排列的镜像层
+fn read_saved(path: &str, platform: &str) -> Result<Vec<LayerDelta>> {
+    let small = scan_saved(path, |_, _| Ok(()))?;
+    let parse_blob = |digest: &str| -> Result<&Vec<u8>> {
+        small.get(&blob_path(digest)).with_context(|| format!("镜像 tar 中缺少 {}", digest))
+    };
+
+    let layers: Vec<String> = if let Some(content) = small.get("manifest.json") {
+        let manifests: Vec<SavedManifest> = serde_json::from_slice(content).context("解析 manifest.json 失败")?;
+        if manifests.len() > 1 {
+            eprintln!("⚠️ '{}' 中有 {} 个镜像，只检查第一个", path, manifests.len());
+        }
+        let manifest = manifests.into_iter().next().context("manifest.json 中没有镜像")?;
+        manifest
+            .layers
+            .iter()
+            .map(|layer| archive::sanitize_path(layer).with_context(|| format!("manifest.json 中的镜像层路径无效: {}", layer)))
+            .collect::<Result<_>>()?
+    } else if let Some(content) = small.get("index.json") {
+        let mut index: Index = serde_json::from_slice(content).context("解析 index.json 失败")?;
+        // 顶层 index.json 通常只有一个清单，指向多平台索引或单个镜像清单
+        let mut descriptor = index.manifests.first().context("index.json 中没有镜像")?;
+        loop {
+            let blob = parse_blob(&descriptor.digest)?;
+            let value: serde_json::Value = serde_json::from_slice(blob).with_context(|| format!("解析 {} 失败", descriptor.digest))?;
+            if value.get("manifests").is_some() {
+                index = serde_json::from_value(value).with_context(|| format!("解析 {} 失败", descriptor.digest))?;
+                descriptor = select_platform(&index, platform)?;
+                continue;
+            }
+            let manifest: ImageManifest =
+                serde_json::from_value(value).with_context(|| format!("解析 {} 失败", descriptor.digest))?;
+            for layer in &manifest.layers {
+                check_layer_type(layer)?;
+            }
+            break manifest.layers.iter().map(|layer| blob_path(&layer.digest)).collect();
+        }
+    } else {
+        bail!("'{}' 不是 docker save 或 OCI 镜像布局导出的 tar（没有 manifest.json 或 index.json）", path);
+    };
+
+    // 较大的镜像层第二遍按 tar 中的顺序读取，之后按清单中的顺序叠加
+    let mut deltas: HashMap<String, LayerDelta> = HashMap::new();
+    for layer in &layers {
+        if let Some(content) = small.get(layer) {
+            deltas.insert(layer.clone(), read_layer(content.as_slice()).with_context(|| format!("读取镜像层 '{}' 失败", layer))?);
+        }
+    }
+    if deltas.len() < layers.len() {
+        scan_saved(path, |name, reader| {
+            if layers.iter().any(|layer| layer == name) {
+                deltas.insert(name.to_string(), read_layer(reader).with_context(|| format!("读取镜像层 '{}' 失败", name))?);
+            }
+            Ok(())
+        })?;
+    }
+    layers
+        .iter()
+        .map(|layer| deltas.remove(layer).with_context(|| format!("镜像 tar 中缺少镜像层 '{}'", layer)))
+        .collect()
+}
+
+/// 镜像引用 [registry/]repository[:tag|@digest]
+#[derive(Debug, PartialEq)]
+struct Reference {
+    registry: String,
+    repository: String,
+    reference: String,
+}
+
+fn parse_reference(image: &str) -> Result<Reference> {
+    let (name, reference) = match image.split_once('@') {
+        Some((name, digest)) => (name, digest.to_string()),
+        None => match image.rsplit_once(':') {
+            Some((name, tag)) if !tag.contains('/') => (name, tag.to_string()),
+            _ => (image, "latest".to_string()),
+        },
+    };
+    if name.is_empty() {
+        bail!("无效的镜像引用 '{}'", image);
+    }
+    // 第一段含 . 或 :（端口），或为 localhost 时是 registry 地址，否则为 Docker Hub 上的仓库
+    let (registry, repository) = match name.split_once('/') {
+        Some((first, rest)) if first.contains(['.', ':']) || first == "localhost" => (first.to_string(), rest.to_string()),
+        _ => ("docker.io".to_string(), name.to_string()),
+    };
+    let (registry, repository) = match registry.as_str() {
+        "docker.io" | "index.docker.io" if !repository.contains('/') => {
+            ("registry-1.docker.io".to_string(), format!("library/{}", repository))
+        }
+        "docker.io" | "index.docker.io" => ("registry-1.docker.io".to_string(), repository),
+        _ => (registry, repository),
+    };
+    Ok(Reference { registry, repository, reference })
+}
+
+/// registry HTTP API v2 的客户端，按需获取 Bearer token
+struct Registry<'a> {
+    client: &'a Client,
+    base: String,
+    repository: String,
+    /// 用户名:密码
+    auth: Option<&'a str>,
+    authorization: RefCell<Option<String>>,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    #[serde(default)]
+    token: Option<String>,
+    #[serde(default)]
+    access_token: Option<String>,
+}
+
+impl<'a> Registry<'a> {
+    fn new(client: &'a Client, reference: &Reference, auth: Option<&'a str>) -> Self {
+        // 与 docker 一致，本机的 registry 使用 http
+        let host = reference.registry.split(':').next().unwrap_or_default();
+        let scheme = if host == "localhost" || host == "127.0.0.1" { "http" } else { "https" };
+        Registry {
+            client,
+            base: format!("{}://{}", scheme, reference.registry),
+            repository: reference.repository.clone(),
+            auth,
+            authorization: RefCell::new(None),
+        }
+    }
+
+    fn basic(&self) -> Option<String> {
+        self.auth.map(|auth| format!("Basic {}", STANDARD.encode(auth)))
+    }
+
+    /// 按 WWW-Authenticate 的要求获取凭据
+    fn authenticate(&self, challenge: &str) -> Result<String> {
+        let Some(params) = challenge.strip_prefix("Bearer ") else {
+            return self.basic().context("registry 要求登录，请通过 --registry-auth 提供用户名与密码");
+        };
+        let mut fields = HashMap::new();
+        for part in params.split(',') {
+            if let Some((key, value)) = part.trim().split_once('=') {
+                fields.insert(key, value.trim_matches('"'));
+            }
+        }
+        let realm = fields.get("realm").context("WWW-Authenticate 中没有 realm")?;
+        let mut query = Vec::new();
+        if let Some(service) = fields.get("service") {
+            query.push(format!("service={}", service));
+        }
+        query.push(format!("scope={}", fields.get("scope").map_or_else(
+            || format!("repository:{}:pull", self.repository),
+            |scope| scope.to_string(),
+        )));
+        let url = format!("{}?{}", realm, query.join("&"));
+        let basic = self.basic();
+        let headers: Vec<(&str, &str)> = basic.iter().map(|value| ("Authorization", value.as_str())).collect();
+        let response = http::get_response(self.client, &url, &headers)?;
+        if !response.status().is_success() {
+            bail!("获取 registry token 失败（HTTP {}），私有镜像请通过 --registry-auth 提供用户名与密码", response.status().as_u16());
+        }
+        let body = response.into_body().read_to_string().with_context(|| format!("读取响应失败: {}", url))?;
+        let token: TokenResponse = serde_json::from_str(&body).with_context(|| format!("解析响应失败: {}", url))?;
+        let token = token.token.or(token.access_token).context("registry 没有返回 token")?;
+        Ok(format!("Bearer {}", token))
+    }
+
+    fn get(&self, path: &str, accept: Option<&str>) -> Result<ureq::http::Response<ureq::Body>> {
+        let url = format!("{}/v2/{}/{}", self.base, self.repository, path);
+        for _ in 0..2 {
+            let authorization = self.authorization.borrow().clone();
+            let mut headers = Vec::new();
+            if let Some(accept) = accept {
+                headers.push(("Accept", accept));
+            }
+            if let Some(ref authorization) = authorization {
+                headers.push(("Authorization", authorization.as_str()));
+            }
+            let response = http::get_response(self.client, &url, &headers)?;
+            let status = response.status().as_u16();
+            if status == 401 && authorization.is_none() {
+                let challenge = response
+                    .headers()
+                    .get("www-authenticate")
+                    .and_then(|value| value.to_str().ok())
+                    .context("registry 要求认证，但没有返回 WWW-Authenticate")?
+                    .to_string();
+                *self.authorization.borrow_mut() = Some(self.authenticate(&challenge)?);
+                continue;
+            }
+            match status {
+                200..=299 => return Ok(response),
+                401 | 403 => bail!("没有权限拉取 {}（HTTP {}），私有镜像请通过 --registry-auth 提供用户名与密码", url, status),
+                404 => bail!("registry 中没有 {}", url),
+                status => bail!("请求失败: {}（HTTP {}）", url, status),
+            }
+        }
+        bail!("registry 认证失败: {}", url)
+    }
+
+    fn manifest(&self, reference: &str) -> Result<serde_json::Value> {
+        let response = self.get(&format!("manifests/{}", reference), Some(MANIFEST_TYPES))?;
+        let body = response
+            .into_body()
+            .read_to_string()
+            .with_context(|| format!("读取镜像清单 {} 失败", reference))?;
+        serde_json::from_str(&body).with_context(|| format!("解析镜像清单 {} 失败", reference))
+    }
+}
+
+/// 通过 registry 拉取镜像，返回按顺序排列的镜像层
+fn pull(client: &Client, image: &str, platform: &str, auth: Option<&str>) -> Result<Vec<LayerDelta>> {
+    let reference = parse_reference(image)?;
+    let registry = Registry::new(client, &reference, auth);
+    let mut manifest = registry.manifest(&reference.reference)?;
+    if manifest.get("manifests").is_some() {
+        let index: Index = serde_json::from_value(manifest).context("解析镜像索引失败")?;
+        let descriptor = select_platform(&index, platform)?;
+        manifest = registry.manifest(&descriptor.digest)?;
+    }
+    let manifest: ImageManifest = serde_json::from_value(manifest).context("解析镜像清单失败")?;
+
+    let mut deltas = Vec::new();
+    for (i, layer) in manifest.layers.iter().enumerate() {
+        check_layer_type(layer)?;
+        println!(
+            "⬇️ [{}/{}] {} ({:.1} MB)",
+            i + 1,
+            manifest.layers.len(),
+            &layer.digest[..layer.digest.len().min(19)],
+            layer.size as f64 / 1024.0 / 1024.0
+        );
+        let response = registry.get(&format!("blobs/{}", layer.digest), None)?;
+        let delta = read_layer(response.into_body().into_reader())
+            .with_context(|| format!("读取镜像层 {} 失败", layer.digest))?;
+        deltas.push(delta);
+    }
+    Ok(deltas)
+}
+
+pub fn run_image(args: &mut Args, client: &Client, image: &str, platform: &str, auth: Option<&str>) -> Result<()> {
+    multi::reject_reports(args, "image")?;
+    reject_local_options(args, "容器镜像")?;
+
+    let deltas = if Path::new(image).is_file() {
+        read_saved(image, platform)?
+    } else {
+        println!("🐳 拉取镜像 {}（{}）", image, platform);
+        pull(client, image, platform, auth)?
+    };
+    let mut files = BTreeMap::new();
+    for delta in deltas {
+        delta.apply(&mut files);
+    }
+
+    let mut lockfiles: HashMap<&str, &str> = HashMap::new();
+    // 每个最外层 node_modules 所在目录中已安装的包 (包名, 版本, 安装目录)
+    let mut installed: BTreeMap<&str, Vec<(String, String, String)>> = BTreeMap::new();
+    for (path, entry) in &files {
+        match entry {
+            Entry::Lockfile(content) => {
+                lockfiles.insert(path, content);
+            }
+            Entry::Installed(name, version) => {
+                let root = match path.find("/node_modules/") {
+                    Some(pos) => &path[..pos],
+                    None => "",
+                };
+                let dir = path.trim_end_matches("/package.json");
+                installed.entry(root).or_default().push((name.clone(), version.clone(), format!("/{}", dir)));
+            }
+        }
+    }
+    let paths: Vec<&str> = lockfiles.keys().copied().collect();
+    let selected = scan::select_lockfiles(&paths, &args.exclude)?;
+    if selected.is_empty() && installed.is_empty() {
+        bail!("镜像 '{}' 中没有找到锁文件（{}）或 node_modules", image, scan::LOCKFILE_NAMES.join(", "));
+    }
+    println!(
+        "🐳 {}: 找到 {} 个锁文件，{} 个 node_modules 目录\n",
+        image,
+        selected.len(),
+        installed.len()
+    );
+
+    let node_modules: Vec<String> = installed
+        .keys()
+        .map(|root| if root.is_empty() { "/node_modules".to_string() } else { format!("/{}/node_modules", root) })
+        .collect();
+    let labels: Vec<String> = selected
+        .iter()
+        .map(|path| format!("{}:/{}", image, path))
+        .chain(node_modules.iter().map(|dir| format!("{}:{}", image, dir)))
+        .collect();
+    let total = labels.len();
+    let mut summary = Summary::new(args, labels.clone());
+    for (i, path) in selected.iter().enumerate() {
+        println!("📦 [{}/{}] /{}", i + 1, total, path);
+        summary.check(args, client, &labels[i], Path::new(path), lockfiles[path], false)?;
+    }
+    for (i, (dir, packages)) in node_modules.iter().zip(installed.values()).enumerate() {
+        let i = selected.len() + i;
+        println!("📦 [{}/{}] {}（{} 个已安装的包）", i + 1, total, dir, packages.len());
+        summary.check_lockfile(args, client, &labels[i], &installed_lockfile(packages))?;
+    }
+    summary.finish()
+}
@@ -0,0 +1,165 @@
+// init 子命令：生成带注释的起步文件
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+
+use crate::config::DEFAULT_CONFIG_FILE;
+
+const POLICY_FILE: &str = "npm_package_check.policy.yaml";
+
+const CONFIG_TEMPLATE: &str = "\
+# npm_package_check 配置文件
+# 命令行参数优先于此处的配置。默认读取当前目录下的 npm_package_check.yaml，
+# 也可以通过 --config 指定其他路径。
+
+# 锁文件路径，pnpm-lock.yaml、package-lock.json、npm-shrinkwrap.json、yarn.lock、bun.lock 或 deno.lock（等同于 -f/--file）
+file: pnpm-lock.yaml
+
+# 锁文件格式（等同于 --lockfile-format），默认按文件名判断，文件名无法判断时按内容判断
+# lockfile_format: npm
+
+# 默认使用的批量检查文件（等同于 -b/--batch，命令行指定包名时忽略）
+# batch: batch.version2.txt
+# 批量文件也可以是 CSV，例如 Google Sheets 的导出地址（需要登录时用 --batch-token 或 NPC_BATCH_TOKEN 提供 token）：
+# batch: https://docs.google.com/spreadsheets/d/<id>/export?format=csv&gid=0
+
+# 表头与内置格式不同的批量文件或风险清单：各列的表头名称（不区分大小写），status、detection_date、fixed_in 可省略
+# batch_columns:
+#   name: Package
+#   versions: Affected Versions
+#   status: Severity
+#   detection_date: Reported
+#   fixed_in: Patched
+
+# 批量检查报告输出路径（等同于 --output）
+# output: report.tsv
+
+# 期望版本与判定结果的对应规则（等同于 --verdict-mode）
+#   any       任一期望版本命中即为找到
+#   all       所有期望版本均命中为找到，仅部分命中为部分匹配
+#   exact-set 实际版本集合必须与期望版本集合完全一致
+verdict_mode: all
+
+# 版本匹配方式（等同于 --match-mode）
+#   exact   版本号完全一致
+#   prefix  版本号一致，或 1.0 匹配 1.0.x 系列版本
+#   semver  npm 风格的版本范围，如 ^4.8.0
+#   regex   正则表达式
+# 单个期望版本可以用 exact:/prefix:/semver:/regex: 前缀覆盖
+match_mode: prefix
+
+# Status 列映射策略文件（等同于 --policy）
+policy: npm_package_check.policy.yaml
+
+# 本地使用统计文件（等同于 --usage-stats），每次检查后追加一行概要，只写本地文件
+# usage_stats: .npm_package_check.usage.jsonl
+
+# 输出中包名、importer 路径等的排序规则（等同于 --sort-locale），例如 zh 按拼音、zh-u-co-stroke 按笔画
+# sort_locale: zh
+
+# mirrors 子命令允许的镜像（等同于 --allow）：主机名（包括其子域名）或地址前缀
+# allowed_mirrors:
+#   - registry.npmmirror.com
+#   - https://npm.example.com/repository/npm/
+
+# watch-feed 子命令：风险清单地址、检查的锁文件或目录、新增命中时通知的 webhook，
+# 通知粒度（per-run 每轮汇总 / per-finding 逐条通知）与已通知命中的记录文件；
+# webhook、notify、state 同样用于单包查询与批量检查（--webhook、--notify、--notify-state）
+# feed: https://example.com/compromised.version2.txt
+# lockfiles:
+#   - apps/web
+#   - apps/admin/pnpm-lock.yaml
+# webhook: https://hooks.example.com/xxx
+# notify: per-run
+# state: .npm_package_check.watch.json
+";
+
+const POLICY_TEMPLATE: &str = "\
+# 批量文件 Status 列到处理级别的映射（--policy）
+#   fail  命中后以退出码 1 结束，适合 CI 阻断
+#   warn  标记为警告，不影响退出码
+#   info  仅作提示
+# 状态匹配时忽略大小写和前缀符号（\"⚠️ Active\" 与 \"Active\" 视为相同）
+
+# 未在 status 中列出、或批量文件没有 Status 列时使用的级别
+default: fail
+
+status:
+  Compromised: fail
+  Active: fail
+  Under Investigation: warn
+  Removed from NPM: warn
+  Remediated: info
+";
+
+const VERSION1_TEMPLATE: &str = "\
+Row\tPackage Name\tVersion(s)
+# 标准包列表格式：第一行为表头，各列以制表符分隔
+# Version(s) 中多个版本以 \", \" 分隔，留空表示匹配任意版本
+# 版本前可加 exact:/prefix:/semver:/regex: 指定该版本的匹配方式
+# 以 # 开头的行为注释，会被忽略
+1\texample-package\t1.0.0
+2\t@example-scope/example-package\t2.0.1, 2.0.2
+3\tanother-package\t
+";
+
+const VERSION2_TEMPLATE: &str = "\
+Package Name\tCompromised Version(s)\tDetection Date\tStatus
+# 安全报告格式：第一行为表头，各列以制表符分隔
+# Detection Date 使用 YYYY-MM-DD，Status 可在策略文件中映射为 fail/warn/info
+# 以 # 开头的行为注释，会被忽略
+example-package\t1.0.0\t2025-09-16\tCompromised
+@example-scope/example-package\t2.0.1, 2.0.2\t2025-09-16\tUnder Investigation
+";
+
+const JSON_TEMPLATE: &str = r#"[
+  {
+    "name": "example-package",
+    "versions": ["1.0.0"],
+    "status": "Compromised",
+    "detectionDate": "2025-09-16"
+  },
+  {
+    "name": "@example-scope/example-package",
+    "versions": ["2.0.1", "2.0.2"],
+    "fixedIn": ["2.0.3"]
+  },
+  {
+    "name": "another-package"
+  }
+]
+"#;
+
+const TEMPLATES: &[(&str, &str)] = &[
+    (DEFAULT_CONFIG_FILE, CONFIG_TEMPLATE),
+    (POLICY_FILE, POLICY_TEMPLATE),
+    ("batch.version1.txt", VERSION1_TEMPLATE),
+    ("batch.version2.txt", VERSION2_TEMPLATE),
+    ("batch.json", JSON_TEMPLATE),
+];
+
+/// dry_run 时只列出将要生成的文件
+pub fn run_init(dir: &str, force: bool, dry_run: bool) -> Result<()> {
+    let dir = Path::new(dir);
+    if !dry_run {
+        fs::create_dir_all(dir).with_context(|| format!("无法创建目录 '{}'", dir.display()))?;
+    }
+
+    for (name, content) in TEMPLATES {
+        let path = dir.join(name);
+        if path.exists() && !force {
+            println!("⏭️ 已存在，跳过: {}", path.display());
+            continue;
+        }
+        if dry_run {
+            let action = if path.exists() { "覆盖" } else { "生成" };
+            println!("🔍 [dry-run] 将{}: {}（{} 字节）", action, path.display(), content.len());
+            continue;
+        }
+        fs::write(&path, content).with_context(|| format!("无法写入文件 '{}'", path.display()))?;
+        println!("📝 已生成: {}", path.display());
+    }
+
+    Ok(())
+}
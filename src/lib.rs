@@ -0,0 +1,22 @@
+// npm_package_check 的核心库：锁文件解析与规范化的包记录，供命令行工具和外部调用方使用
+
+pub mod advisory;
+pub mod batch;
+#[cfg(feature = "bun")]
+pub mod bun;
+#[cfg(feature = "cargo-lock")]
+pub mod cargo;
+#[cfg(feature = "deno")]
+pub mod deno;
+pub mod ecosystem;
+pub mod fixtures;
+pub mod lockfile;
+#[cfg(feature = "npm")]
+pub mod npm;
+pub mod package_key;
+pub mod progress;
+pub mod purl;
+pub mod snapshot;
+pub mod workspace;
+#[cfg(feature = "yarn")]
+pub mod yarn;
@@ -0,0 +1,1177 @@
+// pnpm-lock.yaml 的数据模型与规范化的包记录，package-lock.json、yarn.lock、bun.lock 与 deno.lock 转换为同一模型
+// （见 npm.rs、yarn.rs、bun.rs、deno.rs）
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use rayon::prelude::*;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::ecosystem::Ecosystem;
+use crate::package_key;
+use crate::snapshot::{self, DepValue};
+
+#[derive(Debug, Deserialize)]
+#[serde(from = "RawLockfile")]
+pub struct Lockfile {
+    pub lockfile_version: String,
+    pub importers: BTreeMap<String, Importer>,
+    pub packages: BTreeMap<String, PackageInfo>,
+    pub snapshots: BTreeMap<String, SnapshotInfo>,
+    /// packages 与 snapshots 节点 key → 规范化的锁定包，解析时生成
+    pub locked: BTreeMap<String, LockedPackage>,
+    /// pnpm 的 catalogs 节点：catalog 名 → 包名 → 版本规格与锁定版本（v9）
+    pub catalogs: BTreeMap<String, BTreeMap<String, DependencyInfo>>,
+    /// pnpm 的 overrides 节点：选择器（name、name@范围、parent>name）→ 覆盖为的版本规格
+    pub overrides: BTreeMap<String, String>,
+    /// pnpm 的 patchedDependencies 节点：name 或 name@版本（范围）→ 补丁文件路径（旧格式只记录哈希）
+    pub patched_dependencies: BTreeMap<String, String>,
+    /// pnpm 的 onlyBuiltDependencies 节点：存在时只有列出的包会执行安装脚本
+    pub only_built_dependencies: Option<BTreeSet<String>>,
+    /// pnpm 的 neverBuiltDependencies 节点：列出的包不会执行安装脚本
+    pub never_built_dependencies: BTreeSet<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(from = "RawImporter")]
+pub struct Importer {
+    pub dependencies: BTreeMap<String, DependencyInfo>,
+    pub dev_dependencies: BTreeMap<String, DependencyInfo>,
+    pub optional_dependencies: BTreeMap<String, DependencyInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DependencyInfo {
+    pub specifier: String,
+    pub version: String,
+}
+
+// 锁文件的原始结构：v5 的版本号是数字，单项目的 v5/v6 锁文件没有 importers 节点，
+// 直接依赖写在顶层；v5 的直接依赖只有版本号，版本规格单独写在 specifiers 中
+#[derive(Deserialize)]
+struct RawLockfile {
+    #[serde(rename = "lockfileVersion")]
+    lockfile_version: RawVersion,
+
+    #[serde(default)]
+    importers: BTreeMap<String, Importer>,
+
+    #[serde(default)]
+    packages: BTreeMap<String, PackageInfo>,
+
+    #[serde(default)]
+    snapshots: BTreeMap<String, SnapshotInfo>,
+
+    #[serde(default)]
+    catalogs: BTreeMap<String, BTreeMap<String, DependencyInfo>>,
+
+    #[serde(default)]
+    overrides: BTreeMap<String, String>,
+
+    #[serde(default)]
+    #[serde(rename = "patchedDependencies")]
+    patched_dependencies: BTreeMap<String, RawPatch>,
+
+    #[serde(default)]
+    #[serde(rename = "onlyBuiltDependencies")]
+    only_built_dependencies: Option<BTreeSet<String>>,
+
+    #[serde(default)]
+    #[serde(rename = "neverBuiltDependencies")]
+    never_built_dependencies: BTreeSet<String>,
+
+    #[serde(flatten)]
+    root: RawImporter,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawVersion {
+    String(String),
+    Number(serde_yaml::Number),
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawPatch {
+    Info { path: String },
+    Hash(String),
+}
+
+#[derive(Default, Deserialize)]
+struct RawImporter {
+    #[serde(default)]
+    specifiers: BTreeMap<String, String>,
+
+    #[serde(default)]
+    dependencies: BTreeMap<String, RawDependency>,
+
+    #[serde(default)]
+    #[serde(rename = "devDependencies")]
+    dev_dependencies: BTreeMap<String, RawDependency>,
+
+    #[serde(default)]
+    #[serde(rename = "optionalDependencies")]
+    optional_dependencies: BTreeMap<String, RawDependency>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawDependency {
+    Info(DependencyInfo),
+    Version(String),
+}
+
+impl From<RawLockfile> for Lockfile {
+    fn from(raw: RawLockfile) -> Self {
+        let mut importers = raw.importers;
+        let root = Importer::from(raw.root);
+        if importers.is_empty() && !root.is_empty() {
+            importers.insert(".".to_string(), root);
+        }
+        let lockfile_version = match raw.lockfile_version {
+            RawVersion::String(version) => version,
+            RawVersion::Number(version) => version.to_string(),
+        };
+        let mut packages = raw.packages;
+        // v5/v6 只在有安装脚本时写出 requiresBuild: true，没有该字段即没有安装脚本；v9 不再记录
+        let major = lockfile_version.split('.').next().and_then(|major| major.parse::<u32>().ok());
+        if major.is_some_and(|major| major < 9) {
+            for package_info in packages.values_mut() {
+                package_info.requires_build.get_or_insert(false);
+            }
+        }
+        let mut lock = Lockfile::new(lockfile_version, importers, packages, raw.snapshots);
+        lock.catalogs = raw.catalogs;
+        lock.overrides = raw.overrides;
+        lock.patched_dependencies = raw
+            .patched_dependencies
+            .into_iter()
+            .map(|(key, patch)| match patch {
+                RawPatch::Info { path } => (key, path),
+                RawPatch::Hash(hash) => (key, hash),
+            })
+            .collect();
+        lock.only_built_dependencies = raw.only_built_dependencies;
+        lock.never_built_dependencies = raw.never_built_dependencies;
+        lock
+    }
+}
+
+impl From<RawImporter> for Importer {
+    fn from(raw: RawImporter) -> Self {
+        let specifiers = raw.specifiers;
+        let convert = |deps: BTreeMap<String, RawDependency>| {
+            deps.into_iter()
+                .map(|(name, dep)| {
+                    let info = match dep {
+                        RawDependency::Info(info) => info,
+                        RawDependency::Version(version) => DependencyInfo {
+                            specifier: specifiers.get(&name).cloned().unwrap_or_default(),
+                            version,
+                        },
+                    };
+                    (name, info)
+                })
+                .collect()
+        };
+        Importer {
+            dependencies: convert(raw.dependencies),
+            dev_dependencies: convert(raw.dev_dependencies),
+            optional_dependencies: convert(raw.optional_dependencies),
+        }
+    }
+}
+
+impl Importer {
+    fn is_empty(&self) -> bool {
+        self.dependencies.is_empty() && self.dev_dependencies.is_empty() && self.optional_dependencies.is_empty()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PackageInfo {
+    pub resolution: Resolution,
+
+    /// 非 registry 来源的包（v6 及更早）在 key 中不含包名和版本号，单独记录在这两个字段中
+    #[serde(default)]
+    pub name: Option<String>,
+
+    #[serde(default)]
+    pub version: Option<String>,
+
+    #[serde(default)]
+    #[serde(rename = "peerDependencies")]
+    pub peer_dependencies: BTreeMap<String, String>,
+
+    #[serde(default)]
+    pub dependencies: BTreeMap<String, String>,
+
+    #[serde(default)]
+    #[serde(rename = "optionalDependencies")]
+    pub optional_dependencies: BTreeMap<String, String>,
+
+    #[serde(default)]
+    #[serde(rename = "devDependencies")]
+    pub dev_dependencies: BTreeMap<String, String>,
+
+    /// 是否有安装脚本：pnpm v5/v6 的 requiresBuild、package-lock.json 的 hasInstallScript；
+    /// pnpm v9 等不记录的锁文件为 None
+    #[serde(default)]
+    #[serde(rename = "requiresBuild")]
+    pub requires_build: Option<bool>,
+}
+
+/// 默认 registry 的主机名，yarn 的默认 registry 是 npm registry 的镜像
+pub const DEFAULT_REGISTRY_HOSTS: &[&str] = &["registry.npmjs.org", "registry.yarnpkg.com"];
+
+/// packages 节点中的 resolution，按来源区分
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum Resolution {
+    /// git 仓库的指定提交：{type: git, repo, commit}
+    Git { repo: String, commit: String },
+    /// 本地目录：{type: directory, directory}
+    Directory { directory: String },
+    /// tarball 地址（非默认 registry、git 托管平台或本地文件）：{tarball, integrity?}
+    Tarball {
+        tarball: String,
+        #[serde(default)]
+        integrity: Option<String>,
+    },
+    /// 默认 registry，仅有完整性校验值：{integrity}
+    Registry { integrity: String },
+    /// workspace 内部链接（importers 中的 link: 版本），不会出现在 packages 节点中
+    #[serde(skip_deserializing)]
+    Link { path: String },
+}
+
+impl Resolution {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Resolution::Git { .. } => "git",
+            Resolution::Directory { .. } => "directory",
+            Resolution::Tarball { .. } => "tarball",
+            Resolution::Registry { .. } => "registry",
+            Resolution::Link { .. } => "link",
+        }
+    }
+
+    /// 类型与来源地址，如 git ssh://git@github.com/acme/tool.git#5c8f1d2
+    pub fn describe(&self) -> String {
+        match self {
+            Resolution::Git { repo, commit } => format!("git {}#{}", repo, commit),
+            Resolution::Directory { directory } => format!("directory {}", directory),
+            Resolution::Tarball { tarball, .. } => format!("tarball {}", tarball),
+            Resolution::Registry { .. } => "registry".to_string(),
+            Resolution::Link { path } => format!("link {}", path),
+        }
+    }
+
+    pub fn integrity(&self) -> Option<&str> {
+        match self {
+            // package-lock.json 中的 bundledDependencies 没有完整性校验值
+            Resolution::Registry { integrity } => Some(integrity.as_str()).filter(|i| !i.is_empty()),
+            Resolution::Tarball { integrity, .. } => integrity.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// 是否可以在 registry 上查询到该包
+    pub fn is_registry(&self) -> bool {
+        matches!(self, Resolution::Registry { .. })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SnapshotInfo {
+    #[serde(default)]
+    pub dependencies: BTreeMap<String, String>,
+
+    #[serde(default)]
+    #[serde(rename = "optionalDependencies")]
+    pub optional_dependencies: BTreeMap<String, String>,
+}
+
+/// 锁文件格式，解析后都转换为 pnpm 的锁文件模型；各格式的识别与解析由对应的 Ecosystem 实现（见 ecosystem.rs）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LockfileFormat {
+    Pnpm,
+    Npm,
+    Yarn,
+    Bun,
+    Deno,
+    Cargo,
+}
+
+impl LockfileFormat {
+    pub const ALL: [LockfileFormat; 6] = [
+        LockfileFormat::Pnpm,
+        LockfileFormat::Npm,
+        LockfileFormat::Yarn,
+        LockfileFormat::Bun,
+        LockfileFormat::Deno,
+        LockfileFormat::Cargo,
+    ];
+
+    /// 按内容识别时的尝试顺序：顶层带 workspaces 的 JSON 为 bun.lock，顶层有 version 而没有 lockfileVersion 的 JSON
+    /// 为 deno.lock，其他 JSON 为 package-lock.json，带 yarn 文件头的为 yarn.lock，带 [[package]] 表的 TOML 为 Cargo.lock，
+    /// 其余按 pnpm-lock.yaml 处理
+    pub const SNIFF_ORDER: [LockfileFormat; 6] = [
+        LockfileFormat::Bun,
+        LockfileFormat::Deno,
+        LockfileFormat::Npm,
+        LockfileFormat::Yarn,
+        LockfileFormat::Cargo,
+        LockfileFormat::Pnpm,
+    ];
+
+    /// 该格式的识别与解析；对应的 cargo feature 未启用时为 Unavailable
+    pub fn ecosystem(&self) -> &'static dyn Ecosystem {
+        match self {
+            LockfileFormat::Pnpm => &Pnpm,
+            #[cfg(feature = "npm")]
+            LockfileFormat::Npm => &crate::npm::Npm,
+            #[cfg(not(feature = "npm"))]
+            LockfileFormat::Npm => &Unavailable { name: "npm", file_names: &["package-lock.json", "npm-shrinkwrap.json"], feature: "npm" },
+            #[cfg(feature = "yarn")]
+            LockfileFormat::Yarn => &crate::yarn::Yarn,
+            #[cfg(not(feature = "yarn"))]
+            LockfileFormat::Yarn => &Unavailable { name: "yarn", file_names: &["yarn.lock"], feature: "yarn" },
+            #[cfg(feature = "bun")]
+            LockfileFormat::Bun => &crate::bun::Bun,
+            #[cfg(not(feature = "bun"))]
+            LockfileFormat::Bun => &Unavailable { name: "bun", file_names: &["bun.lock"], feature: "bun" },
+            #[cfg(feature = "deno")]
+            LockfileFormat::Deno => &crate::deno::Deno,
+            #[cfg(not(feature = "deno"))]
+            LockfileFormat::Deno => &Unavailable { name: "deno", file_names: &["deno.lock"], feature: "deno" },
+            #[cfg(feature = "cargo-lock")]
+            LockfileFormat::Cargo => &crate::cargo::Cargo,
+            #[cfg(not(feature = "cargo-lock"))]
+            LockfileFormat::Cargo => &Unavailable { name: "cargo", file_names: &["Cargo.lock"], feature: "cargo-lock" },
+        }
+    }
+
+    /// 提供该格式支持的 cargo feature，pnpm 始终可用
+    pub fn feature(&self) -> Option<&'static str> {
+        match self {
+            LockfileFormat::Pnpm => None,
+            LockfileFormat::Npm => Some("npm"),
+            LockfileFormat::Yarn => Some("yarn"),
+            LockfileFormat::Bun => Some("bun"),
+            LockfileFormat::Deno => Some("deno"),
+            LockfileFormat::Cargo => Some("cargo-lock"),
+        }
+    }
+
+    /// 当前构建是否包含该格式的支持
+    pub fn is_enabled(&self) -> bool {
+        match self {
+            LockfileFormat::Pnpm => true,
+            LockfileFormat::Npm => cfg!(feature = "npm"),
+            LockfileFormat::Yarn => cfg!(feature = "yarn"),
+            LockfileFormat::Bun => cfg!(feature = "bun"),
+            LockfileFormat::Deno => cfg!(feature = "deno"),
+            LockfileFormat::Cargo => cfg!(feature = "cargo-lock"),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.ecosystem().name()
+    }
+
+    /// 按包管理器生成的默认文件名判断，其他文件名返回 None
+    pub fn from_file_name(path: &Path) -> Option<Self> {
+        let file_name = path.file_name()?.to_str()?;
+        Self::ALL
+            .into_iter()
+            .find(|format| format.ecosystem().file_names().contains(&file_name))
+    }
+
+    /// 按内容判断，依次尝试 SNIFF_ORDER 中的格式
+    pub fn sniff(content: &str) -> Self {
+        Self::SNIFF_ORDER
+            .into_iter()
+            .find(|format| format.ecosystem().sniff(content))
+            .unwrap_or(LockfileFormat::Pnpm)
+    }
+
+    /// 先按文件名判断，文件名不是默认名称时（例如改名后的备份、CI 产物）再按内容判断
+    pub fn detect(path: &Path, content: &str) -> Self {
+        Self::from_file_name(path).unwrap_or_else(|| Self::sniff(content))
+    }
+}
+
+impl FromStr for LockfileFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::ALL.into_iter().find(|format| format.name() == s).with_context(|| {
+            let names: Vec<&str> = Self::ALL.iter().map(|format| format.name()).collect();
+            format!("未知的锁文件格式 '{}'，可选值为 {}", s, names.join("、"))
+        })
+    }
+}
+
+/// pnpm-lock.yaml（lockfileVersion 5.x、6.0 与 9.0）
+pub struct Pnpm;
+
+impl Ecosystem for Pnpm {
+    fn name(&self) -> &'static str {
+        "pnpm"
+    }
+
+    fn file_names(&self) -> &'static [&'static str] {
+        &["pnpm-lock.yaml", "pnpm-lock.yml"]
+    }
+
+    /// 其他格式都无法识别时按 pnpm-lock.yaml 处理
+    fn sniff(&self, _content: &str) -> bool {
+        true
+    }
+
+    fn parse(&self, content: &str) -> Result<Lockfile> {
+        serde_yaml::from_str(content).context("解析 pnpm-lock.yaml 文件失败")
+    }
+}
+
+/// 未编译进当前构建的格式：仍按默认文件名识别，解析时说明需要启用的 cargo feature。
+/// 默认构建包含全部格式，不会用到
+#[allow(dead_code)]
+struct Unavailable {
+    name: &'static str,
+    file_names: &'static [&'static str],
+    feature: &'static str,
+}
+
+impl Ecosystem for Unavailable {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn file_names(&self) -> &'static [&'static str] {
+        self.file_names
+    }
+
+    /// 识别内容需要对应的模块，只按文件名识别
+    fn sniff(&self, _content: &str) -> bool {
+        false
+    }
+
+    fn parse(&self, _content: &str) -> Result<Lockfile> {
+        anyhow::bail!(
+            "当前构建不支持 {}（{}），请启用 cargo feature \"{}\" 后重新编译，例如: cargo build --release --features {}",
+            self.file_names.join(" / "),
+            self.name,
+            self.feature,
+            self.feature
+        )
+    }
+}
+
+/// 安装时是否执行包的安装脚本，供判断命中的包是否可能在安装阶段执行代码
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstallScripts {
+    /// 列在 neverBuiltDependencies 中
+    Never,
+    /// 锁文件有 onlyBuiltDependencies 而该包未列出
+    NotAllowed,
+    /// 列在 onlyBuiltDependencies 中
+    Allowed,
+    /// 锁文件记录该包没有安装脚本
+    NoScripts,
+    /// 锁文件记录该包有安装脚本，且没有限制
+    Runs,
+    /// 锁文件没有记录（pnpm v9、yarn.lock 等）
+    Unknown,
+}
+
+impl InstallScripts {
+    /// 报告中使用的标识
+    pub fn key(&self) -> &'static str {
+        match self {
+            InstallScripts::Never => "never",
+            InstallScripts::NotAllowed => "not_allowed",
+            InstallScripts::Allowed => "allowed",
+            InstallScripts::NoScripts => "none",
+            InstallScripts::Runs => "runs",
+            InstallScripts::Unknown => "unknown",
+        }
+    }
+
+    /// 控制台输出的说明
+    pub fn describe(&self) -> &'static str {
+        match self {
+            InstallScripts::Never => "不会执行（neverBuiltDependencies）",
+            InstallScripts::NotAllowed => "不会执行（未列入 onlyBuiltDependencies）",
+            InstallScripts::Allowed => "会执行（onlyBuiltDependencies 允许）",
+            InstallScripts::NoScripts => "无安装脚本",
+            InstallScripts::Runs => "会执行",
+            InstallScripts::Unknown => "锁文件未记录",
+        }
+    }
+
+    /// 安装脚本是否可能被执行
+    pub fn may_run(&self) -> bool {
+        matches!(self, InstallScripts::Allowed | InstallScripts::Runs | InstallScripts::Unknown)
+    }
+}
+
+/// 记录来自锁文件的哪个节点
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Section {
+    Importers,
+    Packages,
+    Snapshots,
+}
+
+impl Section {
+    /// 锁文件中的节点名
+    pub fn name(&self) -> &'static str {
+        match self {
+            Section::Importers => "importers",
+            Section::Packages => "packages",
+            Section::Snapshots => "snapshots",
+        }
+    }
+}
+
+/// 依赖类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DepKind {
+    Prod,
+    Dev,
+    Optional,
+}
+
+impl DepKind {
+    /// 锁文件中对应的字段名
+    pub fn field_name(&self) -> &'static str {
+        match self {
+            DepKind::Prod => "dependencies",
+            DepKind::Dev => "devDependencies",
+            DepKind::Optional => "optionalDependencies",
+        }
+    }
+}
+
+/// 规范化的锁定包：packages/snapshots 节点 key 拆分出的包名、版本号与 peer 依赖组合。
+/// v5（/name/1.0.0_react@18.2.0）、v6（/name@1.0.0(react@18.2.0)）与 v9（name@1.0.0(react@18.2.0)）
+/// 的写法得到相同的结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockedPackage {
+    pub name: String,
+    pub version: String,
+    /// peer 依赖组合 (包名, 版本号)，保持 key 中的顺序
+    pub peers: Vec<(String, String)>,
+}
+
+impl LockedPackage {
+    /// 拆分节点 key，无法识别包名与版本号时返回 None（语法见 package_key）
+    pub fn parse(key: &str) -> Option<Self> {
+        package_key::parse(key)
+    }
+
+    /// 不含 peer 组合的 name@version，即 v9 中 packages 节点的 key
+    pub fn id(&self) -> String {
+        format!("{}@{}", self.name, self.version)
+    }
+
+    /// 带 peer 组合的显示名，例如 a@1.0.0 (react@18.2.0)
+    pub fn label(&self) -> String {
+        if self.peers.is_empty() {
+            return self.id();
+        }
+        let peers: Vec<String> = self.peers.iter().map(|(name, version)| format!("{}@{}", name, version)).collect();
+        format!("{} ({})", self.id(), peers.join(", "))
+    }
+}
+
+/// 规范化的包记录：无论出现在哪个节点，都拆分为包名与纯版本号
+#[derive(Debug, Clone)]
+pub struct PackageRecord<'a> {
+    pub name: String,
+    pub version: String,
+    pub section: Section,
+    /// 依赖类型，packages/snapshots 节点中的包条目本身为 None
+    pub dep_kind: Option<DepKind>,
+    /// importers 节点中的 importer 路径（"." 为根目录）
+    pub importer: Option<&'a str>,
+    /// snapshots 节点中声明该依赖的 snapshot key
+    pub parent: Option<&'a str>,
+    /// importers 节点中的版本规格
+    pub specifier: Option<&'a str>,
+    pub resolution: Option<Resolution>,
+    /// 以别名安装（"react": "npm:preact@10"）时的别名，name 为实际安装的包名
+    pub alias: Option<&'a str>,
+}
+
+impl Lockfile {
+    /// 由各节点组装锁文件模型，同时为 packages 与 snapshots 节点生成规范化的锁定包
+    pub fn new(
+        lockfile_version: String,
+        importers: BTreeMap<String, Importer>,
+        packages: BTreeMap<String, PackageInfo>,
+        snapshots: BTreeMap<String, SnapshotInfo>,
+    ) -> Self {
+        let mut locked: BTreeMap<String, LockedPackage> = snapshots
+            .par_iter()
+            .filter_map(|(key, _)| Some((key.clone(), LockedPackage::parse(key)?)))
+            .collect();
+        for (key, package_info) in &packages {
+            // 非 registry 来源的包以 name、version 字段为准
+            let package = match (&package_info.name, &package_info.version) {
+                (Some(name), Some(version)) => Some(LockedPackage {
+                    name: name.clone(),
+                    version: version.clone(),
+                    peers: LockedPackage::parse(key).map(|package| package.peers).unwrap_or_default(),
+                }),
+                _ => LockedPackage::parse(key),
+            };
+            if let Some(package) = package {
+                locked.insert(key.clone(), package);
+            }
+        }
+        Lockfile {
+            lockfile_version,
+            importers,
+            packages,
+            snapshots,
+            locked,
+            catalogs: BTreeMap::new(),
+            overrides: BTreeMap::new(),
+            patched_dependencies: BTreeMap::new(),
+            only_built_dependencies: None,
+            never_built_dependencies: BTreeSet::new(),
+        }
+    }
+
+    /// 解析锁文件，格式按内容判断（见 LockfileFormat::sniff）
+    pub fn parse(content: &str) -> Result<Self> {
+        Self::parse_as(content, LockfileFormat::sniff(content))
+    }
+
+    /// 按指定的格式解析锁文件
+    pub fn parse_as(content: &str, format: LockfileFormat) -> Result<Self> {
+        format.ecosystem().parse(content)
+    }
+
+    /// 遍历所有节点中的包，依次为 importers、packages、snapshots
+    pub fn packages(&self) -> impl Iterator<Item = PackageRecord<'_>> + '_ {
+        let importers = self.importers.iter().flat_map(move |(path, importer)| {
+            [
+                (DepKind::Prod, &importer.dependencies),
+                (DepKind::Dev, &importer.dev_dependencies),
+                (DepKind::Optional, &importer.optional_dependencies),
+            ]
+            .into_iter()
+            .flat_map(move |(kind, deps)| {
+                deps.iter().map(move |(key, dep_info)| {
+                    let parsed = snapshot::parse_dep_value(&dep_info.version);
+                    let name = parsed.resolved_name(key).to_string();
+                    let alias = parsed.alias(key);
+                    let (version, resolution) = match parsed {
+                        DepValue::Link(path) => {
+                            (format!("link:{}", path), Some(Resolution::Link { path }))
+                        }
+                        DepValue::File(path) => {
+                            let version = format!("file:{}", path);
+                            let resolution = self.resolution_of(&name, &version);
+                            (version, resolution)
+                        }
+                        DepValue::Version { version } | DepValue::Alias { version, .. } => {
+                            let resolution = self.resolution_of(&name, &version);
+                            (version, resolution)
+                        }
+                    };
+                    PackageRecord {
+                        name,
+                        version,
+                        section: Section::Importers,
+                        dep_kind: Some(kind),
+                        importer: Some(path.as_str()),
+                        parent: None,
+                        specifier: Some(self.resolve_specifier(key, &dep_info.specifier)),
+                        resolution,
+                        alias,
+                    }
+                })
+            })
+        });
+
+        let packages = self.packages.iter().flat_map(move |(key, package_info)| {
+            // v5/v6 的依赖关系记录在 packages 条目中
+            let deps = [
+                (DepKind::Prod, &package_info.dependencies),
+                (DepKind::Dev, &package_info.dev_dependencies),
+                (DepKind::Optional, &package_info.optional_dependencies),
+            ]
+            .into_iter()
+            .flat_map(move |(kind, deps)| {
+                deps.iter().filter_map(move |(dep_key, dep_value)| {
+                    let parsed = snapshot::parse_dep_value(dep_value);
+                    let name = parsed.resolved_name(dep_key).to_string();
+                    let alias = parsed.alias(dep_key);
+                    let version = parsed.version()?.to_string();
+                    let resolution = self.resolution_of(&name, &version);
+                    Some(PackageRecord {
+                        name,
+                        version,
+                        section: Section::Packages,
+                        dep_kind: Some(kind),
+                        importer: None,
+                        parent: Some(key.as_str()),
+                        specifier: None,
+                        resolution,
+                        alias,
+                    })
+                })
+            });
+
+            let Some(locked) = self.locked.get(key) else {
+                return deps.chain(None);
+            };
+            let entry = PackageRecord {
+                name: locked.name.clone(),
+                version: locked.version.clone(),
+                section: Section::Packages,
+                dep_kind: None,
+                importer: None,
+                parent: None,
+                specifier: None,
+                resolution: Some(package_info.resolution.clone()),
+                alias: None,
+            };
+            deps.chain(Some(entry))
+        });
+
+        let snapshots = self
+            .snapshots
+            .iter()
+            .flat_map(move |(key, snapshot_info)| self.snapshot_records(key, snapshot_info));
+
+        importers.chain(packages).chain(snapshots)
+    }
+
+    /// 一个 snapshot 条目产生的包记录：先是其声明的依赖，最后是条目本身
+    fn snapshot_records<'a>(
+        &'a self,
+        key: &'a str,
+        snapshot_info: &'a SnapshotInfo,
+    ) -> impl Iterator<Item = PackageRecord<'a>> + 'a {
+        let deps = [
+            (DepKind::Prod, &snapshot_info.dependencies),
+            (DepKind::Optional, &snapshot_info.optional_dependencies),
+        ]
+        .into_iter()
+        .flat_map(move |(kind, deps)| {
+            deps.iter().filter_map(move |(dep_key, dep_value)| {
+                let parsed = snapshot::parse_dep_value(dep_value);
+                let name = parsed.resolved_name(dep_key).to_string();
+                let alias = parsed.alias(dep_key);
+                let version = parsed.version()?.to_string();
+                let resolution = self.resolution_of(&name, &version);
+                Some(PackageRecord {
+                    name,
+                    version,
+                    section: Section::Snapshots,
+                    dep_kind: Some(kind),
+                    importer: None,
+                    parent: Some(key),
+                    specifier: None,
+                    resolution,
+                    alias,
+                })
+            })
+        });
+
+        let entry = self.locked.get(key).map(|locked| {
+            let resolution = self.resolution_of(&locked.name, &locked.version);
+            PackageRecord {
+                name: locked.name.clone(),
+                version: locked.version.clone(),
+                section: Section::Snapshots,
+                dep_kind: None,
+                importer: None,
+                parent: None,
+                specifier: None,
+                resolution,
+                alias: None,
+            }
+        });
+
+        deps.chain(entry)
+    }
+
+    /// importer 路径加上前缀 prefix（锁文件所在目录相对于仓库根目录的路径），根 importer 即为 prefix 本身
+    pub fn rebase_importers(&mut self, prefix: &str) {
+        let importers = std::mem::take(&mut self.importers);
+        self.importers = importers
+            .into_iter()
+            .map(|(path, importer)| {
+                let path = if path == "." { prefix.to_string() } else { format!("{}/{}", prefix, path) };
+                (path, importer)
+            })
+            .collect();
+    }
+
+    /// 查找 packages 节点中对应条目的 resolution（兼容 v9、v6 与 v5 的 key 格式）
+    pub fn resolution_of(&self, name: &str, version: &str) -> Option<Resolution> {
+        let key = format!("{}@{}", name, version);
+        self.packages
+            .get(&key)
+            .or_else(|| self.packages.get(&format!("/{}", key)))
+            .or_else(|| self.packages.get(&format!("/{}/{}", name, version)))
+            // v6 及更早的非 registry 依赖，版本号即 packages 节点的 key
+            .or_else(|| self.packages.get(version))
+            .map(|p| p.resolution.clone())
+    }
+}
+
+impl Lockfile {
+    /// 依赖值对应的 snapshots/packages 节点 key（保留 peer 后缀以区分不同的 peer 组合），
+    /// workspace 内部链接与找不到的条目返回 None
+    pub fn node_key(&self, name: &str, value: &str) -> Option<String> {
+        let value = value.trim();
+        if value.starts_with("link:") {
+            return None;
+        }
+        let value = value.strip_prefix("npm:").unwrap_or(value);
+        // 依次为 v9、v6、v5 的 key 格式，以及别名与非 registry 依赖直接以依赖值为 key 的情况
+        [
+            format!("{}@{}", name, value),
+            format!("/{}@{}", name, value),
+            format!("/{}/{}", name, value),
+            value.to_string(),
+            format!("/{}", value),
+        ]
+        .into_iter()
+        .find(|key| self.snapshots.contains_key(key) || self.packages.contains_key(key))
+    }
+
+    /// catalog: 规格对应的 catalogs 条目，catalog: 与 catalog:default 均指默认 catalog
+    pub fn catalog_entry(&self, name: &str, specifier: &str) -> Option<&DependencyInfo> {
+        let catalog = specifier.strip_prefix("catalog:")?.trim();
+        let catalog = if catalog.is_empty() { "default" } else { catalog };
+        self.catalogs.get(catalog)?.get(name)
+    }
+
+    /// 实际的版本规格：catalog: 规格替换为 catalogs 中的版本规格，找不到对应条目时保持原样
+    pub fn resolve_specifier<'a>(&'a self, name: &str, specifier: &'a str) -> &'a str {
+        self.catalog_entry(name, specifier).map_or(specifier, |entry| entry.specifier.as_str())
+    }
+
+    /// overrides 中作用于该包的条目 (选择器, 覆盖为的版本规格)。选择器中的版本范围限定的是覆盖前的版本，
+    /// 锁定的已是覆盖后的版本，因此只按包名判断
+    pub fn override_for(&self, name: &str) -> Option<(&str, &str)> {
+        self.overrides
+            .iter()
+            .find(|(selector, _)| {
+                let target = selector.rsplit('>').next().unwrap_or(selector);
+                let (target_name, _) = split_selector(target);
+                target_name == name
+            })
+            .map(|(selector, value)| (selector.as_str(), value.as_str()))
+    }
+
+    /// patchedDependencies 中作用于该版本的补丁：键为 name（全部版本）、name@版本或 name@范围
+    pub fn patch_for(&self, name: &str, version: &str) -> Option<&str> {
+        self.patched_dependencies
+            .iter()
+            .find(|(key, _)| match split_selector(key) {
+                (key_name, None) => key_name == name,
+                (key_name, Some(range)) => {
+                    key_name == name
+                        && (range == version
+                            || matches!(
+                                (semver::VersionReq::parse(range), semver::Version::parse(version)),
+                                (Ok(req), Ok(version)) if req.matches(&version)
+                            ))
+                }
+            })
+            .map(|(_, patch)| patch.as_str())
+    }
+
+    /// 安装该版本时是否会执行安装脚本（preinstall/install/postinstall）
+    pub fn install_scripts(&self, name: &str, version: &str) -> InstallScripts {
+        if self.never_built_dependencies.contains(name) {
+            return InstallScripts::Never;
+        }
+        let requires_build = self
+            .node_key(name, version)
+            .and_then(|key| self.package_info(&key))
+            .and_then(|package_info| package_info.requires_build);
+        match (requires_build, &self.only_built_dependencies) {
+            (Some(false), _) => InstallScripts::NoScripts,
+            (_, Some(only)) if only.contains(name) => InstallScripts::Allowed,
+            (_, Some(_)) => InstallScripts::NotAllowed,
+            (Some(true), None) => InstallScripts::Runs,
+            (None, None) => InstallScripts::Unknown,
+        }
+    }
+
+    /// 已锁定版本的来源，workspace 内部链接（link: 版本）直接由版本得出
+    pub fn resolution_for(&self, name: &str, version: &str) -> Option<Resolution> {
+        if let Some(path) = version.strip_prefix("link:") {
+            return Some(Resolution::Link { path: path.to_string() });
+        }
+        self.node_key(name, version)
+            .and_then(|key| self.package_info(&key))
+            .map(|package_info| package_info.resolution.clone())
+    }
+
+    /// 节点对应的 packages 条目：v5/v6 与节点 key 相同，v9 的 snapshot key 去掉 peer 组合后即为 packages 的 key
+    pub fn package_info(&self, key: &str) -> Option<&PackageInfo> {
+        self.packages
+            .get(key)
+            .or_else(|| self.locked.get(key).and_then(|locked| self.packages.get(&locked.id())))
+    }
+
+    /// 节点的直接依赖 (依赖名, 依赖值)：v9 记录在 snapshots 中，v5/v6 记录在 packages 中
+    pub fn node_dependencies(&self, key: &str) -> Vec<(&str, &str)> {
+        let maps = match (self.snapshots.get(key), self.packages.get(key)) {
+            (Some(snapshot_info), _) => vec![&snapshot_info.dependencies, &snapshot_info.optional_dependencies],
+            (None, Some(package_info)) => vec![
+                &package_info.dependencies,
+                &package_info.optional_dependencies,
+                &package_info.dev_dependencies,
+            ],
+            (None, None) => Vec::new(),
+        };
+        maps.into_iter()
+            .flatten()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+            .collect()
+    }
+
+    /// 从给定的 (依赖名, 依赖值) 出发可达的全部节点 key，包含起点本身
+    pub fn closure<'a>(&self, roots: impl IntoIterator<Item = (&'a str, &'a str)>) -> BTreeSet<String> {
+        let mut visited = BTreeSet::new();
+        let mut stack: Vec<String> = roots
+            .into_iter()
+            .filter_map(|(name, value)| self.node_key(name, value))
+            .collect();
+        while let Some(key) = stack.pop() {
+            if !visited.insert(key.clone()) {
+                continue;
+            }
+            for (name, value) in self.node_dependencies(&key) {
+                if let Some(child) = self.node_key(name, value)
+                    && !visited.contains(&child)
+                {
+                    stack.push(child);
+                }
+            }
+        }
+        visited
+    }
+}
+
+// 每个并行任务处理的 snapshot 条目数
+const SNAPSHOT_CHUNK_SIZE: usize = 4096;
+
+/// 按包名索引的包记录，同一个包的记录保持 packages() 的遍历顺序
+#[derive(Debug, Default)]
+pub struct PackageIndex<'a> {
+    records: HashMap<String, Vec<PackageRecord<'a>>>,
+}
+
+impl<'a> PackageIndex<'a> {
+    /// 包名对应的全部记录，不存在时为空
+    pub fn get(&self, name: &str) -> &[PackageRecord<'a>] {
+        self.records.get(name).map_or(&[], Vec::as_slice)
+    }
+
+    /// 索引中的包名数量
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// 以别名安装的记录同时登记在别名与实际的包名下
+    fn extend(&mut self, records: impl IntoIterator<Item = PackageRecord<'a>>) {
+        for record in records {
+            if let Some(alias) = record.alias {
+                self.records.entry(alias.to_string()).or_default().push(record.clone());
+            }
+            self.records.entry(record.name.clone()).or_default().push(record);
+        }
+    }
+
+    fn merge(&mut self, other: PackageIndex<'a>) {
+        for (name, records) in other.records {
+            self.records.entry(name).or_default().extend(records);
+        }
+    }
+}
+
+impl Lockfile {
+    /// 建立按包名的索引，避免每次查找都遍历整个锁文件。
+    /// 大型 monorepo 的 snapshots 节点可能有十万以上的条目，按块并行解析后再依次合并，
+    /// 合并顺序与 packages() 一致，因此结果与串行遍历相同
+    pub fn index(&self) -> PackageIndex<'_> {
+        let mut index = PackageIndex::default();
+        let snapshots: Vec<_> = self.snapshots.iter().collect();
+        let chunks: Vec<PackageIndex<'_>> = snapshots
+            .par_chunks(SNAPSHOT_CHUNK_SIZE)
+            .map(|chunk| {
+                let mut partial = PackageIndex::default();
+                for (key, snapshot_info) in chunk {
+                    partial.extend(self.snapshot_records(key, snapshot_info));
+                }
+                partial
+            })
+            .collect();
+
+        index.extend(self.packages().take_while(|record| record.section != Section::Snapshots));
+        for partial in chunks {
+            index.merge(partial);
+        }
+        index
+    }
+}
+
+/// 拆分 overrides/patchedDependencies 的选择器 name@范围，作用域包名开头的 @ 不作为分隔符
+fn split_selector(selector: &str) -> (&str, Option<&str>) {
+    let scoped = usize::from(selector.starts_with('@'));
+    match selector[scoped..].find('@') {
+        Some(pos) => (&selector[..pos + scoped], Some(&selector[pos + scoped + 1..])),
+        None => (selector, None),
+    }
+}
+
+/// 从 packages/snapshots 节点的 key 中拆分包名和版本号
+pub fn parse_package_key(key: &str) -> Option<(String, String)> {
+    LockedPackage::parse(key).map(|package| (package.name, package.version))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 单项目的 v5 锁文件：数字版本号，直接依赖写在顶层，版本规格在 specifiers 中
+    const V5: &str = r#"lockfileVersion: 5.4
+
+specifiers:
+  '@ant-design/icons': ^4.8.0
+  lodash: 4.17.20
+  string-width-cjs: npm:string-width@^4.2.0
+
+dependencies:
+  '@ant-design/icons': 4.8.3_@types+react@18.2.0+react@18.2.0
+  string-width-cjs: /string-width/4.2.3
+
+devDependencies:
+  lodash: 4.17.20
+
+packages:
+
+  /@ant-design/icons/4.8.3_@types+react@18.2.0+react@18.2.0:
+    resolution: {integrity: sha512-icons}
+    dev: false
+
+  /lodash/4.17.20:
+    resolution: {integrity: sha512-lodash}
+    dev: true
+
+  /string-width/4.2.3:
+    resolution: {integrity: sha512-string-width}
+    dev: false
+"#;
+
+    fn dependency<'a>(lock: &'a Lockfile, importer: &str, name: &str) -> &'a DependencyInfo {
+        let importer = &lock.importers[importer];
+        [&importer.dependencies, &importer.dev_dependencies, &importer.optional_dependencies]
+            .into_iter()
+            .find_map(|deps| deps.get(name))
+            .unwrap()
+    }
+
+    fn integrity(resolution: Option<Resolution>) -> Option<String> {
+        match resolution {
+            Some(Resolution::Registry { integrity }) => Some(integrity),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn v5_version() {
+        let lock = Lockfile::parse(V5).unwrap();
+        assert_eq!(lock.lockfile_version, "5.4");
+    }
+
+    /// 只有版本号与一个依赖的 v9 锁文件
+    fn v9_with_version(version: &str) -> String {
+        format!(
+            "lockfileVersion: {}\n\nimporters:\n  .:\n    dependencies:\n      lodash:\n        specifier: 4.17.20\n        version: 4.17.20\n",
+            version
+        )
+    }
+
+    #[test]
+    fn version_as_number_or_string() {
+        // 9.0 在 YAML 中是浮点数，'9.0' 是字符串，两者得到相同的版本号
+        for (raw, expected) in [("9.0", "9.0"), ("'9.0'", "9.0"), ("5.4", "5.4"), ("'6.0'", "6.0")] {
+            let lock = Lockfile::parse(&v9_with_version(raw)).unwrap();
+            assert_eq!(lock.lockfile_version, expected, "lockfileVersion: {}", raw);
+            assert!(lock.importers["."].dependencies.contains_key("lodash"));
+        }
+    }
+
+    #[test]
+    fn v5_specifiers() {
+        let lock = Lockfile::parse(V5).unwrap();
+        // 没有 importers 节点时顶层的直接依赖归入根目录
+        assert_eq!(lock.importers.keys().collect::<Vec<_>>(), ["."]);
+
+        let icons = dependency(&lock, ".", "@ant-design/icons");
+        assert_eq!(icons.specifier, "^4.8.0");
+        assert_eq!(icons.version, "4.8.3_@types+react@18.2.0+react@18.2.0");
+        assert_eq!(dependency(&lock, ".", "lodash").specifier, "4.17.20");
+        assert_eq!(dependency(&lock, ".", "string-width-cjs").specifier, "npm:string-width@^4.2.0");
+        assert!(lock.importers["."].dev_dependencies.contains_key("lodash"));
+    }
+
+    #[test]
+    fn v5_package_keys() {
+        let lock = Lockfile::parse(V5).unwrap();
+        assert_eq!(
+            lock.locked["/lodash/4.17.20"],
+            LockedPackage {
+                name: "lodash".to_string(),
+                version: "4.17.20".to_string(),
+                peers: Vec::new(),
+            }
+        );
+        // 作用域包的 peer 组合以 _ 开始、以 + 连接，peer 作用域包名中的 / 写作 +
+        let icons = &lock.locked["/@ant-design/icons/4.8.3_@types+react@18.2.0+react@18.2.0"];
+        assert_eq!(icons.name, "@ant-design/icons");
+        assert_eq!(icons.version, "4.8.3");
+        assert_eq!(icons.label(), "@ant-design/icons@4.8.3 (@types/react@18.2.0, react@18.2.0)");
+    }
+
+    #[test]
+    fn v5_importer_records() {
+        let lock = Lockfile::parse(V5).unwrap();
+        let records: Vec<PackageRecord> = lock.packages().filter(|r| r.section == Section::Importers).collect();
+        let record = |name: &str| records.iter().find(|r| r.name == name).unwrap();
+
+        let icons = record("@ant-design/icons");
+        assert_eq!(icons.version, "4.8.3");
+        assert_eq!(icons.specifier, Some("^4.8.0"));
+        assert_eq!(icons.importer, Some("."));
+        // /name/version 形式的 key 同样能找到 resolution
+        assert_eq!(integrity(record("lodash").resolution.clone()).as_deref(), Some("sha512-lodash"));
+
+        let alias = record("string-width");
+        assert_eq!(alias.version, "4.2.3");
+        assert_eq!(alias.alias, Some("string-width-cjs"));
+        assert_eq!(integrity(alias.resolution.clone()).as_deref(), Some("sha512-string-width"));
+    }
+}
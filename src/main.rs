@@ -1,16 +1,87 @@
 use anyhow::{Context, Result};
-use clap::Parser;
+use clap::parser::ValueSource;
+use clap::{ArgMatches, CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
 use serde::Deserialize;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fs;
+use std::io::Read;
 use std::path::Path;
+use std::sync::Mutex;
+
+mod align;
+mod archive;
+mod bundle;
+mod collation;
+mod config;
+mod consistency;
+mod dates;
+mod depends;
+mod dir;
+mod export;
+mod exposure;
+mod extract;
+mod files;
+mod github;
+mod gitlab;
+mod graph;
+mod health;
+mod history;
+mod http;
+mod identical;
+mod image;
+mod init;
+mod matching;
+mod members;
+mod mirrors;
+mod multi;
+mod node_modules;
+mod org;
+mod osv;
+mod overrides;
+mod pager;
+mod policy;
+mod redact;
+mod rekor;
+mod registry;
+mod repo;
+mod report;
+mod reverify;
+mod risk;
+mod sbom;
+mod scan;
+mod selftest;
+mod stats;
+mod suppress;
+mod trend;
+mod usage;
+mod visibility;
+mod watch;
+mod why;
+
+use config::Config;
+use http::{Bundle, Client};
+use npm_package_check::batch;
+use npm_package_check::lockfile::{InstallScripts, Lockfile, LockfileFormat, PackageIndex, Resolution, Section};
+use npm_package_check::progress::ProgressEvent;
+#[cfg(feature = "yarn")]
+use npm_package_check::yarn;
+use exposure::{ExposureWindow, LockHistory};
+use github::{DEFAULT_GITHUB_API, GitHub};
+use gitlab::DEFAULT_GITLAB_API;
+use matching::{MatchMode, version_matches};
+use policy::{Policy, Severity};
+use rekor::{DEFAULT_REKOR, Rekor};
+use registry::{Availability, DEFAULT_DOWNLOADS_API, DEFAULT_REGISTRY, DownloadsApi, Registry};
 
 #[derive(Parser, Debug)]
 #[command(
     name = "npm_package_check",
-    about = "检查 pnpm-lock.yaml 文件中是否包含指定的包和版本"
+    about = "检查 pnpm-lock.yaml、package-lock.json（npm-shrinkwrap.json）、yarn.lock、bun.lock 或 deno.lock 文件中是否包含指定的包和版本"
 )]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     #[arg(help = "要查找的包名（例如：antd 或 @ant-design/icons）")]
     package: Option<String>,
 
@@ -18,102 +89,756 @@ struct Args {
     version: Option<String>,
 
     #[arg(
-        short,
-        long,
+        short = 'f',
+        long = "file",
+        id = "file",
+        value_name = "FILE",
+        global = true,
         default_value = "pnpm-lock.yaml",
-        help = "pnpm-lock.yaml 文件路径"
+        help = "锁文件路径（pnpm-lock.yaml、package-lock.json、npm-shrinkwrap.json、yarn.lock、bun.lock、deno.lock 或 Cargo.lock），指定目录时在目录中查找，- 表示从标准输入读取，http(s):// 地址表示从远程下载，.tar.gz/.tgz/.tar/.zip 压缩包表示检查其中的全部锁文件；可多次指定或使用 glob 模式，逐一检查后汇总"
     )]
+    files: Vec<String>,
+
+    /// 当前检查的锁文件，由 --file 或 --dir、--repo 等多锁文件模式依次设置
+    #[arg(skip)]
     file: String,
 
-    #[arg(short, long, help = "显示详细信息")]
+    /// org-scan 汇总报告时收集各锁文件的检查结果，不单独写出报告
+    #[arg(skip)]
+    reports: Option<Mutex<Vec<report::JsonReport>>>,
+
+    #[arg(
+        long,
+        value_name = "OWNER/NAME[@REF]",
+        conflicts_with = "file",
+        help = "通过 GitHub API 查找并下载仓库中的全部锁文件逐一检查，不需要本地检出；REF 为分支、标签或提交，默认为默认分支"
+    )]
+    repo: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "DIR",
+        conflicts_with_all = ["file", "repo"],
+        help = "递归查找目录中的全部锁文件逐一检查（遵循 .gitignore，跳过 node_modules，可用 --exclude 排除），最后汇总各项目的结果"
+    )]
+    dir: Option<String>,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "FORMAT",
+        help = "锁文件格式（pnpm、npm、yarn、bun、deno、cargo），默认按文件名判断，文件名无法判断时按内容判断"
+    )]
+    lockfile_format: Option<LockfileFormat>,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "GLOB",
+        help = "在目录中查找锁文件时排除的路径（可多次指定，node_modules 与 .gitignore 中的路径默认排除）"
+    )]
+    exclude: Vec<String>,
+
+    #[arg(
+        long,
+        global = true,
+        help = "在目录中查找锁文件或展开 -f 的 glob 模式时跟随符号链接（只跟随指向该目录内部的链接），默认不跟随"
+    )]
+    follow_symlinks: bool,
+
+    #[arg(long, global = true, help = "不使用分页程序（默认在交互式终端中通过 $PAGER 或 less 显示超过一屏的输出）")]
+    no_pager: bool,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "DIR",
+        help = "仓库根目录，输出与报告中的 importer 路径改为相对于该目录，而不是锁文件所在目录"
+    )]
+    base_dir: Option<String>,
+
+    #[arg(long, global = true, conflicts_with = "only_private", help = "只检查可以发布的 importer（package.json 中没有 \"private\": true）")]
+    only_public: bool,
+
+    #[arg(long, global = true, help = "只检查 package.json 中声明了 \"private\": true 的 importer")]
+    only_private: bool,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "NAME",
+        help = "只检查指定的 workspace（package.json 中的包名或 importer 路径，可多次指定）；控制台输出中的 importer 同时标注 workspace 包名"
+    )]
+    workspace: Vec<String>,
+
+    #[arg(short, long, global = true, help = "显示详细信息")]
     verbose: bool,
     
-    #[arg(short, long, help = "批量检查模式：指定包列表文件路径")]
+    #[arg(short, long, help = "批量检查模式：指定包列表文件路径（或 OSV 公告目录）")]
     batch: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "SBOM",
+        conflicts_with_all = ["batch", "package"],
+        help = "以 CycloneDX（JSON）或 SPDX（JSON、tag-value）格式 SBOM 中的组件为检查清单，同时列出锁文件中存在但 SBOM 未列出的组件"
+    )]
+    batch_sbom: Option<String>,
+
+    #[arg(
+        long,
+        global = true,
+        env = "NPC_BATCH_TOKEN",
+        hide_env_values = true,
+        value_name = "TOKEN",
+        help = "下载远程批量文件或风险清单时以 Authorization: Bearer 发送的 token（例如需要登录才能导出的 Google Sheets）"
+    )]
+    batch_token: Option<String>,
+
+    /// 批量文件的列映射，来自配置文件的 batch_columns
+    #[arg(skip)]
+    batch_columns: Option<batch::Columns>,
     
-    #[arg(long, help = "输出报告文件路径（批量模式）")]
+    #[arg(long, help = "输出报告文件路径（单包模式与批量模式均写 TSV 报告）")]
     output: Option<String>,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = ReportFormat::Text,
+        help = "报告格式（批量模式）：text 为控制台输出加 TSV 报告，json 为 JSON 报告，osv 导出 OSV 公告"
+    )]
+    format: ReportFormat,
+
+    #[arg(long, default_value = "NPC", help = "导出 OSV 公告时使用的 ID 前缀")]
+    osv_prefix: String,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = VerdictMode::All,
+        help = "批量模式下期望版本与判定结果的对应规则"
+    )]
+    verdict_mode: VerdictMode,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = MatchMode::Prefix,
+        help = "版本匹配方式，单个期望版本可用 exact:/prefix:/semver:/regex: 前缀覆盖"
+    )]
+    match_mode: MatchMode,
+
+    #[arg(long, help = "批量模式下逐条解释判定原因")]
+    explain: bool,
+
+    #[arg(long, help = "结合锁文件的 git 历史计算受影响版本的暴露窗口（批量模式）")]
+    exposure: bool,
+
+    #[arg(long, help = "Status 列到处理级别（fail/warn/info）的映射策略文件（批量模式）")]
+    policy: Option<String>,
+
+    #[arg(long, global = true, help = "配置文件路径（默认读取当前目录下的 npm_package_check.yaml）")]
+    config: Option<String>,
+
+    #[arg(long, help = "查询命中包在 registry 上的 dist-tags，检查受影响版本是否仍为 latest")]
+    dist_tags: bool,
+
+    #[arg(long, help = "检查锁定版本是否已从 registry 撤下或被安全接管")]
+    check_unpublished: bool,
+
+    #[arg(long, global = true, default_value = DEFAULT_REGISTRY, help = "npm registry 地址")]
+    registry: String,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "URL",
+        help = "网络请求使用的代理（http://、https:// 或 socks5://），未指定时读取 HTTPS_PROXY/HTTP_PROXY/ALL_PROXY 与 NO_PROXY"
+    )]
+    proxy: Option<String>,
+
+    #[arg(long, global = true, value_name = "PEM", env = "NPC_CACERT", help = "PEM 格式的 CA 证书文件（例如企业代理的根证书），与系统的 CA 证书一起信任")]
+    cacert: Option<String>,
+
+    #[arg(long, global = true, env = "GITHUB_TOKEN", hide_env_values = true, help = "GitHub API token（health --github、--repo 与 org-scan）")]
+    github_token: Option<String>,
+
+    #[arg(long, global = true, default_value = DEFAULT_GITHUB_API, help = "GitHub API 地址")]
+    github_api: String,
+
+    #[arg(long, help = "查询命中包最近一周的下载量，区分小众包与生态级事件")]
+    downloads: bool,
+
+    #[arg(long, default_value = DEFAULT_DOWNLOADS_API, help = "npm 下载量统计 API 地址")]
+    downloads_api: String,
+
+    #[arg(long, help = "在 Sigstore Rekor 透明日志中查找命中版本 tarball 摘要的记录（批量模式）")]
+    rekor: bool,
+
+    #[arg(long, default_value = DEFAULT_REKOR, help = "Rekor 服务地址")]
+    rekor_url: String,
+
+    #[arg(long, help = "控制台最多显示的条目数（单包模式的位置、批量模式的结果），报告文件不受影响")]
+    limit: Option<usize>,
+
+    #[arg(long, default_value_t = 0, help = "控制台输出跳过的条目数，与 --limit 配合翻页")]
+    offset: usize,
+
+    #[arg(
+        long,
+        value_parser = dates::parse_timezone,
+        help = "报告中日期与时间使用的时区（UTC、local 或 +08:00），带时间的 Detection Date 按此换算为日期"
+    )]
+    timezone: Option<chrono::FixedOffset>,
+
+    #[arg(
+        long,
+        value_name = "LOCALE",
+        help = "输出中包名、importer 路径等的排序规则所用的语言区域（BCP 47，例如 zh 按拼音、zh-u-co-stroke 按笔画），默认为与语言无关的 Unicode 根排序规则"
+    )]
+    sort_locale: Option<String>,
+
+    #[arg(long, help = "写出简短的 JSON 结论（状态、各状态计数、报告路径），以非零状态结束或出错时同样写出")]
+    result_file: Option<String>,
+
+    #[arg(long, help = "把本次检查的概要（命中数、风险评分等）追加到本地的 JSON Lines 统计文件，不会发送到任何服务")]
+    usage_stats: Option<String>,
+
+    #[arg(long, env = "NPC_WEBHOOK", hide_env_values = true, help = "检查发现命中时 POST {\"text\": ...} 的 webhook 地址")]
+    webhook: Option<String>,
+
+    #[arg(long, value_enum, help = "--webhook 的通知粒度：per-run 每次检查发送一条汇总（默认），per-finding 每个新增命中单独发送")]
+    notify: Option<watch::NotifyMode>,
+
+    #[arg(long, value_name = "FILE", help = "--webhook 已通知命中的记录文件，按命中去重，只通知新增的命中")]
+    notify_state: Option<String>,
+
+    #[arg(
+        long,
+        global = true,
+        help = "只输出将要执行的写操作（webhook 通知与状态文件、--usage-stats 追加、init 生成文件），不实际执行"
+    )]
+    dry_run: bool,
+
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        default_value_t = LogFormat::Text,
+        help = "标准错误的日志格式：json 时额外逐行输出进度事件（发现的锁文件、已检查的条目数、目前的命中数）"
+    )]
+    log_format: LogFormat,
 }
 
-#[derive(Debug, Deserialize)]
-struct PnpmLock {
-    #[serde(rename = "lockfileVersion")]
-    lockfile_version: String,
-    
-    #[serde(default)]
-    importers: HashMap<String, Importer>,
-    
-    #[serde(default)]
-    packages: HashMap<String, PackageInfo>,
-    
-    #[serde(default)]
-    snapshots: HashMap<String, SnapshotInfo>,
+#[derive(Subcommand, Debug)]
+enum Command {
+    #[command(about = "生成带注释的配置文件、策略文件和批量文件模板")]
+    Init {
+        #[arg(long, default_value = ".", help = "模板输出目录")]
+        dir: String,
+
+        #[arg(long, help = "覆盖已存在的文件")]
+        force: bool,
+    },
+
+    #[command(about = "报告长期未发布、仓库已归档或仅有单一维护者的依赖")]
+    Health {
+        #[arg(long, default_value_t = 2, help = "超过多少年未发布新版本视为停止维护")]
+        years: u32,
+
+        #[arg(long, help = "通过 GitHub API 检查源码仓库是否已归档")]
+        github: bool,
+    },
+
+    #[command(about = "导出内部包名与私有 registry 地址经过哈希处理的锁文件副本，便于与外部共享")]
+    Redact {
+        #[arg(long, value_name = "SCOPE", help = "内部作用域（例如 @acme，可多次指定），其下的包名会被哈希")]
+        scope: Vec<String>,
+
+        #[arg(long, env = "NPC_REDACT_SALT", hide_env_values = true, help = "哈希时附加的盐值，避免通过常见包名反推")]
+        salt: Option<String>,
+
+        #[arg(long, help = "只导出规范化的包列表（每行一个 name@version），而不是完整锁文件")]
+        list: bool,
+
+        #[arg(long, help = "输出文件路径，默认输出到标准输出")]
+        output: Option<String>,
+    },
+
+    #[command(about = "提取重现问题所需的最小锁文件片段（importer、依赖链上的 packages 与 snapshots 条目），便于附在 issue 中")]
+    Extract {
+        #[arg(help = "查询表达式 name@range，range 为 npm 风格的版本范围，省略时匹配任意版本")]
+        query: String,
+
+        #[arg(long, help = "输出文件路径，默认输出到标准输出")]
+        output: Option<String>,
+    },
+
+    #[command(about = "对照 CycloneDX / SPDX 格式的 SBOM 检查锁文件中的组件与版本是否一致")]
+    Sbom {
+        #[arg(help = "SBOM 文件路径（CycloneDX JSON，SPDX JSON 或 tag-value）")]
+        sbom: String,
+    },
+
+    #[command(about = "检查 snapshots 与 packages 节点的条目是否一一对应，找出缺失或多余的条目")]
+    Consistency,
+
+    #[command(about = "找出不同 importer 对同一个包使用不同版本规格或锁定版本的情况")]
+    Align {
+        #[arg(long, help = "查询 registry，给出同时满足各 importer 版本规格的最高版本")]
+        suggest_align: bool,
+    },
+
+    #[command(about = "列出声明了满足条件的依赖的 importer 与包，例如 depends-on 'react@<18'，用于规划升级")]
+    DependsOn {
+        #[arg(help = "查询表达式 name@range，range 为 npm 风格的版本范围，省略时匹配任意版本")]
+        query: String,
+    },
+
+    #[command(about = "追踪包被安装的原因：列出从各 importer 出发的依赖链及每一步的依赖类型，并判断是否存在生产依赖链")]
+    Why {
+        #[arg(help = "查询表达式 name@range，range 为 npm 风格的版本范围，省略时匹配任意版本")]
+        query: String,
+    },
+
+    #[command(about = "导出规范化的包记录（包名、版本、所在节点、依赖类型、来源等），供数据分析使用")]
+    Export {
+        #[arg(long, value_enum, default_value_t = export::ExportFormat::Jsonl, help = "导出格式")]
+        format: export::ExportFormat,
+
+        #[arg(long, help = "输出文件路径，默认输出到标准输出")]
+        output: Option<String>,
+    },
+
+    #[command(about = "导出 importer 之间通过 workspace:/link: 形成的依赖关系图（DOT 或 JSON），了解 monorepo 内部的耦合")]
+    WorkspaceGraph {
+        #[arg(long, value_enum, default_value_t = graph::GraphFormat::Dot, help = "导出格式")]
+        format: graph::GraphFormat,
+
+        #[arg(long, help = "输出文件路径，默认输出到标准输出")]
+        output: Option<String>,
+    },
+
+    #[command(about = "按 importer 统计直接依赖、开发依赖、传递依赖数与不同版本数")]
+    Stats {
+        #[arg(long, value_enum, default_value_t = stats::SortKey::Importer, help = "排序列，数值列从大到小排序")]
+        sort: stats::SortKey,
+    },
+
+    #[command(about = "检查各包的下载地址是否来自允许的镜像，列出 git 托管平台生成的 tarball 与未知来源的地址")]
+    Mirrors {
+        #[arg(long, value_name = "MIRROR", help = "允许的镜像：主机名（包括其子域名）或地址前缀，可多次指定；默认 registry 始终允许")]
+        allow: Vec<String>,
+    },
+
+    #[command(about = "找出 integrity 相同（tarball 内容逐字节相同）却以不同包名发布的包，常见于 fork 与抢注的包名")]
+    Identical,
+
+    #[command(about = "检查 package.json 中的 resolutions / overrides 是否在锁文件中全部生效，列出仍引用其他版本的位置")]
+    Overrides,
+
+    #[command(about = "遍历 node_modules 读取已安装包的 package.json，找出锁文件中没有或版本不一致的包")]
+    NodeModules {
+        #[arg(help = "node_modules 目录，默认检查锁文件所在目录与各 importer 目录中的 node_modules")]
+        dir: Option<String>,
+    },
+
+    #[command(about = "用内置锁文件样本和指定的锁文件检查解析覆盖率")]
+    Selftest {
+        #[arg(help = "额外检查的锁文件或目录（目录中的全部 .yaml/.yml/.json/.lock 文件）")]
+        paths: Vec<String>,
+    },
+
+    #[command(about = "定期拉取远程风险清单，清单或锁文件变化时重新检查，并通知新的命中")]
+    WatchFeed {
+        #[arg(long, help = "风险清单地址（http/https）或本地文件，格式与批量文件相同")]
+        feed: Option<String>,
+
+        #[arg(help = "要检查的锁文件或目录（可多个），默认使用 -f/--file")]
+        lockfiles: Vec<String>,
+
+        #[arg(long, default_value_t = 900, help = "检查间隔（秒）")]
+        interval: u64,
+
+        #[arg(long, env = "NPC_WEBHOOK", hide_env_values = true, help = "新增命中时 POST {\"text\": ...} 的 webhook 地址")]
+        webhook: Option<String>,
+
+        #[arg(long, value_enum, help = "通知粒度：per-run 每轮发送一条汇总（默认），per-finding 每个新增命中单独发送")]
+        notify: Option<watch::NotifyMode>,
+
+        #[arg(long, value_name = "FILE", help = "已通知命中的记录文件，按命中去重，重启或配合 --once 定期执行时不会重复通知")]
+        state: Option<String>,
+
+        #[arg(long, help = "只检查一次，有命中时以退出码 1 结束")]
+        once: bool,
+    },
+
+    #[command(about = "创建或使用离线包：预先拉取远程输入，在无网络的机器上复现相同的检查")]
+    Bundle {
+        #[command(subcommand)]
+        action: BundleAction,
+    },
+
+    #[command(about = "汇总 --usage-stats 记录的历次批量检查，按项目与周期列出命中数、命中包数与风险评分的变化")]
+    Trend {
+        #[arg(long, value_enum, default_value_t = trend::Period::Month, help = "汇总周期，每个周期取最后一次检查的结果")]
+        by: trend::Period,
+
+        #[arg(long, help = "只显示该锁文件路径（与记录中的 lockfile 相同）的趋势")]
+        project: Option<String>,
+
+        #[arg(long, help = "同时把趋势数据导出为 CSV 文件")]
+        csv: Option<String>,
+    },
+
+    #[command(about = "沿锁文件的全部 git 历史逐个提交执行检查，报告每个受影响版本首次与最后一次出现的提交")]
+    History {
+        #[arg(help = "要查找的包名，省略时检查 -b/--batch 指定的清单")]
+        package: Option<String>,
+
+        #[arg(help = "版本号（可选，不指定则匹配任意版本）")]
+        version: Option<String>,
+    },
+
+    #[command(about = "拉取容器镜像（或读取 docker save 导出的 tar），检查其中的锁文件与 node_modules，审计实际发布的依赖")]
+    Image {
+        #[arg(help = "镜像引用 [registry/]repository[:tag|@digest]，或 docker save / OCI 镜像布局导出的 tar 文件")]
+        image: String,
+
+        #[arg(long, default_value = "linux/amd64", help = "多平台镜像中要检查的平台 os/architecture[/variant]")]
+        platform: String,
+
+        #[arg(long, env = "NPC_REGISTRY_AUTH", hide_env_values = true, value_name = "USER:PASSWORD", help = "拉取私有镜像时 registry 的用户名与密码")]
+        registry_auth: Option<String>,
+    },
+
+    #[command(about = "通过 GitHub / GitLab API 列出组织的全部仓库，检查各仓库默认分支中的锁文件，输出汇总报告")]
+    OrgScan {
+        #[arg(help = "GitHub 组织或用户名，或 GitLab group 路径（包括子 group）")]
+        org: String,
+
+        #[arg(long, value_enum, default_value = "github", help = "代码托管平台")]
+        provider: org::Provider,
+
+        #[arg(long, help = "同时检查已归档的仓库")]
+        include_archived: bool,
+
+        #[arg(long, help = "同时检查 fork 的仓库")]
+        include_forks: bool,
+
+        #[arg(long, default_value = DEFAULT_GITLAB_API, help = "GitLab API 地址（自建实例为 https://<host>/api/v4）")]
+        gitlab_api: String,
+
+        #[arg(long, env = "GITLAB_TOKEN", hide_env_values = true, help = "GitLab API token（read_api 权限）")]
+        gitlab_token: Option<String>,
+    },
+
+    #[command(about = "对照当前锁文件复核历史 JSON 报告中的发现项，输出修复进度")]
+    Reverify {
+        #[arg(help = "之前使用 --format json 生成的报告文件")]
+        report: String,
+
+        #[arg(long, help = "复核结果 JSON 输出路径")]
+        output: Option<String>,
+    },
 }
 
-#[derive(Debug, Deserialize)]
-struct Importer {
-    #[serde(default)]
-    dependencies: HashMap<String, DependencyInfo>,
-    
-    #[serde(default)]
-    #[serde(rename = "devDependencies")]
-    dev_dependencies: HashMap<String, DependencyInfo>,
-    
-    #[serde(default)]
-    #[serde(rename = "optionalDependencies")]
-    optional_dependencies: HashMap<String, DependencyInfo>,
+#[derive(Subcommand, Debug)]
+enum BundleAction {
+    #[command(about = "为锁文件拉取 registry 元数据、风险清单等远程输入，写入离线包")]
+    Create {
+        #[arg(long, help = "离线包输出路径")]
+        output: String,
+
+        #[arg(long, value_name = "URL", help = "一并拉取的风险清单地址（可多次指定）")]
+        feed: Vec<String>,
+
+        #[arg(long, help = "同时拉取各包最近一周的下载量（--downloads）")]
+        downloads: bool,
+
+        #[arg(long, help = "同时查询锁文件中各包 integrity 在 Rekor 中的记录（--rekor）")]
+        rekor: bool,
+    },
+
+    #[command(about = "使用离线包执行检查，所有远程输入从离线包读取")]
+    Use {
+        #[arg(help = "bundle create 生成的离线包")]
+        bundle: String,
+
+        #[arg(
+            trailing_var_arg = true,
+            allow_hyphen_values = true,
+            help = "检查参数，与直接运行时相同（例如 -b version2.txt --dist-tags）"
+        )]
+        args: Vec<String>,
+    },
 }
 
-#[derive(Debug, Deserialize)]
-struct DependencyInfo {
-    specifier: String,
-    version: String,
+/// 批量模式的报告格式
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+enum ReportFormat {
+    /// 控制台输出，指定 --output 时另写 TSV 报告
+    Text,
+    /// JSON 报告，输出到 --output 或标准输出
+    Json,
+    /// OSV 公告 JSON，输出到 --output（文件或目录）或标准输出
+    Osv,
 }
 
-#[derive(Debug, Deserialize)]
-struct PackageInfo {
-    resolution: Resolution,
-    
-    #[serde(default)]
-    #[serde(rename = "peerDependencies")]
-    peer_dependencies: HashMap<String, String>,
-    
-    #[serde(default)]
-    dependencies: HashMap<String, String>,
-    
-    #[serde(default)]
-    #[serde(rename = "devDependencies")]
-    dev_dependencies: HashMap<String, String>,
+/// 标准错误的日志格式
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+enum LogFormat {
+    /// 只输出面向人的信息
+    Text,
+    /// 额外逐行输出 JSON 格式的进度事件，供 GUI 封装与 CI 插件渲染实时进度
+    Json,
 }
 
-#[derive(Debug, Deserialize)]
-struct Resolution {
-    integrity: String,
-    
-    #[serde(default)]
-    tarball: Option<String>,
+/// 批量模式下，期望版本集合如何映射为检查结论
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum VerdictMode {
+    /// 任一期望版本命中即视为找到
+    Any,
+    /// 所有期望版本均命中才视为找到，部分命中为部分匹配
+    All,
+    /// 实际版本集合必须与期望版本集合完全一致
+    ExactSet,
 }
 
-#[derive(Debug, Deserialize)]
-struct SnapshotInfo {
-    #[serde(default)]
-    dependencies: HashMap<String, String>,
-    
-    #[serde(default)]
-    #[serde(rename = "devDependencies")]
-    dev_dependencies: HashMap<String, String>,
-    
-    #[serde(default)]
-    #[serde(rename = "optionalDependencies")]
-    optional_dependencies: HashMap<String, String>,
+/// 控制台输出的分页范围
+#[derive(Debug, Clone, Copy)]
+struct Page {
+    offset: usize,
+    limit: Option<usize>,
 }
 
-#[derive(Debug)]
+impl Page {
+    fn contains(&self, index: usize) -> bool {
+        index >= self.offset && self.limit.is_none_or(|limit| index < self.offset + limit)
+    }
+
+    fn range(&self, total: usize) -> std::ops::Range<usize> {
+        let start = self.offset.min(total);
+        let end = self.limit.map_or(total, |limit| (start + limit).min(total));
+        start..end
+    }
+
+    fn slice<'a, T>(&self, items: &'a [T]) -> &'a [T] {
+        &items[self.range(items.len())]
+    }
+
+    /// 有条目未显示时提示翻页方式
+    fn print_omitted(&self, total: usize) {
+        let range = self.range(total);
+        if range.is_empty() && total > 0 {
+            println!("   … 共 {} 条，--offset {} 之后没有更多条目", total, self.offset);
+        } else if range.len() < total {
+            println!(
+                "   … 共 {} 条，当前显示第 {}-{} 条，使用 --offset/--limit 查看其余条目",
+                total,
+                range.start + 1,
+                range.end
+            );
+        }
+    }
+}
+
+/// 包在锁文件中出现的位置
+#[derive(Debug, Clone, PartialEq)]
+enum Location {
+    /// importers 节点中的 importer 路径（"." 为根目录）
+    Importer(String),
+    PackagesSection,
+    SnapshotsSection,
+}
+
+impl Location {
+    /// 机器可读报告中使用的稳定标识：importer:<路径>、packages、snapshots
+    fn id(&self) -> String {
+        match self {
+            Location::Importer(path) => format!("importer:{}", path),
+            Location::PackagesSection => "packages".to_string(),
+            Location::SnapshotsSection => "snapshots".to_string(),
+        }
+    }
+
+    fn is_importer(&self) -> bool {
+        matches!(self, Location::Importer(_))
+    }
+}
+
+/// 控制台输出使用的名称
+impl std::fmt::Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Location::Importer(path) if path == "." => f.write_str("根目录"),
+            Location::Importer(path) => f.write_str(path),
+            Location::PackagesSection => f.write_str("packages节点"),
+            Location::SnapshotsSection => f.write_str("snapshots节点"),
+        }
+    }
+}
+
+/// 锁文件所在目录提供的上下文（见 apply_local_context），每个锁文件单独生成：
+/// 根 importer 的路径（使用 --base-dir 时为锁文件所在目录的相对路径）与根项目 package.json 中的包名，
+/// 以及各 importer 的 workspace 包名（见 members.rs），控制台输出中标注在 importer 路径前
+struct LockfileContext {
+    root: String,
+    root_name: Option<String>,
+    workspace_names: BTreeMap<String, String>,
+}
+
+/// 没有本地目录的锁文件（远程锁文件、压缩包与仓库中的锁文件）：根 importer 为 "."，没有包名
+impl Default for LockfileContext {
+    fn default() -> Self {
+        LockfileContext { root: ".".to_string(), root_name: None, workspace_names: BTreeMap::new() }
+    }
+}
+
+/// -f - 表示从标准输入读取锁文件
+const STDIN_LOCKFILE: &str = "-";
+
+/// 锁文件与批量文件可以是 http(s) 地址
+fn is_remote(path: &str) -> bool {
+    path.starts_with("http://") || path.starts_with("https://")
+}
+
+/// 本地磁盘上的锁文件：不是标准输入、远程地址，也不是压缩包或镜像中的条目（这些条目的名称不是实际存在的文件）
+fn is_local_file(path: &str) -> bool {
+    path != STDIN_LOCKFILE && !is_remote(path) && Path::new(path).is_file()
+}
+
+/// 远程锁文件（以及压缩包中的锁文件）旁没有本地的 package.json 与 git 历史，不能使用依赖它们的选项；
+/// source 为错误信息中的锁文件来源
+fn reject_local_options(args: &Args, source: &str) -> Result<()> {
+    if args.exposure {
+        anyhow::bail!("--exposure 需要读取锁文件的 git 历史，不能用于{}", source);
+    }
+    if args.only_public || args.only_private || !args.workspace.is_empty() || args.base_dir.is_some() {
+        anyhow::bail!("--only-public、--only-private、--workspace 与 --base-dir 需要读取锁文件所在目录，不能用于{}", source);
+    }
+    Ok(())
+}
+
+/// 远程锁文件地址中的文件名（去掉查询参数与片段），用于判断锁文件格式
+fn remote_file_name(url: &str) -> &str {
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+impl LockfileContext {
+    /// importer 在控制台中的显示名称：根 importer 显示为根项目的包名，没有包名时为 "根目录"；
+    /// 其他 importer 有 workspace 包名时显示为 "包名 (路径)"
+    fn importer_label(&self, path: &str) -> String {
+        let root_name = self.root_name.as_deref().filter(|_| self.root == path);
+        if root_name.is_none()
+            && let Some(name) = self.workspace_names.get(path)
+        {
+            return format!("{} ({})", name, path);
+        }
+        match (root_name, path) {
+            (Some(name), ".") => name.to_string(),
+            (Some(name), path) => format!("{} ({})", name, path),
+            (None, ".") => "根目录".to_string(),
+            (None, path) => path.to_string(),
+        }
+    }
+}
+
+/// 锁文件所在目录相对于 base_dir 的路径，两者相同时为空
+fn importer_prefix(base_dir: &str, lockfile: &Path) -> Result<String> {
+    let base = fs::canonicalize(base_dir).with_context(|| format!("无法访问 --base-dir '{}'", base_dir))?;
+    // 按锁文件所在目录计算，从标准输入读取的锁文件（-）视为位于当前目录
+    let dir = lockfile_dir(lockfile);
+    let dir = fs::canonicalize(dir).with_context(|| format!("无法访问目录 '{}'", dir.display()))?;
+    let relative = dir
+        .strip_prefix(&base)
+        .with_context(|| format!("锁文件 '{}' 不在 --base-dir '{}' 之下", lockfile.display(), base_dir))?;
+    let parts: Vec<_> = relative.components().map(|c| c.as_os_str().to_string_lossy()).collect();
+    Ok(parts.join("/"))
+}
+
+/// 锁文件旁 package.json 中的包名
+fn root_package_name(lockfile: &Path) -> Option<String> {
+    let manifest = lockfile.parent()?.join("package.json");
+    let content = fs::read_to_string(manifest).ok()?;
+    let value: serde_json::Value = serde_json::from_str(&content).ok()?;
+    value.get("name")?.as_str().map(str::to_string)
+}
+
+#[derive(Debug, Clone)]
 struct PackageFound {
-    location: String,
+    location: Location,
     specifier: String,
     version: String,
     dependency_type: String,
+    /// 被锁文件 overrides 覆盖时为 "选择器 → 版本规格"
+    overridden: Option<String>,
+    /// 锁文件 patchedDependencies 中的补丁文件
+    patched: Option<String>,
+    /// 安装该版本时是否执行安装脚本
+    install_scripts: Option<InstallScripts>,
+    /// 锁定版本的来源（registry、git、本地目录、tarball 地址或 workspace 链接）
+    resolution: Option<Resolution>,
+    /// 以别名安装时的 (别名, 实际的包名)
+    alias: Option<(String, String)>,
+    /// importer 在控制台中的显示名称，由 annotate_found 按锁文件的上下文填写
+    importer_label: Option<String>,
+}
+
+impl PackageFound {
+    /// 控制台输出中的位置
+    fn place(&self) -> String {
+        self.importer_label.clone().unwrap_or_else(|| self.location.to_string())
+    }
+
+    /// 控制台输出中附加的覆盖与补丁说明
+    fn notes(&self) -> String {
+        let mut notes = String::new();
+        if let Some(alias) = self.alias_text() {
+            notes.push_str(&format!(" 🏷️ 别名: {}", alias));
+        }
+        if let Some(ref overridden) = self.overridden {
+            notes.push_str(&format!(" 🔧 overrides: {}", overridden));
+        }
+        if let Some(ref patched) = self.patched {
+            notes.push_str(&format!(" 📎 补丁: {}", patched));
+        }
+        // registry 以外的来源需要单独审计
+        if let Some(resolution) = self.resolution.as_ref().filter(|resolution| !resolution.is_registry()) {
+            notes.push_str(&format!(" 📦 来源: {}", resolution.kind()));
+        }
+        // 没有安装脚本与锁文件未记录的情况只在 --verbose 中显示
+        if let Some(scripts) = self
+            .install_scripts
+            .filter(|scripts| !matches!(scripts, InstallScripts::Unknown | InstallScripts::NoScripts))
+        {
+            notes.push_str(&format!(" ⚙️ 安装脚本: {}", scripts.describe()));
+        }
+        notes
+    }
+
+    /// 别名与实际的包名，如 string-width-cjs → string-width
+    fn alias_text(&self) -> Option<String> {
+        self.alias.as_ref().map(|(alias, name)| format!("{} → {}", alias, name))
+    }
+
+    /// TSV 报告 Locations 列中的依赖类型与别名、覆盖、补丁标记
+    fn report_type(&self) -> String {
+        let mut text = self.dependency_type.clone();
+        if let Some(alias) = self.alias_text() {
+            text.push_str(&format!(", alias {}", alias));
+        }
+        if self.overridden.is_some() {
+            text.push_str(", overridden");
+        }
+        if self.patched.is_some() {
+            text.push_str(", patched");
+        }
+        text
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -122,6 +847,12 @@ struct BatchPackage {
     versions: Vec<String>,
     status: Option<String>,
     detection_date: Option<String>,
+    // 可选的 Fixed In 列：修复版本，锁文件中已同时存在修复版本时判定为 Mixed (fix present)
+    fixed_in: Vec<String>,
+    // 条目来源的公告 ID（OSV 公告等）
+    advisories: Vec<String>,
+    // 期望版本未带模式前缀时使用的匹配方式
+    match_mode: MatchMode,
 }
 
 #[derive(Debug)]
@@ -129,6 +860,23 @@ struct BatchResult {
     package: BatchPackage,
     found_versions: Vec<PackageFound>,
     status: CheckStatus,
+    exposure: Vec<ExposureWindow>,
+    // 仅在指定策略文件且命中时存在
+    severity: Option<Severity>,
+    // 仅在使用 --dist-tags 且命中时存在
+    dist_tags: Option<BTreeMap<String, String>>,
+    // 使用 --check-unpublished 时，已不可安装的锁定版本
+    unavailable: Vec<(String, Availability)>,
+    // 仅在使用 --downloads 且命中时存在
+    weekly_downloads: Option<u64>,
+    // 清单中有同名条目时，合并后用于判定的期望版本
+    merged_versions: Option<Vec<String>>,
+    // 使用 --rekor 时，命中版本在透明日志中的记录数
+    rekor_entries: BTreeMap<String, usize>,
+    // 风险评分（见 risk.rs），未命中时为 0
+    risk_score: u32,
+    // 被锁文件旁的抑制文件抑制时的抑制记录（见 suppress.rs）
+    suppressed: Option<suppress::Suppression>,
 }
 
 #[derive(Debug, PartialEq)]
@@ -137,42 +885,489 @@ enum CheckStatus {
     VersionMismatch,
     NotFound,
     PartialMatch,
+    // 命中受影响版本，同时锁文件中已有修复版本，通常是部分 importer 尚未升级
+    MixedFixPresent,
+    // 锁定的版本已从 registry 撤下或被安全接管
+    Unpublished,
+}
+
+impl CheckStatus {
+    /// 机器可读报告中使用的状态文本
+    fn report_label(&self) -> &'static str {
+        match self {
+            CheckStatus::Found => "Found",
+            CheckStatus::NotFound => "Not Found",
+            CheckStatus::VersionMismatch => "Version Mismatch",
+            CheckStatus::PartialMatch => "Partial Match",
+            CheckStatus::MixedFixPresent => "Mixed (fix present)",
+            CheckStatus::Unpublished => "Unpublished",
+        }
+    }
+
+    /// 结果文件中统计各状态时使用的键
+    fn key(&self) -> &'static str {
+        match self {
+            CheckStatus::Found => "found",
+            CheckStatus::NotFound => "not_found",
+            CheckStatus::VersionMismatch => "version_mismatch",
+            CheckStatus::PartialMatch => "partial_match",
+            CheckStatus::MixedFixPresent => "mixed_fix_present",
+            CheckStatus::Unpublished => "unpublished",
+        }
+    }
+    
+    /// 是否命中受影响版本，需要处置
+    fn is_hit(&self) -> bool {
+        matches!(self, CheckStatus::Found | CheckStatus::PartialMatch | CheckStatus::MixedFixPresent)
+    }
 }
 
 fn main() -> Result<()> {
-    let args = Args::parse();
+    let matches = Args::command().get_matches();
+    let args = Args::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
     
-    let file_path = Path::new(&args.file);
-    if !file_path.exists() {
-        eprintln!("错误：文件 '{}' 不存在", args.file);
-        std::process::exit(1);
+    // 出错退出时同样写出结果文件，便于 CI 下游区分“检查失败”和“运行出错”
+    let result_file = args.result_file.clone();
+    let lockfile = args.files.join(" ");
+    let outcome = run(args, &matches, None);
+    if let (Err(e), Some(path)) = (&outcome, &result_file) {
+        report::Verdict::from_error(e, &lockfile).write(path)?;
+    }
+    outcome
+}
+
+/// client 为 None 时按命令行的代理与证书设置创建
+fn run(mut args: Args, matches: &ArgMatches, client: Option<Client>) -> Result<()> {
+    if let Some(Command::Bundle { action: BundleAction::Use { ref bundle, args: ref inner } }) = args.command {
+        let bundle = Bundle::load(bundle)?;
+        eprintln!("📦 使用离线包（创建于 {}，锁文件 {}）", bundle.created_at, bundle.lockfile);
+        let matches = Args::command()
+            .try_get_matches_from(std::iter::once("npm_package_check").chain(inner.iter().map(String::as_str)))
+            .unwrap_or_else(|e| e.exit());
+        let args = Args::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+        return run(args, &matches, Some(Client::offline(bundle)));
     }
     
-    let content = fs::read_to_string(file_path)
-        .with_context(|| format!("无法读取文件 '{}'", args.file))?;
+    if let Some(Command::Init { ref dir, force }) = args.command {
+        return init::run_init(dir, force, args.dry_run);
+    }
+    
+    if let Some(Command::Selftest { ref paths }) = args.command {
+        return selftest::run_selftest(paths, args.verbose);
+    }
     
-    let lock_data: PnpmLock = serde_yaml::from_str(&content)
-        .with_context(|| "解析 pnpm-lock.yaml 文件失败")?;
+    if let Some(config) = Config::load(args.config.as_deref())? {
+        apply_config(&mut args, matches, config);
+    }
+    collation::init(args.sort_locale.as_deref())?;
+    // watch-feed 持续输出，不经过分页程序
+    if !matches!(args.command, Some(Command::WatchFeed { .. })) {
+        pager::start(args.no_pager);
+    }
+    
+    if let Some(Command::Trend { by, ref project, ref csv }) = args.command {
+        let Some(ref path) = args.usage_stats else {
+            eprintln!("错误：请通过 --usage-stats 或配置文件中的 usage_stats 指定使用统计文件");
+            std::process::exit(1);
+        };
+        return trend::run_trend(path, by, project.as_deref(), csv.as_deref());
+    }
+    
+    let client = match client {
+        Some(client) => client,
+        None => Client::new(args.proxy.as_deref(), args.cacert.as_deref())?,
+    };
+    
+    if let Some(Command::WatchFeed { ref feed, ref lockfiles, interval, ref webhook, notify, ref state, once }) = args.command {
+        let Some(feed) = feed else {
+            eprintln!("错误：请通过 --feed 或配置文件中的 feed 指定风险清单");
+            std::process::exit(1);
+        };
+        let lockfiles = if lockfiles.is_empty() { &args.files } else { lockfiles };
+        return watch::run_watch_feed(&watch::WatchOptions {
+            feed,
+            lockfiles,
+            excludes: &args.exclude,
+            follow_symlinks: args.follow_symlinks,
+            interval: std::time::Duration::from_secs(interval),
+            webhook: webhook.as_deref(),
+            notify: notify.unwrap_or_default(),
+            state_file: state.as_deref(),
+            once,
+            match_mode: args.match_mode,
+            columns: args.batch_columns.as_ref(),
+            token: args.batch_token.as_deref(),
+            client: &client,
+            verbose: args.verbose,
+            dry_run: args.dry_run,
+        });
+    }
+    
+    if let Some(Command::Image { ref image, ref platform, ref registry_auth }) = args.command {
+        let (image, platform, registry_auth) = (image.clone(), platform.clone(), registry_auth.clone());
+        return image::run_image(&mut args, &client, &image, &platform, registry_auth.as_deref());
+    }
+    
+    if let Some(Command::OrgScan { ref org, provider, include_archived, include_forks, ref gitlab_api, ref gitlab_token }) = args.command {
+        let (org, gitlab_api, gitlab_token) = (org.clone(), gitlab_api.clone(), gitlab_token.clone());
+        let scan = org::OrgScan { org: &org, provider, include_archived, include_forks, gitlab_api: &gitlab_api, gitlab_token };
+        return org::run_org_scan(&mut args, &client, scan);
+    }
+    
+    if let Some(spec) = args.repo.clone() {
+        let github = GitHub::new(&args.github_api, args.github_token.clone(), &client);
+        return repo::run_repo(&mut args, &spec, &github, &client);
+    }
+    
+    if let Some(dir) = args.dir.clone() {
+        return dir::run_dir(&mut args, Path::new(&dir), &client);
+    }
+    
+    if args.files.len() > 1 || args.files.iter().any(|file| files::is_glob(file)) {
+        return files::run_files(&mut args, &client);
+    }
+    args.file = args.files[0].clone();
+    
+    if let Some(Command::History { ref package, ref version }) = args.command {
+        let exit_code = history::run_history(&args, &client, package.as_deref(), version.as_deref())?;
+        if exit_code != 0 {
+            std::process::exit(exit_code);
+        }
+        return Ok(());
+    }
+    
+    if archive::is_archive(&args.file) && !is_remote(&args.file) {
+        return archive::run_archive(&mut args, &client);
+    }
+    
+    let remote = is_remote(&args.file);
+    if remote {
+        reject_local_options(&args, "远程锁文件")?;
+    }
+    let content = if args.file == STDIN_LOCKFILE {
+        // 锁文件不落盘的管道场景，例如 git show HEAD:pnpm-lock.yaml | npm_package_check -f - lodash
+        if args.exposure {
+            anyhow::bail!("--exposure 需要读取锁文件的 git 历史，不能用于从标准输入读取的锁文件");
+        }
+        let mut content = String::new();
+        std::io::stdin()
+            .read_to_string(&mut content)
+            .context("无法从标准输入读取锁文件")?;
+        content
+    } else if remote {
+        // 不克隆仓库直接检查，例如 raw.githubusercontent.com 上的锁文件；遵循 --proxy 与 --cacert
+        if args.verbose {
+            println!("下载锁文件: {}", args.file);
+        }
+        http::get_text(&client, &args.file)?
+    } else {
+        let file_path = Path::new(&args.file);
+        if !file_path.exists() {
+            anyhow::bail!("{}", scan::missing_lockfile_message(file_path));
+        }
+        if file_path.is_dir() {
+            args.file = resolve_lockfile_in_dir(file_path, &args.exclude, args.follow_symlinks)?;
+            if args.verbose {
+                println!("使用锁文件: {}", args.file);
+            }
+        }
+        fs::read_to_string(&args.file).with_context(|| format!("无法读取文件 '{}'", args.file))?
+    };
+    // 从标准输入读取时按位于当前目录的锁文件处理（package.json、--base-dir），格式按内容判断；
+    // 远程锁文件按地址中的文件名判断格式
+    let file_path = Path::new(if remote { remote_file_name(&args.file) } else { &args.file });
+    
+    let format = args.lockfile_format.unwrap_or_else(|| LockfileFormat::detect(file_path, &content));
+    if args.verbose {
+        println!("锁文件格式: {}", format.name());
+    }
+    let mut lock_data = Lockfile::parse_as(&content, format)?;
+    if args.verbose {
+        println!("{}", version_line(&lock_data));
+    }
+    let lockfile_dir = lockfile_dir(file_path);
+    let context = match remote {
+        true => LockfileContext::default(),
+        false => apply_local_context(&args, &mut lock_data, format, &content, file_path)?,
+    };
+    
+    if let Some(Command::Health { years, github }) = args.command {
+        let registry = Registry::new(&args.registry, &client);
+        let github = github.then(|| GitHub::new(&args.github_api, args.github_token.clone(), &client));
+        return health::run_health(&lock_data, &registry, github.as_ref(), years, args.verbose);
+    }
+    
+    if let Some(Command::Redact { ref scope, ref salt, list, ref output }) = args.command {
+        let redactor = redact::Redactor::new(scope, salt.as_deref())?;
+        return redact::run_redact(&content, &lock_data, &redactor, list, output.as_deref());
+    }
+    
+    if let Some(Command::Extract { ref query, ref output }) = args.command {
+        return extract::run_extract(&content, format, query, output.as_deref());
+    }
+    
+    if let Some(Command::Sbom { ref sbom }) = args.command {
+        return sbom::run_sbom(&lock_data, sbom, args.verbose);
+    }
     
+    if let Some(Command::Consistency) = args.command {
+        return consistency::run_consistency(&lock_data, args.verbose);
+    }
+    
+    if let Some(Command::Align { suggest_align }) = args.command {
+        let registry = suggest_align.then(|| Registry::new(&args.registry, &client));
+        return align::run_align(&lock_data, &context, registry.as_ref(), args.verbose);
+    }
+    
+    if let Some(Command::DependsOn { ref query }) = args.command {
+        return depends::run_depends_on(&lock_data, &context, query, args.verbose);
+    }
+    
+    if let Some(Command::Why { ref query }) = args.command {
+        return why::run_why(&lock_data, &context, query, args.verbose);
+    }
+    
+    if let Some(Command::Export { format, ref output }) = args.command {
+        return export::run_export(&lock_data, &args.file, format, output.as_deref());
+    }
+    
+    if let Some(Command::WorkspaceGraph { format, ref output }) = args.command {
+        return graph::run_workspace_graph(&lock_data, &context.root, format, output.as_deref());
+    }
+    
+    if let Some(Command::Bundle { action: BundleAction::Create { ref output, ref feed, downloads, rekor } }) = args.command {
+        return bundle::run_create(&lock_data, &client, &bundle::CreateOptions {
+            lockfile: &args.file,
+            output,
+            feeds: feed,
+            feed_token: args.batch_token.as_deref(),
+            registry: &args.registry,
+            downloads_api: downloads.then_some(args.downloads_api.as_str()),
+            rekor_url: rekor.then_some(args.rekor_url.as_str()),
+            verbose: args.verbose,
+        });
+    }
+    
+    if let Some(Command::Stats { sort }) = args.command {
+        return stats::run_stats(&lock_data, sort);
+    }
+    
+    if let Some(Command::Mirrors { ref allow }) = args.command {
+        return mirrors::run_mirrors(&lock_data, allow);
+    }
+    
+    if let Some(Command::Identical) = args.command {
+        return identical::run_identical(&lock_data, args.verbose);
+    }
+    
+    if let Some(Command::Overrides) = args.command {
+        return overrides::run_overrides(&lock_data, &context, (!remote).then_some(lockfile_dir), args.verbose);
+    }
+    
+    if let Some(Command::NodeModules { ref dir }) = args.command {
+        if remote {
+            anyhow::bail!("node-modules 需要本地的安装目录，不能用于远程锁文件");
+        }
+        return node_modules::run_node_modules(&lock_data, lockfile_dir, dir.as_deref(), args.verbose);
+    }
+    
+    if let Some(Command::Reverify { ref report, ref output }) = args.command {
+        return reverify::run_reverify(&lock_data, &args.file, report, output.as_deref());
+    }
+    
+    let exit_code = run_check(&args, &client, &lock_data, &context)?;
+    if exit_code != 0 {
+        std::process::exit(exit_code);
+    }
+    Ok(())
+}
+
+/// 锁文件所在目录，相对路径的锁文件没有父目录时为当前目录
+// -v 输出的锁文件版本，数字与字符串写法的 lockfileVersion 在解析时已统一为字符串
+fn version_line(lock_data: &Lockfile) -> String {
+    format!("Lockfile 版本: {}", lock_data.lockfile_version)
+}
+
+fn lockfile_dir(file_path: &Path) -> &Path {
+    file_path.parent().filter(|dir| !dir.as_os_str().is_empty()).unwrap_or(Path::new("."))
+}
+
+/// 结合锁文件所在目录补全锁文件模型：yarn.lock 的 importer、--only-public/--only-private 与 --workspace 筛选、--base-dir，
+/// 返回该锁文件的上下文（根项目的 importer 路径与包名、workspace 包名）
+#[cfg_attr(not(feature = "yarn"), allow(unused_variables))]
+fn apply_local_context(
+    args: &Args,
+    lock_data: &mut Lockfile,
+    format: LockfileFormat,
+    content: &str,
+    file_path: &Path,
+) -> Result<LockfileContext> {
+    let lockfile_dir = lockfile_dir(file_path);
+    // yarn.lock 不记录直接依赖，根项目与 workspace 的 importer 由 package.json 生成
+    #[cfg(feature = "yarn")]
+    if format == LockfileFormat::Yarn {
+        lock_data.importers.extend(yarn::importers(content, lockfile_dir)?);
+    }
+    let visibility = match (args.only_public, args.only_private) {
+        (true, _) => Some(visibility::Visibility::Public),
+        (_, true) => Some(visibility::Visibility::Private),
+        _ => None,
+    };
+    if let Some(visibility) = visibility {
+        visibility::retain_importers(lock_data, lockfile_dir, visibility, args.verbose)?;
+    }
+    if !args.workspace.is_empty() {
+        members::retain_workspaces(lock_data, lockfile_dir, &args.workspace, args.verbose)?;
+    }
+    let mut root_path = ".".to_string();
+    if let Some(ref base_dir) = args.base_dir {
+        let prefix = importer_prefix(base_dir, file_path)?;
+        if !prefix.is_empty() {
+            lock_data.rebase_importers(&prefix);
+            root_path = prefix;
+        }
+    }
+    Ok(LockfileContext {
+        workspace_names: members::names(lock_data, lockfile_dir, &root_path),
+        root: root_path,
+        root_name: root_package_name(file_path),
+    })
+}
+
+/// --log-format json 时把进度事件写到标准错误
+fn progress(args: &Args, event: ProgressEvent) {
+    if args.log_format == LogFormat::Json
+        && let Ok(line) = serde_json::to_string(&event)
+    {
+        eprintln!("{}", line);
+    }
+}
+
+/// 单包检查或批量检查，返回退出码
+fn run_check(args: &Args, client: &Client, lock_data: &Lockfile, context: &LockfileContext) -> Result<i32> {
+    progress(args, ProgressEvent::FileStarted { file: args.file.clone() });
     if let Some(ref batch_file) = args.batch {
         // 批量检查模式
-        run_batch_check(&args, &lock_data, batch_file)?;
+        let batch_packages = load_batch_packages(args, client, batch_file)?;
+        run_batch_check(args, client, lock_data, context, batch_packages)
+    } else if let Some(ref sbom_file) = args.batch_sbom {
+        // 以 SBOM 中的组件为检查清单
+        run_batch_check(args, client, lock_data, context, sbom::batch_packages(sbom_file)?)
     } else {
         // 单包检查模式
         if let Some(ref package_name) = args.package {
-            run_single_check(&args, &lock_data, package_name)?;
+            run_single_check(args, client, lock_data, context, package_name)
         } else {
             eprintln!("错误：必须指定包名或使用批量模式(-b/--batch)");
             std::process::exit(1);
         }
     }
+}
+
+/// 在目录中查找唯一的锁文件
+fn resolve_lockfile_in_dir(dir: &Path, excludes: &[String], follow_symlinks: bool) -> Result<String> {
+    let lockfiles = scan::find_lockfiles(dir, excludes, follow_symlinks)?;
+    match lockfiles.as_slice() {
+        [] => {
+            eprintln!("错误：目录 '{}' 中没有找到锁文件（{}）", dir.display(), scan::LOCKFILE_NAMES.join(", "));
+            std::process::exit(1);
+        }
+        [lockfile] => Ok(lockfile.display().to_string()),
+        _ => {
+            eprintln!("错误：目录 '{}' 中找到多个锁文件，请通过 -f 指定其中一个或使用 --exclude 排除：", dir.display());
+            for lockfile in &lockfiles {
+                eprintln!("   - {}", lockfile.display());
+            }
+            std::process::exit(1);
+        }
+    }
+}
+
+fn apply_config(args: &mut Args, matches: &ArgMatches, config: Config) {
+    // 仅在命令行未显式指定时使用配置文件中的值
+    let from_cli = |id: &str| matches.value_source(id) == Some(ValueSource::CommandLine);
     
-    Ok(())
+    if let Some(file) = config.file
+        && !from_cli("file")
+    {
+        args.files = vec![file];
+    }
+    if args.lockfile_format.is_none() {
+        args.lockfile_format = config.lockfile_format;
+    }
+    if let Some(mode) = config.verdict_mode
+        && !from_cli("verdict_mode")
+    {
+        args.verdict_mode = mode;
+    }
+    if let Some(mode) = config.match_mode
+        && !from_cli("match_mode")
+    {
+        args.match_mode = mode;
+    }
+    if args.batch.is_none() && args.package.is_none() {
+        args.batch = config.batch;
+    }
+    args.batch_columns = config.batch_columns;
+    // 配置文件中的报告路径只用于批量模式，避免单包查询覆盖批量报告
+    if args.output.is_none() && (args.batch.is_some() || args.batch_sbom.is_some()) {
+        args.output = config.output;
+    }
+    if args.policy.is_none() {
+        args.policy = config.policy;
+    }
+    if args.usage_stats.is_none() {
+        args.usage_stats = config.usage_stats;
+    }
+    if args.webhook.is_none() {
+        args.webhook = config.webhook.clone();
+    }
+    if args.notify.is_none() {
+        args.notify = config.notify;
+    }
+    if args.notify_state.is_none() {
+        args.notify_state = config.state.clone();
+    }
+    if args.sort_locale.is_none() {
+        args.sort_locale = config.sort_locale;
+    }
+    if let Some(Command::Mirrors { ref mut allow }) = args.command
+        && allow.is_empty()
+    {
+        *allow = config.allowed_mirrors;
+    }
+    if let Some(Command::WatchFeed { ref mut feed, ref mut lockfiles, ref mut webhook, ref mut notify, ref mut state, .. }) =
+        args.command
+    {
+        if feed.is_none() {
+            *feed = config.feed;
+        }
+        if lockfiles.is_empty() {
+            *lockfiles = config.lockfiles;
+        }
+        if webhook.is_none() {
+            *webhook = config.webhook;
+        }
+        if notify.is_none() {
+            *notify = config.notify;
+        }
+        if state.is_none() {
+            *state = config.state;
+        }
+    }
 }
 
-fn run_single_check(args: &Args, lock_data: &PnpmLock, package_name: &str) -> Result<()> {
+fn run_single_check(args: &Args, client: &Client, lock_data: &Lockfile, context: &LockfileContext, package_name: &str) -> Result<i32> {
+    if args.format != ReportFormat::Text {
+        eprintln!("错误：单包检查模式仅支持 --format text，其他格式请使用批量模式(-b/--batch)");
+        std::process::exit(1);
+    }
+    
+    if let Some(ref version) = args.version {
+        matching::validate(version, args.match_mode)?;
+    }
+    
     if args.verbose {
-        println!("Lockfile 版本: {}", lock_data.lockfile_version);
         println!("正在查找包: {}", package_name);
         if let Some(ref version) = args.version {
             println!("指定版本: {}", version);
@@ -180,18 +1375,22 @@ fn run_single_check(args: &Args, lock_data: &PnpmLock, package_name: &str) -> Re
         println!("---");
     }
     
-    let found_packages = find_package_in_lock(lock_data, package_name);
+    let mut found_packages = find_package_in_lock(&lock_data.index(), package_name);
+    annotate_found(lock_data, context, package_name, &mut found_packages);
+    let page = Page { offset: args.offset, limit: args.limit };
+    let mut dist_tags = None;
+    let mut weekly_downloads = None;
+    let mut unavailable = Vec::new();
     
     // 输出结果
     if found_packages.is_empty() {
         println!("❌ 未找到包: {}", package_name);
-        std::process::exit(1);
     } else {
         // 如果指定了版本，过滤结果
         if let Some(ref target_version) = args.version {
             let matched: Vec<_> = found_packages
                 .iter()
-                .filter(|p| version_matches(&p.version, target_version))
+                .filter(|p| version_matches(&p.version, target_version, args.match_mode))
                 .collect();
             
             if matched.is_empty() {
@@ -199,283 +1398,798 @@ fn run_single_check(args: &Args, lock_data: &PnpmLock, package_name: &str) -> Re
                 println!("   期望版本: {}", target_version);
                 println!("   实际版本:");
                 for pkg in &found_packages {
-                    println!("   - {} ({})", pkg.version, pkg.location);
+                    println!("   - {} ({})", pkg.version, pkg.place());
                 }
-                std::process::exit(1);
             } else {
                 println!("✅ 找到包: {} @ {}", package_name, target_version);
-                for pkg in matched {
+                for pkg in page.slice(&matched) {
                     print_package_info(pkg, args.verbose);
                 }
+                page.print_omitted(matched.len());
+                if args.dist_tags {
+                    dist_tags = print_dist_tags(args, client, package_name, std::slice::from_ref(target_version));
+                }
+            }
+        } else {
+            println!("✅ 找到包: {}", package_name);
+            for pkg in page.slice(&found_packages) {
+                print_package_info(pkg, args.verbose);
+            }
+            page.print_omitted(found_packages.len());
+            if args.dist_tags {
+                let versions: Vec<_> = found_packages.iter().map(|p| p.version.clone()).collect();
+                dist_tags = print_dist_tags(args, client, package_name, &versions);
+            }
+        }
+        
+        if args.check_unpublished {
+            unavailable = print_unpublished(args, client, package_name, &distinct_versions(&found_packages));
+        }
+        
+        if args.downloads {
+            match DownloadsApi::new(&args.downloads_api, client).weekly_downloads(package_name) {
+                Ok(count) => {
+                    println!("   📈 下载量: {}", registry::describe_downloads(count));
+                    weekly_downloads = Some(count);
+                }
+                Err(e) => eprintln!("⚠️ 无法获取 {} 的下载量: {:#}", package_name, e),
+            }
+        }
+    }
+    
+    let package = BatchPackage {
+        name: package_name.to_string(),
+        versions: args.version.iter().cloned().collect(),
+        status: None,
+        detection_date: None,
+        fixed_in: Vec::new(),
+        advisories: Vec::new(),
+        match_mode: args.match_mode,
+    };
+    let mut status = evaluate_status(&found_packages, &package, VerdictMode::Any);
+    // 与批量模式相同：命中的结果保持原状态，只标注不可用的版本，其他结果判定为已撤下
+    if !unavailable.is_empty() && !status.is_hit() {
+        status = CheckStatus::Unpublished;
+    }
+    let exit_code = if status == CheckStatus::Found { 0 } else { 1 };
+    let result = BatchResult {
+        package,
+        found_versions: found_packages,
+        status,
+        exposure: Vec::new(),
+        severity: None,
+        dist_tags,
+        unavailable,
+        weekly_downloads,
+        merged_versions: None,
+        rekor_entries: BTreeMap::new(),
+        risk_score: 0,
+        suppressed: None,
+    };
+    let results = std::slice::from_ref(&result);
+    
+    // 与批量模式相同的 TSV 报告，便于脚本处理
+    if let Some(ref output_file) = args.output {
+        write_batch_report(results, output_file)?;
+        println!("📄 报告已保存到: {}", output_file);
+    }
+    
+    finish(args, client, results, exit_code)
+}
+
+/// 输出结论行，通知 --webhook，写出 --result-file 与使用统计，返回退出码
+fn finish(args: &Args, client: &Client, results: &[BatchResult], exit_code: i32) -> Result<i32> {
+    if let Some(ref url) = args.webhook {
+        notify_findings(args, client, url, results);
+    }
+    // 结论行是标准错误的最后一行，--dry-run 的说明在它之前输出
+    if args.dry_run && let Some(ref path) = args.usage_stats {
+        eprintln!("🔍 [dry-run] 不追加使用统计: {}", path);
+    }
+    let findings = results.iter().filter(|result| result.status.is_hit()).count();
+    progress(args, ProgressEvent::FileFinished { file: args.file.clone(), findings, exit_code });
+    eprintln!("{}", result_line(results, exit_code));
+    if let Some(ref path) = args.result_file {
+        report::Verdict::from_results(results, &args.file, args.output.as_deref(), exit_code).write(path)?;
+    }
+    if let Some(ref reports) = args.reports {
+        reports.lock().unwrap().push(report::JsonReport::from_results(results, &args.file, args.timezone));
+    }
+    if let Some(ref path) = args.usage_stats
+        && !args.dry_run
+    {
+        let mode = if args.batch.is_some() || args.batch_sbom.is_some() { "batch" } else { "single" };
+        usage::UsageRecord::from_results(results, mode, &args.file, exit_code, args.timezone).append(path)?;
+    }
+    Ok(exit_code)
+}
+
+/// 通知未抑制的命中；通知失败不影响检查结论，给出警告，未送达的命中下次检查时重新通知
+fn notify_findings(args: &Args, client: &Client, url: &str, results: &[BatchResult]) {
+    let findings: BTreeSet<String> = results
+        .iter()
+        .filter(|result| result.status.is_hit() && result.suppressed.is_none())
+        .map(|result| format!("{}: {}@{}", args.file, result.package.name, matched_versions(result).join(", ")))
+        .collect();
+    let webhook = watch::Webhook { url, notify: args.notify.unwrap_or_default(), client, dry_run: args.dry_run };
+    if let Err(e) = watch::notify_findings(&webhook, args.notify_state.as_deref(), &args.file, &findings) {
+        eprintln!("⚠️ {:#}", e);
+    }
+}
+
+/// 写到标准错误的最后一行结论，格式固定、不随 --format 与输出语言变化，供脚本直接匹配：
+/// RESULT total=120 found=3 mismatch=1 notfound=116 partial=0 mixed=0 unpublished=0 exit=0
+fn result_line(results: &[BatchResult], exit_code: i32) -> String {
+    let count = |status: CheckStatus| results.iter().filter(|r| r.status == status).count();
+    format!(
+        "RESULT total={} found={} mismatch={} notfound={} partial={} mixed={} unpublished={} exit={}",
+        results.len(),
+        count(CheckStatus::Found),
+        count(CheckStatus::VersionMismatch),
+        count(CheckStatus::NotFound),
+        count(CheckStatus::PartialMatch),
+        count(CheckStatus::MixedFixPresent),
+        count(CheckStatus::Unpublished),
+        exit_code
+    )
+}
+
+fn print_dist_tags(args: &Args, client: &Client, package_name: &str, versions: &[String]) -> Option<BTreeMap<String, String>> {
+    let registry = Registry::new(&args.registry, client);
+    match registry.dist_tags(package_name) {
+        Ok(tags) => {
+            println!("   🏷️ dist-tags: {}", format_dist_tags(&tags));
+            let exposed = exposed_tags(&tags, versions, args.match_mode);
+            if !exposed.is_empty() {
+                println!("   ⚠️ 以下 tag 仍指向命中版本，新安装仍会受影响: {}", exposed.join(", "));
+            }
+            Some(tags)
+        }
+        Err(e) => {
+            eprintln!("⚠️ 无法获取 {} 的 dist-tags: {:#}", package_name, e);
+            None
+        }
+    }
+}
+
+/// 输出已撤下或被安全接管的版本并返回这些版本，查询失败时只提示
+fn print_unpublished(args: &Args, client: &Client, package_name: &str, versions: &[String]) -> Vec<(String, Availability)> {
+    let registry = Registry::new(&args.registry, client);
+    match registry.availability(package_name, versions) {
+        Ok(states) => {
+            let unavailable: Vec<_> = states.into_iter().filter(|(_, a)| *a != Availability::Published).collect();
+            for (version, availability) in &unavailable {
+                println!("   🚫 {} @ {}: {}", package_name, version, availability.describe());
+            }
+            unavailable
+        }
+        Err(e) => {
+            eprintln!("⚠️ 无法查询 {} 的发布状态: {:#}", package_name, e);
+            Vec::new()
+        }
+    }
+}
+
+/// 例如 "1.0.0: 2 条记录, 1.0.1: 无记录"
+fn format_rekor_entries(entries: &BTreeMap<String, usize>) -> String {
+    entries
+        .iter()
+        .map(|(version, count)| match count {
+            0 => format!("{}: 无记录", version),
+            n => format!("{}: {} 条记录", version, n),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn format_dist_tags(tags: &BTreeMap<String, String>) -> String {
+    tags.iter()
+        .map(|(tag, version)| format!("{}={}", tag, version))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+// 返回仍指向给定版本之一的 tag 名称
+fn exposed_tags(tags: &BTreeMap<String, String>, versions: &[String], match_mode: MatchMode) -> Vec<String> {
+    tags.iter()
+        .filter(|(_, tagged)| versions.iter().any(|v| version_matches(tagged, v, match_mode)))
+        .map(|(tag, _)| tag.clone())
+        .collect()
+}
+
+/// 读取批量文件并校验期望版本
+fn load_batch_packages(args: &Args, client: &Client, batch_file: &str) -> Result<Vec<BatchPackage>> {
+    // 批量文件可以是远程风险清单的地址
+    let mut batch_packages = if is_remote(batch_file) {
+        let content = http::get_text_with(client, batch_file, &http::bearer(args.batch_token.as_deref()))?;
+        parse_batch_content(&content, args.batch_columns.as_ref(), args.match_mode)?
+    } else {
+        parse_batch_file(batch_file, args.batch_columns.as_ref(), args.match_mode)?
+    };
+    // 上游清单的 Detection Date 格式不一，统一为 YYYY-MM-DD
+    for package in &mut batch_packages {
+        if let Some(ref mut date) = package.detection_date {
+            *date = dates::normalize_date(date, args.timezone);
+        }
+    }
+    for package in &batch_packages {
+        for version in &package.versions {
+            matching::validate(version, package.match_mode)
+                .with_context(|| format!("批量文件中 {} 的期望版本有误", package.name))?;
+        }
+    }
+    Ok(batch_packages)
+}
+
+fn run_batch_check(
+    args: &Args,
+    client: &Client,
+    lock_data: &Lockfile,
+    context: &LockfileContext,
+    batch_packages: Vec<BatchPackage>,
+) -> Result<i32> {
+    let policy = args.policy.as_deref().map(Policy::load).transpose()?;
+    
+    if args.verbose {
+        println!("批量检查模式: {} 个包", batch_packages.len());
+        println!("---");
+    }
+    
+    let mut results = Vec::new();
+    
+    // 同一个包在清单中多次出现（不同事件）时只查找一次，
+    // 并合并各条目的期望版本统一判定，避免报告中出现互相矛盾的结论
+    let merged = merge_expected_versions(&batch_packages);
+    let index = lock_data.index();
+    let mut lookups: HashMap<&str, Vec<PackageFound>> = HashMap::new();
+    // 进度事件大约每完成 1% 的条目发送一次
+    let step = (batch_packages.len() / 100).max(1);
+    let mut findings = 0;
+    
+    for (i, package) in batch_packages.iter().enumerate() {
+        let found_packages = lookups
+            .entry(&package.name)
+            .or_insert_with(|| {
+                let mut found = find_package_in_lock(&index, &package.name);
+                annotate_found(lock_data, context, &package.name, &mut found);
+                found
+            })
+            .clone();
+        
+        let merged_package = merged_package(package, &merged);
+        let merged_versions = merged_package.as_ref().map(|merged| merged.versions.clone());
+        let status = evaluate_status(&found_packages, merged_package.as_ref().unwrap_or(package), args.verdict_mode);
+        
+        findings += usize::from(status.is_hit());
+        results.push(BatchResult {
+            package: package.clone(),
+            found_versions: found_packages,
+            status,
+            exposure: Vec::new(),
+            severity: None,
+            dist_tags: None,
+            unavailable: Vec::new(),
+            weekly_downloads: None,
+            merged_versions,
+            rekor_entries: BTreeMap::new(),
+            risk_score: 0,
+            suppressed: None,
+        });
+        
+        let checked = i + 1;
+        if checked % step == 0 || checked == batch_packages.len() {
+            progress(args, ProgressEvent::EntriesChecked {
+                file: args.file.clone(),
+                checked,
+                total: batch_packages.len(),
+                findings,
+            });
+        }
+    }
+    
+    // 抑制文件与锁文件放在同一目录，只对本地锁文件读取，避免当前目录中的抑制文件作用于标准输入或远程的锁文件
+    let suppressions = match is_local_file(&args.file) {
+        true => suppress::Suppressions::load(Path::new(&args.file))?,
+        false => None,
+    };
+    if let Some(suppressions) = suppressions {
+        if args.verbose {
+            println!("抑制文件: {}", suppressions.path.display());
+        }
+        let today = chrono::Local::now().date_naive();
+        for result in &mut results {
+            if !result.status.is_hit() {
+                continue;
+            }
+            let Some(suppression) = suppressions.get(&result_fingerprint(result)) else {
+                continue;
+            };
+            if suppression.expires >= today {
+                result.suppressed = Some(suppression.clone());
+            } else {
+                eprintln!(
+                    "⏰ {} 的抑制已于 {} 到期，重新报告（{}）",
+                    result.package.name, suppression.expires, suppression.fingerprint
+                );
+            }
+        }
+    }
+    
+    if let Some(ref policy) = policy {
+        for result in &mut results {
+            if result.status.is_hit() && result.suppressed.is_none() {
+                result.severity = Some(policy.severity_for(result.package.status.as_deref()));
+            }
+        }
+    }
+    
+    let reach = risk::Reach::new(lock_data);
+    for result in &mut results {
+        if result.suppressed.is_none() {
+            result.risk_score = risk::score(result, &reach);
+        }
+    }
+    
+    if args.dist_tags {
+        let registry = Registry::new(&args.registry, client);
+        let mut cache: HashMap<String, Option<BTreeMap<String, String>>> = HashMap::new();
+        for result in &mut results {
+            if !result.status.is_hit() {
+                continue;
+            }
+            let name = &result.package.name;
+            result.dist_tags = cache
+                .entry(name.clone())
+                .or_insert_with(|| match registry.dist_tags(name) {
+                    Ok(tags) => Some(tags),
+                    Err(e) => {
+                        eprintln!("⚠️ 无法获取 {} 的 dist-tags: {:#}", name, e);
+                        None
+                    }
+                })
+                .clone();
+        }
+    }
+    
+    if args.downloads {
+        let api = DownloadsApi::new(&args.downloads_api, client);
+        let mut cache: HashMap<String, Option<u64>> = HashMap::new();
+        for result in &mut results {
+            if !result.status.is_hit() {
+                continue;
+            }
+            let name = &result.package.name;
+            result.weekly_downloads = *cache.entry(name.clone()).or_insert_with(|| match api.weekly_downloads(name) {
+                Ok(count) => Some(count),
+                Err(e) => {
+                    eprintln!("⚠️ 无法获取 {} 的下载量: {:#}", name, e);
+                    None
+                }
+            });
+        }
+    }
+    
+    if args.rekor {
+        let rekor = Rekor::new(&args.rekor_url, client);
+        for result in &mut results {
+            if !result.status.is_hit() {
+                continue;
+            }
+            let name = &result.package.name;
+            for version in matched_versions(result) {
+                let Some(integrity) = lock_data
+                    .resolution_of(name, &version)
+                    .and_then(|r| r.integrity().map(str::to_string))
+                else {
+                    eprintln!("⚠️ {}@{} 在锁文件中没有 integrity，无法查询 Rekor", name, version);
+                    continue;
+                };
+                match rekor.entries_for(&integrity) {
+                    Ok(count) => {
+                        result.rekor_entries.insert(version, count);
+                    }
+                    Err(e) => eprintln!("⚠️ 无法在 Rekor 中查询 {}@{}: {:#}", name, version, e),
+                }
+            }
+        }
+    }
+    
+    if args.exposure {
+        let history = LockHistory::load(Path::new(&args.file), args.timezone)?;
+        if args.verbose {
+            println!("锁文件历史版本数: {}", history.revision_count());
+        }
+        for result in &mut results {
+            if !result.status.is_hit() {
+                continue;
+            }
+            // 未指定期望版本时，计算当前实际出现的各个版本
+            let (versions, match_mode) = if result.package.versions.is_empty() {
+                (distinct_versions(&result.found_versions), MatchMode::Exact)
+            } else {
+                (result.package.versions.clone(), result.package.match_mode)
+            };
+            result.exposure = history.windows(&result.package.name, &versions, match_mode);
+        }
+    }
+    
+    if args.check_unpublished {
+        let registry = Registry::new(&args.registry, client);
+        let mut cache: HashMap<String, Option<Vec<(String, Availability)>>> = HashMap::new();
+        for result in &mut results {
+            if result.status == CheckStatus::NotFound {
+                continue;
+            }
+            let name = &result.package.name;
+            let versions = distinct_versions(&result.found_versions);
+            let states = cache.entry(name.clone()).or_insert_with(|| match registry.availability(name, &versions) {
+                Ok(states) => Some(states),
+                Err(e) => {
+                    eprintln!("⚠️ 无法查询 {} 的发布状态: {:#}", name, e);
+                    None
+                }
+            });
+            if let Some(states) = states {
+                result.unavailable = states
+                    .iter()
+                    .filter(|(_, a)| *a != Availability::Published)
+                    .cloned()
+                    .collect();
+                // 命中受影响版本的结果保持原状态（仍是需要处置的发现项，保留指纹与统计），只标注不可用的版本；
+                // 其他结果（锁定的版本均不在期望列表中）才判定为已撤下
+                if !result.unavailable.is_empty() && !result.status.is_hit() {
+                    result.status = CheckStatus::Unpublished;
+                }
+            }
+        }
+    }
+    
+    // --batch-sbom：SBOM 中的组件之外，锁文件中多出的组件与版本同样是差异
+    let unlisted = match args.batch_sbom {
+        Some(_) => sbom::unlisted(lock_data, &batch_packages),
+        None => Vec::new(),
+    };
+    
+    match args.format {
+        ReportFormat::Text => {
+            // 输出批量检查结果
+            print_batch_results(&results, args.verbose, args.explain, Page { offset: args.offset, limit: args.limit });
+            sbom::print_unlisted(&unlisted);
+            
+            // 如果指定了输出文件，写入报告
+            if let Some(output_file) = &args.output {
+                write_batch_report(&results, output_file)?;
+                println!("\n📊 报告已写入: {}", output_file);
             }
-        } else {
-            println!("✅ 找到包: {}", package_name);
-            for pkg in &found_packages {
-                print_package_info(pkg, args.verbose);
+        }
+        ReportFormat::Json => {
+            report::JsonReport::from_results(&results, &args.file, args.timezone).write(args.output.as_deref())?;
+            if !unlisted.is_empty() {
+                eprintln!("➕ 锁文件中有 {} 个组件版本未在 SBOM 中列出: {}", unlisted.len(), unlisted.join(", "));
+            }
+            if let Some(output) = &args.output {
+                eprintln!("📊 报告已写入: {}", output);
+            }
+        }
+        ReportFormat::Osv => {
+            let advisories = osv::build_advisories(&results, &args.osv_prefix);
+            osv::write_advisories(&advisories, args.output.as_deref())?;
+            if let Some(output) = &args.output {
+                eprintln!("📊 已导出 {} 条 OSV 公告: {}", advisories.len(), output);
             }
         }
     }
     
-    Ok(())
+    // 指定策略文件时，存在 fail 级别的命中则以非零状态退出；
+    // --batch-sbom 时 SBOM 与锁文件存在差异（组件缺失、版本不一致或锁文件中有未列出的组件）同样以非零状态退出
+    let sbom_drift = args.batch_sbom.is_some()
+        && (!unlisted.is_empty() || results.iter().any(|r| r.status != CheckStatus::Found));
+    let failed = results.iter().any(|r| r.severity == Some(Severity::Fail));
+    let exit_code = if failed || sbom_drift { 1 } else { 0 };
+    finish(args, client, &results, exit_code)
 }
 
-fn run_batch_check(args: &Args, lock_data: &PnpmLock, batch_file: &str) -> Result<()> {
-    let batch_packages = parse_batch_file(batch_file)?;
-    
-    if args.verbose {
-        println!("Lockfile 版本: {}", lock_data.lockfile_version);
-        println!("批量检查模式: {} 个包", batch_packages.len());
-        println!("---");
+/// 清单中出现多次的包名 → 合并后的期望版本；任一条目未指定版本时合并结果为任意版本
+fn merge_expected_versions(packages: &[BatchPackage]) -> HashMap<&str, Vec<String>> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for package in packages {
+        *counts.entry(&package.name).or_default() += 1;
     }
     
-    let mut results = Vec::new();
-    
-    for package in &batch_packages {
-        let found_packages = find_package_in_lock(lock_data, &package.name);
-        
-        let status = if found_packages.is_empty() {
-            CheckStatus::NotFound
-        } else if package.versions.is_empty() {
-            CheckStatus::Found
-        } else {
-            let matched_versions: Vec<_> = found_packages
-                .iter()
-                .filter(|p| package.versions.iter().any(|v| version_matches(&p.version, v)))
-                .collect();
-            
-            if matched_versions.is_empty() {
-                CheckStatus::VersionMismatch
-            } else if matched_versions.len() == package.versions.len() {
-                CheckStatus::Found
-            } else {
-                CheckStatus::PartialMatch
+    let mut merged: HashMap<&str, Vec<String>> = HashMap::new();
+    let mut any_version: BTreeSet<&str> = BTreeSet::new();
+    for package in packages.iter().filter(|p| counts[p.name.as_str()] > 1) {
+        if package.versions.is_empty() {
+            any_version.insert(&package.name);
+        }
+        let versions = merged.entry(&package.name).or_default();
+        for version in &package.versions {
+            if !versions.contains(version) {
+                versions.push(version.clone());
             }
-        };
-        
-        results.push(BatchResult {
-            package: package.clone(),
-            found_versions: found_packages,
-            status,
-        });
+        }
     }
-    
-    // 输出批量检查结果
-    print_batch_results(&results, args.verbose);
-    
-    // 如果指定了输出文件，写入报告
-    if let Some(output_file) = &args.output {
-        write_batch_report(&results, output_file)?;
-        println!("\n📊 报告已写入: {}", output_file);
+    for name in any_version {
+        merged.insert(name, Vec::new());
     }
     
-    Ok(())
+    merged
+}
+
+/// 期望版本被合并时用于判定的条目：版本为合并后的版本，状态与其他信息保留该条目自身的
+fn merged_package(package: &BatchPackage, merged: &HashMap<&str, Vec<String>>) -> Option<BatchPackage> {
+    merged
+        .get(package.name.as_str())
+        .filter(|versions| **versions != package.versions)
+        .map(|versions| BatchPackage { versions: versions.clone(), ..package.clone() })
+}
+
+/// 命中期望版本的实际版本（未指定期望版本时为全部实际版本）
+fn matched_versions(result: &BatchResult) -> Vec<String> {
+    matched_in(&result.found_versions, &result.package)
+}
+
+fn matched_in(found_packages: &[PackageFound], package: &BatchPackage) -> Vec<String> {
+    let mut versions: Vec<String> = found_packages
+        .iter()
+        .filter(|p| {
+            package.versions.is_empty()
+                || package.versions.iter().any(|v| version_matches(&p.version, v, package.match_mode))
+        })
+        .map(|p| p.version.clone())
+        .collect();
+    versions.sort();
+    versions.dedup();
+    versions
+}
+
+/// 命中的指纹，用于在抑制文件中逐条抑制
+fn result_fingerprint(result: &BatchResult) -> String {
+    suppress::fingerprint(&result.package.name, &matched_versions(result))
+}
+
+fn distinct_versions(found_packages: &[PackageFound]) -> Vec<String> {
+    let mut versions: Vec<_> = found_packages.iter().map(|p| p.version.clone()).collect();
+    versions.sort();
+    versions.dedup();
+    versions
+}
+
+fn evaluate_status(found_packages: &[PackageFound], package: &BatchPackage, mode: VerdictMode) -> CheckStatus {
+    let status = evaluate_expected(found_packages, package, mode);
+    if status.is_hit() && !fixed_versions_present(found_packages, package).is_empty() {
+        return CheckStatus::MixedFixPresent;
+    }
+    status
 }
 
-fn find_package_in_lock(lock_data: &PnpmLock, package_name: &str) -> Vec<PackageFound> {
-    let mut found_packages = Vec::new();
+/// 锁文件中已有的修复版本：不是受影响版本，且满足 Fixed In 列中的任一修复版本
+fn fixed_versions_present(found_packages: &[PackageFound], package: &BatchPackage) -> Vec<String> {
+    let mut versions: Vec<String> = found_packages
+        .iter()
+        .filter(|p| !package.versions.iter().any(|v| version_matches(&p.version, v, package.match_mode)))
+        .filter(|p| package.fixed_in.iter().any(|fixed| matching::is_fixed(&p.version, fixed)))
+        .map(|p| p.version.clone())
+        .collect();
+    versions.sort();
+    versions.dedup();
+    versions
+}
+
+fn evaluate_expected(found_packages: &[PackageFound], package: &BatchPackage, mode: VerdictMode) -> CheckStatus {
+    let expected = &package.versions;
+    if found_packages.is_empty() {
+        return CheckStatus::NotFound;
+    }
+    if expected.is_empty() {
+        return CheckStatus::Found;
+    }
     
-    // 在 importers 中查找
-    for (importer_path, importer) in &lock_data.importers {
-        let display_path = if importer_path == "." {
-            "根目录".to_string()
-        } else {
-            importer_path.clone()
-        };
-        
-        // 检查 dependencies
-        if let Some(dep_info) = importer.dependencies.get(package_name) {
-            found_packages.push(PackageFound {
-                location: display_path.clone(),
-                specifier: dep_info.specifier.clone(),
-                version: extract_version(&dep_info.version),
-                dependency_type: "dependencies".to_string(),
-            });
-        }
-        
-        // 检查 devDependencies
-        if let Some(dep_info) = importer.dev_dependencies.get(package_name) {
-            found_packages.push(PackageFound {
-                location: display_path.clone(),
-                specifier: dep_info.specifier.clone(),
-                version: extract_version(&dep_info.version),
-                dependency_type: "devDependencies".to_string(),
-            });
+    // 按期望版本统计命中情况，而不是按找到的条目数量统计，
+    // 避免同一版本出现在多个节点或锁文件中存在额外版本时误判
+    let matched_expected = expected
+        .iter()
+        .filter(|v| found_packages.iter().any(|p| version_matches(&p.version, v, package.match_mode)))
+        .count();
+    
+    if matched_expected == 0 {
+        return CheckStatus::VersionMismatch;
+    }
+    
+    match mode {
+        VerdictMode::Any => CheckStatus::Found,
+        VerdictMode::All => {
+            if matched_expected == expected.len() {
+                CheckStatus::Found
+            } else {
+                CheckStatus::PartialMatch
+            }
         }
-        
-        // 检查 optionalDependencies
-        if let Some(dep_info) = importer.optional_dependencies.get(package_name) {
-            found_packages.push(PackageFound {
-                location: display_path,
-                specifier: dep_info.specifier.clone(),
-                version: extract_version(&dep_info.version),
-                dependency_type: "optionalDependencies".to_string(),
-            });
+        VerdictMode::ExactSet => {
+            let has_extra = found_packages
+                .iter()
+                .any(|p| !expected.iter().any(|v| version_matches(&p.version, v, package.match_mode)));
+            if matched_expected == expected.len() && !has_extra {
+                CheckStatus::Found
+            } else {
+                CheckStatus::PartialMatch
+            }
         }
     }
+}
+
+fn find_package_in_lock(index: &PackageIndex, package_name: &str) -> Vec<PackageFound> {
+    let mut found_packages: Vec<PackageFound> = Vec::new();
     
-    // 在 packages 中查找
-    let package_patterns = vec![
-        format!("{}@", package_name),
-        format!("/{}@", package_name),
-    ];
-    
-    for (package_key, _package_info) in &lock_data.packages {
-        for pattern in &package_patterns {
-            if package_key.contains(pattern) {
-                let version = extract_version_from_key(package_key, package_name);
-                if !found_packages.iter().any(|p| p.version == version) {
+    for record in index.get(package_name) {
+        match (record.section, record.dep_kind) {
+            // 在 importers 中查找
+            (Section::Importers, Some(kind)) => {
+                found_packages.push(PackageFound {
+                    location: Location::Importer(record.importer.unwrap_or(".").to_string()),
+                    specifier: record.specifier.unwrap_or_default().to_string(),
+                    version: record.version.clone(),
+                    dependency_type: kind.field_name().to_string(),
+                    overridden: None,
+                    patched: None,
+                    install_scripts: None,
+                    resolution: record.resolution.clone(),
+                    alias: record.alias.map(|alias| (alias.to_string(), record.name.clone())),
+                    importer_label: None,
+                });
+            }
+            // 在 packages 中查找
+            (Section::Packages, None) => {
+                if !found_packages.iter().any(|p| p.version == record.version) {
                     found_packages.push(PackageFound {
-                        location: "packages节点".to_string(),
+                        location: Location::PackagesSection,
                         specifier: "".to_string(),
-                        version: version.clone(),
+                        version: record.version.clone(),
                         dependency_type: "packages".to_string(),
+                        overridden: None,
+                        patched: None,
+                        install_scripts: None,
+                        resolution: record.resolution.clone(),
+                        alias: record.alias.map(|alias| (alias.to_string(), record.name.clone())),
+                        importer_label: None,
                     });
                 }
             }
-        }
-    }
-    
-    // 在 snapshots 中查找
-    for (snapshot_key, snapshot_info) in &lock_data.snapshots {
-        let key_without_version = extract_package_name_from_snapshot_key(snapshot_key);
-        
-        // 检查 snapshot 的 dependencies
-        if let Some(dep_version) = snapshot_info.dependencies.get(package_name) {
-            let version = extract_version(dep_version);
-            if !found_packages.iter().any(|p| p.version == version && p.location == "snapshots节点") {
+            // packages 条目中声明的依赖（v5/v6）
+            (Section::Packages, Some(kind)) => {
+                if found_packages.iter().any(|p| p.version == record.version && p.location == Location::PackagesSection) {
+                    continue;
+                }
                 found_packages.push(PackageFound {
-                    location: "snapshots节点".to_string(),
+                    location: Location::PackagesSection,
                     specifier: "".to_string(),
-                    version: version.clone(),
-                    dependency_type: format!("snapshots[{}].dependencies", snapshot_key),
+                    version: record.version.clone(),
+                    dependency_type: format!("packages[{}].{}", record.parent.unwrap_or_default(), kind.field_name()),
+                    overridden: None,
+                    patched: None,
+                    install_scripts: None,
+                    resolution: record.resolution.clone(),
+                    alias: record.alias.map(|alias| (alias.to_string(), record.name.clone())),
+                    importer_label: None,
                 });
             }
-        }
-        
-        // 检查包名是否匹配 snapshot key 本身
-        if key_without_version == package_name || key_without_version.ends_with(&format!("/{}", package_name)) {
-            let version = extract_version_from_snapshot_key(snapshot_key);
-            if !version.is_empty() && !found_packages.iter().any(|p| p.version == version && p.location == "snapshots节点") {
+            // 在 snapshots 中查找：依赖边按实际安装的包名匹配（别名指向的目标包），其次是 snapshot key 本身
+            (Section::Snapshots, kind) => {
+                if found_packages.iter().any(|p| p.version == record.version && p.location == Location::SnapshotsSection) {
+                    continue;
+                }
+                let dependency_type = match (kind, record.parent) {
+                    (Some(kind), Some(parent)) => format!("snapshots[{}].{}", parent, kind.field_name()),
+                    _ => "snapshots".to_string(),
+                };
                 found_packages.push(PackageFound {
-                    location: "snapshots节点".to_string(),
+                    location: Location::SnapshotsSection,
                     specifier: "".to_string(),
-                    version,
-                    dependency_type: "snapshots".to_string(),
+                    version: record.version.clone(),
+                    dependency_type,
+                    overridden: None,
+                    patched: None,
+                    install_scripts: None,
+                    resolution: record.resolution.clone(),
+                    alias: record.alias.map(|alias| (alias.to_string(), record.name.clone())),
+                    importer_label: None,
                 });
             }
+            (Section::Importers, None) => {}
         }
     }
     
     found_packages
 }
 
-fn parse_batch_file(file_path: &str) -> Result<Vec<BatchPackage>> {
-    let content = fs::read_to_string(file_path)
-        .with_context(|| format!("无法读取批量文件 '{}'", file_path))?;
-    
-    let lines: Vec<&str> = content.lines().collect();
-    if lines.is_empty() {
-        return Ok(Vec::new());
-    }
-    
-    // 检测文件格式
-    let header = lines[0];
-    if header.contains("Package Name\tVersion(s)") {
-        // version1.txt 格式
-        parse_version1_format(&lines[1..])
-    } else if header.contains("Package Name\tCompromised Version(s)\tDetection Date\tStatus") {
-        // version2.txt 格式  
-        parse_version2_format(&lines[1..])
-    } else {
-        Err(anyhow::anyhow!("无法识别的文件格式：{}", header))
+/// 标注锁文件 overrides、patchedDependencies 与安装脚本设置对找到的包的影响
+fn annotate_found(lock_data: &Lockfile, context: &LockfileContext, package_name: &str, found_packages: &mut [PackageFound]) {
+    for pkg in found_packages {
+        if let Location::Importer(ref path) = pkg.location {
+            pkg.importer_label = Some(context.importer_label(path));
+        }
+        // 通过别名找到的记录按实际安装的包名查找覆盖、补丁与安装脚本
+        let package_name = pkg.alias.as_ref().map_or(package_name, |(_, name)| name.as_str());
+        let overridden = lock_data.override_for(package_name);
+        // parent>name 形式的覆盖只作用于 parent 的依赖，不影响 importer 的直接依赖
+        pkg.overridden = overridden
+            .filter(|(selector, _)| !(selector.contains('>') && pkg.location.is_importer()))
+            .map(|(selector, value)| format!("{} → {}", selector, value));
+        pkg.patched = lock_data.patch_for(package_name, &pkg.version).map(str::to_string);
+        pkg.install_scripts = Some(lock_data.install_scripts(package_name, &pkg.version));
+        if pkg.resolution.is_none() {
+            pkg.resolution = lock_data.resolution_for(package_name, &pkg.version);
+        }
     }
 }
 
-fn parse_version1_format(lines: &[&str]) -> Result<Vec<BatchPackage>> {
-    let mut packages = Vec::new();
+/// 锁文件中出现的全部包名（不含 workspace 内部链接）
+fn all_package_names(lock_data: &Lockfile) -> BTreeSet<String> {
+    let mut names = BTreeSet::new();
+    // git、本地目录等非 registry 来源的包不在 registry 上
+    let mut local = BTreeSet::new();
     
-    for line in lines {
-        if line.trim().is_empty() {
-            continue;
-        }
-        
-        let parts: Vec<&str> = line.split('\t').collect();
-        if parts.len() < 3 {
-            continue;
+    for record in lock_data.packages() {
+        match record.resolution {
+            Some(ref resolution) if !resolution.is_registry() => {
+                local.insert(record.name);
+            }
+            _ => {
+                names.insert(record.name);
+            }
         }
-        
-        let package_name = parts[1].trim().to_string();
-        let versions_str = parts[2].trim();
-        let versions: Vec<String> = if versions_str.is_empty() {
-            Vec::new()
-        } else {
-            versions_str.split(", ").map(|s| s.trim().to_string()).collect()
-        };
-        
-        packages.push(BatchPackage {
-            name: package_name,
-            versions,
-            status: None,
-            detection_date: None,
-        });
     }
+    names.retain(|name| !name.is_empty() && !local.contains(name));
     
-    Ok(packages)
+    names
 }
 
-fn parse_version2_format(lines: &[&str]) -> Result<Vec<BatchPackage>> {
-    let mut packages = Vec::new();
-    
-    for line in lines {
-        if line.trim().is_empty() {
-            continue;
+fn parse_batch_file(file_path: &str, columns: Option<&batch::Columns>, match_mode: MatchMode) -> Result<Vec<BatchPackage>> {
+    // 目录（例如 OSV 公告库的检出）中的全部 .json 文件逐一读取后合并
+    if Path::new(file_path).is_dir() {
+        let mut packages = Vec::new();
+        for path in scan::json_files(Path::new(file_path))? {
+            let content = fs::read_to_string(&path)
+                .with_context(|| format!("无法读取批量文件 '{}'", path.display()))?;
+            packages.extend(
+                parse_batch_content(&content, columns, match_mode)
+                    .with_context(|| format!("解析批量文件 '{}' 失败", path.display()))?,
+            );
         }
-        
-        let parts: Vec<&str> = line.split('\t').collect();
-        if parts.len() < 4 {
-            continue;
-        }
-        
-        let package_name = parts[0].trim().to_string();
-        let versions_str = parts[1].trim();
-        let detection_date = Some(parts[2].trim().to_string());
-        let status = Some(parts[3].trim().to_string());
-        
-        let versions: Vec<String> = if versions_str.is_empty() {
-            Vec::new()
-        } else {
-            versions_str.split(", ").map(|s| s.trim().to_string()).collect()
-        };
-        
-        packages.push(BatchPackage {
-            name: package_name,
-            versions,
-            status,
-            detection_date,
-        });
+        return Ok(packages);
     }
-    
-    Ok(packages)
+    let content = fs::read_to_string(file_path)
+        .with_context(|| format!("无法读取批量文件 '{}'", file_path))?;
+    parse_batch_content(&content, columns, match_mode)
 }
-fn extract_version(version_str: &str) -> String {
-    // 从版本字符串中提取纯版本号
-    // 例如: "4.8.3(react-dom@18.3.1)(react@18.3.1)" -> "4.8.3"
-    if let Some(pos) = version_str.find('(') {
-        version_str[..pos].to_string()
-    } else {
-        version_str.to_string()
-    }
+
+fn parse_batch_content(content: &str, columns: Option<&batch::Columns>, match_mode: MatchMode) -> Result<Vec<BatchPackage>> {
+    let entries = batch::parse_with_columns(content, columns)?;
+    Ok(entries
+        .into_iter()
+        .map(|entry| BatchPackage {
+            name: entry.name,
+            versions: entry.versions,
+            status: entry.status,
+            detection_date: entry.detection_date,
+            fixed_in: entry.fixed_in,
+            advisories: entry.advisories,
+            match_mode,
+        })
+        .collect())
 }
 
-fn print_batch_results(results: &[BatchResult], verbose: bool) {
+fn print_batch_results(results: &[BatchResult], verbose: bool, explain: bool, page: Page) {
     let mut found_count = 0;
     let mut not_found_count = 0;
     let mut version_mismatch_count = 0;
     let mut partial_match_count = 0;
+    let mut mixed_count = 0;
+    let mut unpublished_count = 0;
     
     println!("📊 批量检查结果:\n");
     
-    for result in results {
+    for (index, result) in results.iter().enumerate() {
         let status_icon = match result.status {
             CheckStatus::Found => {
                 found_count += 1;
@@ -493,11 +2207,39 @@ fn print_batch_results(results: &[BatchResult], verbose: bool) {
                 partial_match_count += 1;
                 "🟡"
             }
+            CheckStatus::MixedFixPresent => {
+                mixed_count += 1;
+                "🩹"
+            }
+            CheckStatus::Unpublished => {
+                unpublished_count += 1;
+                "🚫"
+            }
         };
         
-        println!("{} {}", status_icon, result.package.name);
+        // 统计信息覆盖全部结果，逐条输出只显示当前页
+        if !page.contains(index) {
+            continue;
+        }
+        
+        match result.severity {
+            Some(severity) => println!(
+                "{} {} [{} {}]",
+                status_icon,
+                result.package.name,
+                severity.icon(),
+                severity.label()
+            ),
+            None => println!("{} {}", status_icon, result.package.name),
+        }
+        
+        if let Some(ref suppression) = result.suppressed {
+            println!("   🔕 已抑制（{} 到期）: {}", suppression.expires, suppression.justification);
+        } else if verbose && result.status.is_hit() {
+            println!("   🔑 指纹: {}", result_fingerprint(result));
+        }
         
-        if verbose || result.status != CheckStatus::Found {
+        if verbose || result.status != CheckStatus::Found || !result.unavailable.is_empty() {
             println!("   预期版本: {}", 
                 if result.package.versions.is_empty() { 
                     "任意版本".to_string() 
@@ -505,13 +2247,41 @@ fn print_batch_results(results: &[BatchResult], verbose: bool) {
                     result.package.versions.join(", ") 
                 });
             
+            if let Some(ref versions) = result.merged_versions {
+                println!("   合并同名条目后的期望版本: {}",
+                    if versions.is_empty() { "任意版本".to_string() } else { versions.join(", ") });
+            }
+            
+            if !result.package.fixed_in.is_empty() {
+                println!("   修复版本: {}", result.package.fixed_in.join(", "));
+            }
+            
             if result.status != CheckStatus::NotFound {
                 println!("   实际版本:");
                 for pkg in &result.found_versions {
-                    println!("   - {} @ {} ({})", pkg.location, pkg.version, pkg.dependency_type);
+                    println!("   - {} @ {} ({}){}", pkg.place(), pkg.version, pkg.dependency_type, pkg.notes());
                 }
             }
             
+            if result.status == CheckStatus::MixedFixPresent {
+                println!(
+                    "   🩹 锁文件中已有修复版本 {}，将仍在使用受影响版本的位置升级后即可清理",
+                    fixed_versions_present(&result.found_versions, &result.package).join(", ")
+                );
+            }
+            
+            for (version, availability) in &result.unavailable {
+                println!("   🚫 {}: {}", version, availability.describe());
+            }
+            
+            if !result.rekor_entries.is_empty() {
+                println!("   🔏 Rekor: {}", format_rekor_entries(&result.rekor_entries));
+            }
+            
+            if let Some(count) = result.weekly_downloads {
+                println!("   📈 下载量: {}", registry::describe_downloads(count));
+            }
+            
             if let Some(ref status) = result.package.status {
                 println!("   状态: {}", status);
             }
@@ -520,16 +2290,148 @@ fn print_batch_results(results: &[BatchResult], verbose: bool) {
                 println!("   检测日期: {}", date);
             }
             
+            if !result.package.advisories.is_empty() {
+                println!("   公告: {}", result.package.advisories.join(", "));
+            }
+            
+            if explain {
+                print_explanation(result);
+            }
+            
+            println!();
+        } else if explain {
+            print_explanation(result);
             println!();
         }
     }
     
+    if page.range(results.len()).len() < results.len() {
+        page.print_omitted(results.len());
+        println!();
+    }
+    
+    let tagged: Vec<_> = results.iter().filter(|r| r.dist_tags.is_some()).collect();
+    if !tagged.is_empty() {
+        println!("🏷️ dist-tags:");
+        for result in tagged {
+            let Some(ref tags) = result.dist_tags else {
+                continue;
+            };
+            println!("   {}: {}", result.package.name, format_dist_tags(tags));
+            let exposed = exposed_tags(tags, &result.package.versions, result.package.match_mode);
+            if !result.package.versions.is_empty() && !exposed.is_empty() {
+                println!("   ⚠️ 以下 tag 仍指向受影响版本，新安装仍会受影响: {}", exposed.join(", "));
+            }
+        }
+        println!();
+    }
+    
+    let exposed: Vec<_> = results.iter().filter(|r| !r.exposure.is_empty()).collect();
+    if !exposed.is_empty() {
+        println!("⏱️ 暴露窗口:");
+        for result in exposed {
+            println!("   {}", result.package.name);
+            for window in &result.exposure {
+                print!("   - {}", window.summary());
+                if let Some(days) = result
+                    .package
+                    .detection_date
+                    .as_deref()
+                    .and_then(|d| window.days_before_detection(d))
+                {
+                    print!("；检测日期前已存在 {} 天", days);
+                }
+                println!();
+            }
+        }
+        println!();
+    }
+    
     println!("🎯 统计信息:");
     println!("   总数: {}", results.len());
     println!("   ✅ 找到: {}", found_count);
     println!("   🟡 部分匹配: {}", partial_match_count);
+    if mixed_count > 0 {
+        println!("   🩹 已有修复版本: {}", mixed_count);
+    }
     println!("   ⚠️ 版本不匹配: {}", version_mismatch_count);
     println!("   ❌ 未找到: {}", not_found_count);
+    if unpublished_count > 0 {
+        println!("   🚫 已撤下: {}", unpublished_count);
+    }
+    let suppressed = results.iter().filter(|r| r.suppressed.is_some()).count();
+    if suppressed > 0 {
+        println!("   🔕 已抑制: {}", suppressed);
+    }
+    println!("   📈 风险评分: {}", risk::total(results));
+    
+    if results.iter().any(|r| r.severity.is_some()) {
+        let count = |severity: Severity| results.iter().filter(|r| r.severity == Some(severity)).count();
+        println!("\n🛡️ 策略判定:");
+        println!("   🚨 fail: {}", count(Severity::Fail));
+        println!("   ⚠️ warn: {}", count(Severity::Warn));
+        println!("   ℹ️ info: {}", count(Severity::Info));
+    }
+}
+
+fn print_explanation(result: &BatchResult) {
+    println!("   判定说明:");
+    
+    if result.found_versions.is_empty() {
+        println!("   - 锁文件的 importers、packages、snapshots 节点中均未出现该包");
+        return;
+    }
+    
+    if result.package.versions.is_empty() {
+        println!("   - 未指定期望版本，出现任意版本即视为命中");
+        return;
+    }
+    
+    for expected in &result.package.versions {
+        let matched: Vec<_> = result
+            .found_versions
+            .iter()
+            .filter(|p| version_matches(&p.version, expected, result.package.match_mode))
+            .collect();
+        
+        if matched.is_empty() {
+            let mut actual: Vec<_> = result.found_versions.iter().map(|p| p.version.as_str()).collect();
+            actual.sort();
+            actual.dedup();
+            println!("   - {} 未命中：版本不满足（实际版本: {}）", expected, actual.join(", "));
+            continue;
+        }
+        
+        let targets: Vec<_> = matched
+            .iter()
+            .map(|p| format!("{} @ {} ({})", p.place(), p.version, p.dependency_type))
+            .collect();
+        println!("   - {} 命中: {}", expected, targets.join("; "));
+        
+        let direct: Vec<_> = matched
+            .iter()
+            .filter(|p| p.location.is_importer())
+            .collect();
+        if direct.is_empty() {
+            if matched.iter().all(|p| p.location == Location::SnapshotsSection) {
+                println!("     注意：仅出现在 snapshots 节点（间接依赖）");
+            } else {
+                println!("     注意：未被任何 importer 直接引用（间接依赖）");
+            }
+        } else if direct.iter().all(|p| p.dependency_type == "devDependencies") {
+            println!("     注意：仅作为 devDependencies 直接引入");
+        }
+    }
+    
+    let extra: Vec<_> = result
+        .found_versions
+        .iter()
+        .filter(|p| !result.package.versions.iter().any(|v| version_matches(&p.version, v, result.package.match_mode)))
+        .map(|p| format!("{} ({})", p.version, p.place()))
+        .collect();
+    if !extra.is_empty() {
+        println!("   - 不在期望列表中的实际版本: {}", extra.join(", "));
+    }
 }
 
 fn write_batch_report(results: &[BatchResult], output_file: &str) -> Result<()> {
@@ -538,15 +2440,10 @@ fn write_batch_report(results: &[BatchResult], output_file: &str) -> Result<()>
     let mut file = std::fs::File::create(output_file)
         .with_context(|| format!("无法创建输出文件 '{}'", output_file))?;
     
-    writeln!(file, "Package Name\tStatus\tExpected Versions\tFound Versions\tLocations\tOriginal Status\tDetection Date")?;
+    writeln!(file, "Package Name\tStatus\tExpected Versions\tFound Versions\tLocations\tOriginal Status\tDetection Date\tExposure\tSeverity\tDist Tags\tWeekly Downloads\tRekor Entries\tRisk Score\tFixed In\tInstall Scripts\tResolution\tAdvisories")?;
     
     for result in results {
-        let status_text = match result.status {
-            CheckStatus::Found => "Found",
-            CheckStatus::NotFound => "Not Found",
-            CheckStatus::VersionMismatch => "Version Mismatch",
-            CheckStatus::PartialMatch => "Partial Match",
-        };
+        let status_text = result.status.report_label();
         
         let expected_versions = if result.package.versions.is_empty() {
             "Any".to_string()
@@ -567,97 +2464,221 @@ fn write_batch_report(results: &[BatchResult], output_file: &str) -> Result<()>
             "None".to_string()
         } else {
             result.found_versions.iter()
-                .map(|p| format!("{} ({})", p.location, p.dependency_type))
+                .map(|p| format!("{} ({})", p.location.id(), p.report_type()))
                 .collect::<Vec<_>>()
                 .join("; ")
         };
         
+        // 各版本的安装脚本是否执行，如 1.0.0=runs
+        let mut install_scripts: Vec<String> = result
+            .found_versions
+            .iter()
+            .filter_map(|p| Some(format!("{}={}", p.version, p.install_scripts?.key())))
+            .collect();
+        install_scripts.sort();
+        install_scripts.dedup();
+        let install_scripts = install_scripts.join("; ");
+        
+        // 各版本的来源类型，如 1.0.0=git
+        let mut resolutions: Vec<String> = result
+            .found_versions
+            .iter()
+            .filter_map(|p| Some(format!("{}={}", p.version, p.resolution.as_ref()?.kind())))
+            .collect();
+        resolutions.sort();
+        resolutions.dedup();
+        let resolutions = resolutions.join("; ");
+        
         let original_status = result.package.status.as_deref().unwrap_or("");
         let detection_date = result.package.detection_date.as_deref().unwrap_or("");
         
-        writeln!(file, "{}\t{}\t{}\t{}\t{}\t{}\t{}", 
+        let exposure = result.exposure.iter()
+            .map(|w| w.report_text())
+            .collect::<Vec<_>>()
+            .join("; ");
+        
+        let severity = result.severity.map(|s| s.label()).unwrap_or("");
+        
+        let dist_tags = result.dist_tags.as_ref().map(format_dist_tags).unwrap_or_default();
+        
+        let weekly_downloads = result.weekly_downloads.map(|c| c.to_string()).unwrap_or_default();
+        
+        let rekor_entries = result.rekor_entries.iter()
+            .map(|(version, count)| format!("{}={}", version, count))
+            .collect::<Vec<_>>()
+            .join("; ");
+        
+        writeln!(file, "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}", 
             result.package.name,
             status_text,
             expected_versions,
             found_versions,
             locations,
             original_status,
-            detection_date
+            detection_date,
+            exposure,
+            severity,
+            dist_tags,
+            weekly_downloads,
+            rekor_entries,
+            result.risk_score,
+            result.package.fixed_in.join(", "),
+            install_scripts,
+            resolutions,
+            result.package.advisories.join(", ")
         )?;
     }
     
     Ok(())
 }
 
-fn extract_version_from_key(key: &str, package_name: &str) -> String {
-    // 从 packages key 中提取版本号
-    // 例如: "@ant-design/icons@4.8.3" -> "4.8.3"
-    let patterns = vec![
-        format!("{}@", package_name),
-        format!("/{}@", package_name),
-    ];
-    
-    for pattern in patterns {
-        if let Some(pos) = key.find(&pattern) {
-            let start = pos + pattern.len();
-            return key[start..].split('_').next().unwrap_or("").to_string();
+fn print_package_info(pkg: &PackageFound, verbose: bool) {
+    if verbose {
+        println!("   📍 位置: {}", pkg.place());
+        println!("      类型: {}", pkg.dependency_type);
+        if !pkg.specifier.is_empty() {
+            println!("      规格: {}", pkg.specifier);
         }
+        println!("      版本: {}", pkg.version);
+        if let Some(ref overridden) = pkg.overridden {
+            println!("      覆盖: {}（锁文件 overrides）", overridden);
+        }
+        if let Some(ref patched) = pkg.patched {
+            println!("      补丁: {}（锁文件 patchedDependencies）", patched);
+        }
+        if let Some(scripts) = pkg.install_scripts {
+            println!("      安装脚本: {}", scripts.describe());
+        }
+        if let Some(ref resolution) = pkg.resolution {
+            println!("      来源: {}", resolution.describe());
+        }
+        if let Some(alias) = pkg.alias_text() {
+            println!("      别名: {}（npm: 别名安装）", alias);
+        }
+        println!();
+    } else {
+        println!("   {} @ {} ({}){}", pkg.place(), pkg.version, pkg.dependency_type, pkg.notes());
     }
-    
-    String::new()
 }
 
-fn version_matches(actual: &str, expected: &str) -> bool {
-    // 简单的版本匹配
-    // 可以扩展支持语义化版本匹配（^, ~, >=, 等）
-    actual == expected || actual.starts_with(&format!("{}.", expected))
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-fn extract_package_name_from_snapshot_key(key: &str) -> String {
-    // 从 snapshot key 中提取包名
-    // 例如: "@ahooksjs/use-request@2.8.15(react@18.3.1)" -> "@ahooksjs/use-request"
-    if let Some(at_pos) = key.rfind('@') {
-        // 找到最后一个@，它之前的是包名
-        let package_part = &key[..at_pos];
-        // 处理可能的括号情况
-        if let Some(paren_pos) = package_part.find('(') {
-            package_part[..paren_pos].to_string()
-        } else {
-            package_part.to_string()
+    #[test]
+    fn verbose_version_line() {
+        for raw in ["9.0", "'9.0'"] {
+            let content = format!("lockfileVersion: {}\n\nimporters: {{}}\n", raw);
+            let lock_data = Lockfile::parse(&content).unwrap();
+            assert_eq!(version_line(&lock_data), "Lockfile 版本: 9.0");
         }
-    } else if let Some(paren_pos) = key.find('(') {
-        key[..paren_pos].to_string()
-    } else {
-        key.to_string()
+        let lock_data = Lockfile::parse("lockfileVersion: 5.4\n").unwrap();
+        assert_eq!(version_line(&lock_data), "Lockfile 版本: 5.4");
     }
-}
 
-fn extract_version_from_snapshot_key(key: &str) -> String {
-    // 从 snapshot key 中提取版本号
-    // 例如: "@ahooksjs/use-request@2.8.15(react@18.3.1)" -> "2.8.15"
-    if let Some(at_pos) = key.rfind('@') {
-        let after_at = &key[at_pos + 1..];
-        // 版本号在括号之前或到字符串结束
-        if let Some(paren_pos) = after_at.find('(') {
-            after_at[..paren_pos].to_string()
-        } else {
-            after_at.to_string()
+    fn found(importer: &str, version: &str) -> PackageFound {
+        PackageFound {
+            location: Location::Importer(importer.to_string()),
+            specifier: version.to_string(),
+            version: version.to_string(),
+            dependency_type: "dependencies".to_string(),
+            overridden: None,
+            patched: None,
+            install_scripts: None,
+            resolution: None,
+            alias: None,
+            importer_label: None,
         }
-    } else {
-        String::new()
     }
-}
 
-fn print_package_info(pkg: &PackageFound, verbose: bool) {
-    if verbose {
-        println!("   📍 位置: {}", pkg.location);
-        println!("      类型: {}", pkg.dependency_type);
-        if !pkg.specifier.is_empty() {
-            println!("      规格: {}", pkg.specifier);
+    fn batch_package(name: &str, versions: &[&str]) -> BatchPackage {
+        BatchPackage {
+            name: name.to_string(),
+            versions: versions.iter().map(|v| v.to_string()).collect(),
+            status: None,
+            detection_date: None,
+            fixed_in: Vec::new(),
+            advisories: Vec::new(),
+            match_mode: MatchMode::Exact,
         }
-        println!("      版本: {}", pkg.version);
-        println!();
-    } else {
-        println!("   {} @ {} ({})", pkg.location, pkg.version, pkg.dependency_type);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn evaluate_expected_by_mode() {
+        use CheckStatus::{Found, PartialMatch, VersionMismatch};
+        // (锁文件中的版本, 期望版本, [Any, All, ExactSet] 的结论)
+        let cases: &[(&[&str], &[&str], [CheckStatus; 3])] = &[
+            (&["1.0.0"], &["1.0.0"], [Found, Found, Found]),
+            (&["1.0.0", "2.0.0"], &["1.0.0", "2.0.0"], [Found, Found, Found]),
+            // 只命中部分期望版本
+            (&["1.0.0"], &["1.0.0", "2.0.0"], [Found, PartialMatch, PartialMatch]),
+            // 锁文件中有期望之外的版本
+            (&["1.0.0", "2.0.0", "3.0.0"], &["1.0.0", "2.0.0"], [Found, Found, PartialMatch]),
+            (&["1.0.0", "3.0.0"], &["1.0.0", "2.0.0"], [Found, PartialMatch, PartialMatch]),
+            // 同一版本出现在多个 importer 中只算一次命中
+            (&["1.0.0", "1.0.0"], &["1.0.0", "2.0.0"], [Found, PartialMatch, PartialMatch]),
+            (&["3.0.0"], &["1.0.0", "2.0.0"], [VersionMismatch, VersionMismatch, VersionMismatch]),
+        ];
+        let modes = [VerdictMode::Any, VerdictMode::All, VerdictMode::ExactSet];
+        for (versions, expected, statuses) in cases {
+            let found_packages: Vec<PackageFound> =
+                versions.iter().enumerate().map(|(i, v)| found(&format!("packages/{}", i), v)).collect();
+            let package = batch_package("lodash", expected);
+            for (mode, status) in modes.iter().zip(statuses) {
+                assert_eq!(
+                    &evaluate_expected(&found_packages, &package, *mode),
+                    status,
+                    "{:?} {:?} {:?}",
+                    versions,
+                    expected,
+                    mode
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn merge_duplicate_entries() {
+        let first = BatchPackage {
+            status: Some("active".to_string()),
+            detection_date: Some("2025-09-08".to_string()),
+            fixed_in: vec!["4.17.21".to_string()],
+            advisories: vec!["GHSA-0001".to_string()],
+            ..batch_package("lodash", &["4.17.19", "4.17.20"])
+        };
+        let second = BatchPackage {
+            status: Some("resolved".to_string()),
+            detection_date: Some("2025-10-01".to_string()),
+            ..batch_package("lodash", &["4.17.20", "4.17.15"])
+        };
+        let packages = vec![first.clone(), batch_package("chalk", &["5.6.1"]), second];
+        let merged = merge_expected_versions(&packages);
+        // 只出现一次的包不合并，合并后的版本按首次出现的顺序去重
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged["lodash"], vec!["4.17.19", "4.17.20", "4.17.15"]);
+
+        let package = merged_package(&first, &merged).unwrap();
+        assert_eq!(package.versions, vec!["4.17.19", "4.17.20", "4.17.15"]);
+        assert_eq!(package.status.as_deref(), Some("active"));
+        assert_eq!(package.detection_date.as_deref(), Some("2025-09-08"));
+        assert_eq!(package.fixed_in, vec!["4.17.21"]);
+        assert_eq!(package.advisories, vec!["GHSA-0001"]);
+        assert!(merged_package(&packages[1], &merged).is_none());
+
+        // 任一条目未指定版本时合并结果为任意版本
+        let packages = vec![first.clone(), batch_package("lodash", &[])];
+        let merged = merge_expected_versions(&packages);
+        assert_eq!(merged["lodash"], Vec::<String>::new());
+        assert!(merged_package(&first, &merged).unwrap().versions.is_empty());
+    }
+
+    #[test]
+    fn evaluate_expected_without_versions() {
+        let package = batch_package("lodash", &["1.0.0"]);
+        let any_version = batch_package("lodash", &[]);
+        for mode in [VerdictMode::Any, VerdictMode::All, VerdictMode::ExactSet] {
+            assert_eq!(evaluate_expected(&[], &package, mode), CheckStatus::NotFound);
+            assert_eq!(evaluate_expected(&[found(".", "3.0.0")], &any_version, mode), CheckStatus::Found);
+        }
+    }
+}
@@ -0,0 +1,127 @@
+// 版本匹配策略：期望版本可以用 "模式:" 前缀单独指定匹配方式，例如 exact:4.8.3、semver:^4.8.0
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use regex::Regex;
+use semver::{Version, VersionReq};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MatchMode {
+    /// 版本号完全一致
+    Exact,
+    /// 版本号一致，或以期望版本加 "." 开头（4.8 匹配 4.8.3）
+    Prefix,
+    /// npm 风格的版本范围（^4.8.0、>=1.0.0 <2.0.0，支持 || 连接多个范围）
+    Semver,
+    /// 正则表达式，需要自行添加 ^ $ 锚定
+    Regex,
+}
+
+impl MatchMode {
+    fn from_prefix(prefix: &str) -> Option<Self> {
+        match prefix {
+            "exact" => Some(MatchMode::Exact),
+            "prefix" => Some(MatchMode::Prefix),
+            "semver" => Some(MatchMode::Semver),
+            "regex" => Some(MatchMode::Regex),
+            _ => None,
+        }
+    }
+}
+
+/// 拆分期望版本上的匹配模式前缀，没有前缀时使用默认模式
+fn split_mode(expected: &str, default: MatchMode) -> (MatchMode, &str) {
+    if let Some((prefix, pattern)) = expected.split_once(':')
+        && let Some(mode) = MatchMode::from_prefix(prefix)
+    {
+        return (mode, pattern);
+    }
+    (default, expected)
+}
+
+pub fn version_matches(actual: &str, expected: &str, default: MatchMode) -> bool {
+    let (mode, pattern) = split_mode(expected, default);
+    match mode {
+        MatchMode::Exact => actual == pattern,
+        MatchMode::Prefix => actual == pattern || actual.starts_with(&format!("{}.", pattern)),
+        MatchMode::Semver => {
+            let Ok(actual) = Version::parse(actual) else {
+                return false;
+            };
+            pattern
+                .split("||")
+                .filter_map(|range| parse_range(range).ok())
+                .any(|req| req.matches(&actual))
+        }
+        MatchMode::Regex => compiled(pattern).is_some_and(|re| re.is_match(actual)),
+    }
+}
+
+// 编译过的正则表达式按模式缓存：批量检查、history 与 exposure 中同一个期望版本要与每个提交的每个条目逐一比较
+static REGEX_CACHE: LazyLock<Mutex<HashMap<String, Option<Regex>>>> = LazyLock::new(Default::default);
+
+fn compiled(pattern: &str) -> Option<Regex> {
+    let mut cache = REGEX_CACHE.lock().unwrap();
+    if let Some(regex) = cache.get(pattern) {
+        return regex.clone();
+    }
+    let regex = Regex::new(pattern).ok();
+    cache.insert(pattern.to_string(), regex.clone());
+    regex
+}
+
+/// 实际版本是否为修复版本：带模式前缀时按前缀匹配，否则不低于修复版本即视为已修复
+pub fn is_fixed(actual: &str, fixed: &str) -> bool {
+    let (mode, pattern) = split_mode(fixed, MatchMode::Exact);
+    if pattern.len() != fixed.len() {
+        return version_matches(actual, fixed, mode);
+    }
+    match (Version::parse(actual), Version::parse(fixed.trim())) {
+        (Ok(actual), Ok(fixed)) => actual >= fixed,
+        _ => actual == fixed.trim(),
+    }
+}
+
+/// 检查期望版本能否按其匹配模式解析，避免写错的范围或正则静默地不匹配任何版本
+pub fn validate(expected: &str, default: MatchMode) -> Result<()> {
+    let (mode, pattern) = split_mode(expected, default);
+    match mode {
+        MatchMode::Exact | MatchMode::Prefix => {}
+        MatchMode::Semver => {
+            for range in pattern.split("||") {
+                parse_range(range).with_context(|| format!("无效的版本范围 '{}'", expected))?;
+            }
+        }
+        MatchMode::Regex => {
+            Regex::new(pattern).with_context(|| format!("无效的正则表达式 '{}'", expected))?;
+        }
+    }
+    Ok(())
+}
+
+// 转换为 semver crate 的写法：npm 以空格分隔多个比较条件，semver crate 要求以逗号分隔；
+// npm 中不带运算符的完整版本号表示精确匹配，semver crate 中则等同于 ^
+fn parse_range(range: &str) -> Result<VersionReq, semver::Error> {
+    let mut comparators = Vec::new();
+    let mut pending_op = String::new();
+    for token in range.split_whitespace() {
+        // ">= 1.0.0" 这种运算符与版本号之间有空格的写法
+        if token.chars().all(|c| "<>=~^".contains(c)) {
+            pending_op.push_str(token);
+            continue;
+        }
+        let op = std::mem::take(&mut pending_op);
+        let bare = op.is_empty() && token.starts_with(|c: char| c.is_ascii_digit());
+        let wildcard = token.contains(['x', 'X', '*']);
+        if bare && !wildcard {
+            comparators.push(format!("={}", token));
+        } else {
+            comparators.push(format!("{}{}", op, token));
+        }
+    }
+    VersionReq::parse(&comparators.join(", "))
+}
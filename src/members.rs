@@ -0,0 +1,92 @@
+// workspace 成员：把 importer 路径对应到 workspace 的包名（各 importer 目录中 package.json 的 name），
+// 用于 --workspace 按包名筛选，以及在控制台输出中按包名标注 importer（@acme/web (apps/web)）
+//
+// --workspace 可以写包名或 importer 路径；锁文件中没有对应 importer 时，结合 pnpm-workspace.yaml
+// （npm、yarn、bun 为 package.json 的 workspaces 字段）区分“已声明但尚未安装”与“不存在”的 workspace
+
+use anyhow::{Result, bail};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use npm_package_check::lockfile::Lockfile;
+use npm_package_check::workspace;
+
+use crate::{collation, visibility};
+
+/// importer 路径 → workspace 包名；prefix 为 --base-dir 加在 importer 路径前的前缀（即根 importer 的路径），
+/// 没有 package.json 或其中没有 name 的 importer 不列出
+pub fn names(lock: &Lockfile, lockfile_dir: &Path, prefix: &str) -> BTreeMap<String, String> {
+    lock.importers
+        .keys()
+        .filter_map(|path| {
+            let relative = match prefix {
+                "" | "." => path.as_str(),
+                prefix if path == prefix => ".",
+                prefix => path.strip_prefix(&format!("{}/", prefix)).unwrap_or(path),
+            };
+            let manifest = workspace::read_manifest(&lockfile_dir.join(relative)).ok().flatten()?;
+            let name = manifest.get("name")?.as_str()?.to_string();
+            Some((path.clone(), name))
+        })
+        .collect()
+}
+
+/// 根目录中声明的 workspace 成员：pnpm-workspace.yaml 优先，其次为 package.json 的 workspaces 字段
+fn declared(lockfile_dir: &Path) -> Result<Vec<String>> {
+    let patterns = match workspace::pnpm_patterns(lockfile_dir)? {
+        Some(patterns) => patterns,
+        None => workspace::read_manifest(lockfile_dir)?
+            .map(|manifest| workspace::patterns(&manifest))
+            .unwrap_or_default(),
+    };
+    Ok(workspace::expand(lockfile_dir, &patterns))
+}
+
+/// 只保留 --workspace 选中的 importer 及其可以到达的节点
+pub fn retain_workspaces(lock: &mut Lockfile, lockfile_dir: &Path, selectors: &[String], verbose: bool) -> Result<()> {
+    let names = names(lock, lockfile_dir, "");
+    let total = lock.importers.len();
+    let mut retained: Vec<String> = Vec::new();
+    for selector in selectors {
+        let path = selector.trim_start_matches("./").trim_end_matches('/');
+        let path = if path.is_empty() { "." } else { path };
+        let matched: Vec<&String> = lock
+            .importers
+            .keys()
+            .filter(|importer| *importer == path || names.get(*importer) == Some(selector))
+            .collect();
+        if matched.is_empty() {
+            let member = declared(lockfile_dir)?.into_iter().find(|dir| {
+                dir == path
+                    || workspace::read_manifest(&lockfile_dir.join(dir))
+                        .ok()
+                        .flatten()
+                        .is_some_and(|manifest| manifest.get("name").and_then(|n| n.as_str()) == Some(selector))
+            });
+            if let Some(dir) = member {
+                bail!("workspace '{}'（{}）已在 workspace 配置中声明，但锁文件中没有对应的 importer，请重新安装依赖", selector, dir);
+            }
+            let mut available: Vec<&str> = names.values().map(String::as_str).collect();
+            collation::sort_by_key(&mut available, |name| name);
+            bail!("没有名为 '{}' 的 workspace（可用的 workspace 包: {}）", selector, available.join(", "));
+        }
+        for importer in matched {
+            if !retained.contains(importer) {
+                retained.push(importer.clone());
+            }
+        }
+    }
+
+    let before = lock.packages.len();
+    visibility::retain_reachable(lock, &retained);
+    if verbose {
+        println!(
+            "只检查选中的 workspace: {}/{} 个 importer，packages 节点 {}/{} 个",
+            retained.len(),
+            total,
+            lock.packages.len(),
+            before
+        );
+    }
+    Ok(())
+}
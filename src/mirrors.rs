@@ -0,0 +1,113 @@
+// mirrors 子命令：检查各包的下载地址是否来自允许的镜像
+//
+// package-lock.json、yarn.lock 的 resolved 与 pnpm 的 tarball 地址在解析时归入 packages 节点的 resolution：
+//   默认 registry（registry.npmjs.org、registry.yarnpkg.com）始终允许
+//   其他 tarball 地址需要匹配 --allow（或配置文件中的 allowed_mirrors）中的一项，
+//   GitHub 等 git 托管平台生成的 tarball 与未知的 CDN 单独列出，这类地址的内容无法按版本号审计
+//   git 仓库依赖同样需要匹配允许列表；本地目录与 workspace 链接不涉及下载，不做检查
+
+use anyhow::Result;
+use std::collections::BTreeMap;
+
+use npm_package_check::lockfile::{DEFAULT_REGISTRY_HOSTS, LockedPackage, Lockfile, Resolution};
+
+use crate::collation;
+
+// git 托管平台生成 tarball 的主机名
+const GIT_HOSTS: &[&str] = &[
+    "github.com",
+    "codeload.github.com",
+    "raw.githubusercontent.com",
+    "objects.githubusercontent.com",
+    "gitlab.com",
+    "bitbucket.org",
+];
+
+/// 未通过检查的下载地址类别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Finding {
+    GitHostTarball,
+    UnknownHost,
+    GitRepo,
+}
+
+impl Finding {
+    fn label(&self) -> &'static str {
+        match self {
+            Finding::GitHostTarball => "🐙 git 托管平台生成的 tarball",
+            Finding::UnknownHost => "🌐 未知来源的 tarball",
+            Finding::GitRepo => "🔗 git 仓库依赖",
+        }
+    }
+}
+
+/// 地址中的主机名，ssh 地址（git@github.com:a/b）去掉用户名
+fn host(url: &str) -> Option<&str> {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let authority = rest.split(['/', ':', '#', '?']).next()?;
+    let authority = authority.rsplit('@').next().unwrap_or(authority);
+    Some(authority).filter(|host| !host.is_empty())
+}
+
+/// 允许列表中的一项：带协议的写法按地址前缀匹配，否则按主机名匹配（包括子域名）
+fn allowed(url: &str, allow: &[String]) -> bool {
+    let host = host(url);
+    allow.iter().any(|entry| match entry.contains("://") {
+        true => url.starts_with(entry.as_str()),
+        false => host.is_some_and(|host| host == entry || host.ends_with(&format!(".{}", entry))),
+    })
+}
+
+fn classify(resolution: &Resolution, allow: &[String]) -> Option<(Finding, String)> {
+    match resolution {
+        Resolution::Tarball { tarball, .. } => {
+            // pnpm 中本地 tarball 文件写作 file:
+            if !tarball.contains("://") || allowed(tarball, allow) {
+                return None;
+            }
+            let host = host(tarball).unwrap_or_default();
+            if DEFAULT_REGISTRY_HOSTS.contains(&host) {
+                return None;
+            }
+            let finding = if GIT_HOSTS.contains(&host) { Finding::GitHostTarball } else { Finding::UnknownHost };
+            Some((finding, tarball.clone()))
+        }
+        Resolution::Git { repo, commit } if !allowed(repo, allow) => {
+            Some((Finding::GitRepo, format!("{}#{}", repo, commit)))
+        }
+        _ => None,
+    }
+}
+
+pub fn run_mirrors(lock_data: &Lockfile, allow: &[String]) -> Result<()> {
+    let mut findings: BTreeMap<Finding, Vec<(String, String)>> = BTreeMap::new();
+    for (key, package_info) in &lock_data.packages {
+        if let Some((finding, url)) = classify(&package_info.resolution, allow) {
+            let package = lock_data.locked.get(key).map_or_else(|| key.clone(), LockedPackage::id);
+            findings.entry(finding).or_default().push((package, url));
+        }
+    }
+
+    let mut allowed_list: Vec<&str> = DEFAULT_REGISTRY_HOSTS.to_vec();
+    allowed_list.extend(allow.iter().map(String::as_str));
+    println!("🔍 检查 {} 个包的下载地址（允许: {}）\n", lock_data.packages.len(), allowed_list.join(", "));
+
+    if findings.is_empty() {
+        println!("✅ 全部包均来自允许的镜像");
+        return Ok(());
+    }
+
+    let total: usize = findings.values().map(Vec::len).sum();
+    for entries in findings.values_mut() {
+        collation::sort_by_key(entries, |(package, _)| package);
+    }
+    for (finding, entries) in &findings {
+        println!("{} ({}):", finding.label(), entries.len());
+        for (package, url) in entries {
+            println!("   - {}: {}", package, url);
+        }
+        println!();
+    }
+    println!("⚠️ {} 个包的下载地址不在允许的镜像中，可通过 --allow 添加可信的镜像", total);
+    std::process::exit(1);
+}
@@ -0,0 +1,146 @@
+// 一次检查多个锁文件（--dir、--repo 与压缩包）：逐一执行单包查询或批量检查，最后汇总
+//
+// 每个锁文件单独输出检查结果与结论行，args.file 依次设为当前锁文件的名称（报告、--exposure 与使用统计按它处理）；
+// 汇总列出各锁文件的退出码，任一锁文件未通过时以其中最大的退出码结束
+
+use anyhow::{Context, Result, bail};
+use std::path::Path;
+
+use npm_package_check::lockfile::{Lockfile, LockfileFormat};
+use npm_package_check::progress::ProgressEvent;
+
+use crate::http::Client;
+use crate::{Args, LockfileContext, ReportFormat, apply_local_context, collation, progress, run_check};
+
+/// 多个锁文件只能用于单包查询与批量检查，且各自输出结果，报告文件会互相覆盖
+pub fn reject_options(args: &Args, option: &str) -> Result<()> {
+    if args.command.is_some() {
+        bail!("{} 只能用于单包查询与批量检查", option);
+    }
+    reject_reports(args, option)
+}
+
+/// 各锁文件的报告写到同一个文件时会互相覆盖
+pub fn reject_reports(args: &Args, option: &str) -> Result<()> {
+    if args.output.is_some() || args.result_file.is_some() || args.format != ReportFormat::Text {
+        bail!("{} 会检查多个锁文件，不能与 --output、--result-file 或 --format json/osv 同时使用", option);
+    }
+    Ok(())
+}
+
+/// 各锁文件的检查结果
+pub struct Summary {
+    results: Vec<(String, i32)>,
+}
+
+impl Summary {
+    /// files 为找到的全部锁文件，--log-format json 时作为进度事件发送
+    pub fn new(args: &Args, files: Vec<String>) -> Self {
+        progress(args, ProgressEvent::FilesDiscovered { files });
+        Summary { results: Vec::new() }
+    }
+
+    /// 边遍历边检查（--dir）：找到全部锁文件之前就开始检查，每找到一个锁文件用 discovered 单独发送进度事件
+    pub fn streaming() -> Self {
+        Summary { results: Vec::new() }
+    }
+
+    pub fn discovered(&self, args: &Args, name: &str) {
+        progress(args, ProgressEvent::FilesDiscovered { files: vec![name.to_string()] });
+    }
+
+    /// 已检查（包括出错）的锁文件数
+    pub fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+
+    /// 检查一个锁文件：name 为输出与报告中的名称，path 用于按文件名判断格式；
+    /// local 为 true 时锁文件位于本地磁盘的 path，结合所在目录的 package.json 处理（根项目包名、workspace 包名与 --base-dir 各锁文件单独计算）
+    pub fn check(&mut self, args: &mut Args, client: &Client, name: &str, path: &Path, content: &str, local: bool) -> Result<()> {
+        let format = args.lockfile_format.unwrap_or_else(|| LockfileFormat::detect(path, content));
+        if args.verbose {
+            println!("锁文件格式: {}", format.name());
+        }
+        let mut lock_data = Lockfile::parse_as(content, format).with_context(|| format!("解析 '{}' 失败", name))?;
+        let context = match local {
+            true => apply_local_context(args, &mut lock_data, format, content, path)?,
+            false => LockfileContext::default(),
+        };
+        self.check_in_context(args, client, name, &lock_data, &context)
+    }
+
+    /// 检查已经解析的锁文件模型（例如由容器镜像中的 node_modules 生成）
+    pub fn check_lockfile(&mut self, args: &mut Args, client: &Client, name: &str, lock_data: &Lockfile) -> Result<()> {
+        self.check_in_context(args, client, name, lock_data, &LockfileContext::default())
+    }
+
+    fn check_in_context(&mut self, args: &mut Args, client: &Client, name: &str, lock_data: &Lockfile, context: &LockfileContext) -> Result<()> {
+        args.file = name.to_string();
+        let code = run_check(args, client, lock_data, context)?;
+        self.results.push((name.to_string(), code));
+        println!();
+        Ok(())
+    }
+
+    /// 无法读取或解析的锁文件按出错（退出码 1）计入汇总
+    pub fn record_error(&mut self, name: &str) {
+        self.results.push((name.to_string(), 1));
+    }
+
+    /// 按 root 下的第一级目录（各项目、各仓库的检出目录）汇总，直接位于 root 中的锁文件归入 "."；
+    /// 边遍历边检查时结果的顺序不固定，同时把结果按路径排序
+    pub fn print_directories(&mut self, root: &Path) {
+        collation::sort_by_key(&mut self.results, |(name, _)| name);
+        let mut directories: Vec<(String, usize, usize, i32)> = Vec::new();
+        for (name, code) in &self.results {
+            let relative = Path::new(name).strip_prefix(root).unwrap_or(Path::new(name));
+            let mut components = relative.components();
+            let directory = match (components.next(), components.next()) {
+                (Some(first), Some(_)) => first.as_os_str().to_string_lossy().into_owned(),
+                _ => ".".to_string(),
+            };
+            let index = match directories.iter().position(|(known, ..)| *known == directory) {
+                Some(index) => index,
+                None => {
+                    directories.push((directory, 0, 0, 0));
+                    directories.len() - 1
+                }
+            };
+            let entry = &mut directories[index];
+            entry.1 += 1;
+            if *code != 0 {
+                entry.2 += 1;
+                entry.3 = entry.3.max(*code);
+            }
+        }
+        println!("📂 按目录汇总:");
+        for (directory, total, failed, code) in &directories {
+            match failed {
+                0 => println!("   ✅ {}（{} 个锁文件）", directory, total),
+                failed => println!("   ⚠️ {}（{} 个锁文件，{} 个未通过，最大退出码 {}）", directory, total, failed, code),
+            }
+        }
+        println!();
+    }
+
+    /// 输出汇总，任一锁文件未通过时以最大的退出码结束
+    pub fn finish(self) -> Result<()> {
+        let failed = self.results.iter().filter(|(_, code)| *code != 0).count();
+        println!("📋 汇总: {} 个锁文件，{} 个通过，{} 个未通过", self.results.len(), self.results.len() - failed, failed);
+        for (name, code) in &self.results {
+            match code {
+                0 => println!("   ✅ {}", name),
+                code => println!("   ⚠️ {}（退出码 {}）", name, code),
+            }
+        }
+        let exit_code = self.results.iter().map(|(_, code)| *code).max().unwrap_or(0);
+        if exit_code != 0 {
+            std::process::exit(exit_code);
+        }
+        Ok(())
+    }
+}
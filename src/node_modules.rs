@@ -0,0 +1,288 @@
+// node-modules 子命令：遍历 node_modules 读取每个已安装包的 package.json，与锁文件对照，
+// 找出锁文件中没有的包与版本不一致的包，用于发现被篡改或过期的安装目录
+//
+// 支持的目录结构：
+//   npm / yarn classic   node_modules/<name>、node_modules/@scope/<name>，以及包内嵌套的 node_modules
+//   pnpm                 node_modules/.pnpm/<key>/node_modules/<name>
+// 符号链接（pnpm 顶层的链接、workspace 包的链接）不跟随，实际内容在各自的真实目录中检查；
+// 经由符号链接到达、实际位置在扫描根目录（锁文件所在目录或指定的 node_modules 目录）之外的目录与 package.json 不读取；
+// 包随自身发布的 bundledDependencies 不在锁文件中记录，跳过。
+// 没有锁文件的安装目录（例如容器镜像中的 node_modules）可以由已安装的包生成锁文件模型，沿用同一套检查
+
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use npm_package_check::lockfile::{Lockfile, PackageInfo, Resolution};
+
+use crate::{collation, scan};
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Manifest {
+    name: Option<String>,
+    version: Option<String>,
+    #[serde(default)]
+    bundle_dependencies: Bundled,
+    #[serde(default)]
+    bundled_dependencies: Bundled,
+}
+
+/// bundledDependencies 可以是包名数组，也可以是 true（打包全部依赖）
+#[derive(Deserialize, Default)]
+#[serde(untagged)]
+enum Bundled {
+    All(bool),
+    Names(Vec<String>),
+    #[default]
+    None,
+}
+
+impl Bundled {
+    fn contains(&self, name: &str) -> bool {
+        match self {
+            Bundled::All(all) => *all,
+            Bundled::Names(names) => names.iter().any(|n| n == name),
+            Bundled::None => false,
+        }
+    }
+}
+
+/// 一个已安装的包
+struct Installed {
+    name: String,
+    version: String,
+    path: PathBuf,
+}
+
+#[derive(Default)]
+struct Walker {
+    /// 扫描根目录（规范化的路径）
+    root: PathBuf,
+    installed: Vec<Installed>,
+    /// 无法读取或缺少 name/version 的 package.json
+    unreadable: Vec<PathBuf>,
+    visited: HashSet<PathBuf>,
+}
+
+impl Walker {
+    /// 遍历一个 node_modules 目录；bundled 为所属包的 bundledDependencies
+    fn walk(&mut self, node_modules: &Path, bundled: Option<&Bundled>) -> Result<()> {
+        let Ok(canonical) = node_modules.canonicalize() else {
+            return Ok(());
+        };
+        if !canonical.starts_with(&self.root) {
+            eprintln!("⚠️ 跳过扫描目录之外的 node_modules: {} -> {}", node_modules.display(), canonical.display());
+            return Ok(());
+        }
+        if !self.visited.insert(canonical) {
+            return Ok(());
+        }
+        for entry in read_dir_sorted(node_modules)? {
+            let Some(name) = entry.file_name().and_then(|n| n.to_str()).map(str::to_string) else {
+                continue;
+            };
+            if !scan::link_within(&entry, &self.root) {
+                continue;
+            }
+            if name == ".pnpm" {
+                // 每个 <key>/node_modules 中有包本身的目录与指向其依赖的链接
+                for key in read_dir_sorted(&entry)? {
+                    self.walk(&key.join("node_modules"), None)?;
+                }
+            } else if name.starts_with('.') {
+                continue;
+            } else if name.starts_with('@') {
+                for package in read_dir_sorted(&entry)? {
+                    let full_name = format!("{}/{}", name, package.file_name().and_then(|n| n.to_str()).unwrap_or_default());
+                    self.package(&package, &full_name, bundled)?;
+                }
+            } else {
+                self.package(&entry, &name, bundled)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn package(&mut self, dir: &Path, dir_name: &str, bundled: Option<&Bundled>) -> Result<()> {
+        if bundled.is_some_and(|bundled| bundled.contains(dir_name)) {
+            return Ok(());
+        }
+        let is_dir = fs::symlink_metadata(dir).map(|m| m.is_dir()).unwrap_or(false);
+        let manifest_path = dir.join("package.json");
+        if !is_dir || !manifest_path.is_file() || !scan::link_within(&manifest_path, &self.root) {
+            return Ok(());
+        }
+        let manifest: Option<Manifest> = fs::read_to_string(&manifest_path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok());
+        let Some(manifest) = manifest else {
+            self.unreadable.push(manifest_path);
+            return Ok(());
+        };
+        match (&manifest.name, &manifest.version) {
+            (Some(name), Some(version)) => self.installed.push(Installed {
+                name: name.clone(),
+                version: version.clone(),
+                path: dir.to_path_buf(),
+            }),
+            _ => self.unreadable.push(manifest_path),
+        }
+
+        let bundled = match manifest.bundle_dependencies {
+            Bundled::None => manifest.bundled_dependencies,
+            bundled => bundled,
+        };
+        self.walk(&dir.join("node_modules"), Some(&bundled))
+    }
+}
+
+/// 路径（以 / 分隔）是否为 node_modules 中某个包的 package.json：
+/// node_modules/<name>/package.json 或 node_modules/@scope/<name>/package.json，包内子目录中的 package.json 不算
+pub fn is_installed_manifest(path: &str) -> bool {
+    let parts: Vec<&str> = path.split('/').collect();
+    match parts.as_slice() {
+        [.., "node_modules", name, "package.json"] => !name.starts_with('.') && !name.starts_with('@'),
+        [.., "node_modules", scope, _, "package.json"] => scope.starts_with('@'),
+        _ => false,
+    }
+}
+
+/// 由已安装的包 (包名, 版本, 安装目录) 生成锁文件模型：每个 name@version 为 packages 节点中的一个条目，
+/// resolution 记为安装目录
+pub fn installed_lockfile(installed: &[(String, String, String)]) -> Lockfile {
+    let packages = installed
+        .iter()
+        .map(|(name, version, dir)| {
+            let info = PackageInfo {
+                resolution: Resolution::Directory { directory: dir.clone() },
+                name: Some(name.clone()),
+                version: Some(version.clone()),
+                peer_dependencies: BTreeMap::new(),
+                dependencies: BTreeMap::new(),
+                optional_dependencies: BTreeMap::new(),
+                dev_dependencies: BTreeMap::new(),
+                requires_build: None,
+            };
+            (format!("{}@{}", name, version), info)
+        })
+        .collect();
+    Lockfile::new("node_modules".to_string(), BTreeMap::new(), packages, BTreeMap::new())
+}
+
+fn read_dir_sorted(dir: &Path) -> Result<Vec<PathBuf>> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut entries = fs::read_dir(dir)
+        .with_context(|| format!("无法读取目录 '{}'", dir.display()))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .collect::<Vec<_>>();
+    collation::sort_paths(&mut entries);
+    Ok(entries)
+}
+
+/// node_modules 为 None 时检查锁文件所在目录与各 importer 目录中的 node_modules
+pub fn run_node_modules(lock_data: &Lockfile, lockfile_dir: &Path, node_modules: Option<&str>, verbose: bool) -> Result<()> {
+    let roots: Vec<PathBuf> = match node_modules {
+        Some(dir) => {
+            if !Path::new(dir).is_dir() {
+                bail!("目录 '{}' 不存在", dir);
+            }
+            vec![PathBuf::from(dir)]
+        }
+        None => {
+            let mut roots = vec![lockfile_dir.join("node_modules")];
+            for importer in lock_data.importers.keys().filter(|importer| *importer != ".") {
+                roots.push(lockfile_dir.join(importer).join("node_modules"));
+            }
+            let mut seen = HashSet::new();
+            roots.retain(|dir| dir.is_dir() && seen.insert(dir.clone()));
+            if roots.is_empty() {
+                bail!("'{}' 中没有 node_modules 目录，请先安装依赖或指定 node_modules 目录", lockfile_dir.display());
+            }
+            roots
+        }
+    };
+
+    let root = match node_modules {
+        Some(dir) => Path::new(dir),
+        None => lockfile_dir,
+    };
+    let mut walker = Walker {
+        root: root.canonicalize().with_context(|| format!("无法访问目录 '{}'", root.display()))?,
+        ..Walker::default()
+    };
+    for root in &roots {
+        walker.walk(root, None)?;
+    }
+
+    let mut locked: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    for record in lock_data.packages() {
+        locked.entry(record.name).or_default().insert(record.version);
+    }
+
+    let mut missing = Vec::new();
+    let mut mismatched = Vec::new();
+    for package in &walker.installed {
+        match locked.get(&package.name) {
+            None => missing.push(package),
+            Some(versions) if !versions.contains(&package.version) => mismatched.push((package, versions)),
+            Some(_) => {}
+        }
+    }
+
+    let distinct: BTreeSet<_> = walker.installed.iter().map(|p| (&p.name, &p.version)).collect();
+    println!(
+        "📂 已检查 {} 个 node_modules 目录: {} 个已安装的包（{} 个不同的 name@version）",
+        roots.len(),
+        walker.installed.len(),
+        distinct.len()
+    );
+    if verbose {
+        for root in &roots {
+            println!("   {}", root.display());
+        }
+    }
+
+    if !missing.is_empty() {
+        println!("\n❌ 锁文件中没有的包 ({}):", missing.len());
+        for package in &missing {
+            println!("   - {}@{}  {}", package.name, package.version, package.path.display());
+        }
+    }
+    if !mismatched.is_empty() {
+        println!("\n⚠️ 版本与锁文件不一致 ({}):", mismatched.len());
+        for (package, versions) in &mismatched {
+            let versions: Vec<_> = versions.iter().map(String::as_str).collect();
+            println!(
+                "   - {}@{}（锁文件中为 {}）  {}",
+                package.name,
+                package.version,
+                versions.join(", "),
+                package.path.display()
+            );
+        }
+    }
+    if !walker.unreadable.is_empty() {
+        println!("\nℹ️ {} 个 package.json 无法读取或缺少 name/version，已跳过", walker.unreadable.len());
+        if verbose {
+            for path in &walker.unreadable {
+                println!("   - {}", path.display());
+            }
+        }
+    }
+
+    println!();
+    if missing.is_empty() && mismatched.is_empty() {
+        println!("✅ 全部已安装的包均与锁文件一致");
+        return Ok(());
+    }
+    println!(
+        "⚠️ {} 个已安装的包与锁文件不一致，安装目录可能被篡改或已过期，请删除 node_modules 后按锁文件重新安装",
+        missing.len() + mismatched.len()
+    );
+    std::process::exit(1);
+}
@@ -0,0 +1,221 @@
+// package-lock.json（npm v7+ 的 lockfileVersion 2/3）转换为与 pnpm 相同的锁文件模型；
+// 随包发布的 npm-shrinkwrap.json 格式完全相同，按同样的方式解析
+//
+// packages 节点以安装路径为 key："" 为根项目，packages/web 这类不含 node_modules 的路径为 workspace，
+// node_modules/foo、node_modules/a/node_modules/foo 为实际安装的包。转换规则：
+//   根项目与 workspace          → importers（"" 记为 "."）
+//   安装的包                    → packages 节点中以 name@version 为 key 的条目
+//   依赖范围                    → 按 Node 的模块查找规则解析为实际安装的版本
+
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+use crate::ecosystem::Ecosystem;
+use crate::lockfile::{DEFAULT_REGISTRY_HOSTS, DependencyInfo, Importer, Lockfile, PackageInfo, Resolution};
+
+#[derive(Deserialize)]
+struct RawPackageLock {
+    #[serde(rename = "lockfileVersion")]
+    lockfile_version: u32,
+
+    #[serde(default)]
+    packages: BTreeMap<String, RawEntry>,
+}
+
+#[derive(Deserialize)]
+struct RawEntry {
+    // 别名安装时为实际的包名，例如 node_modules/string-width-cjs 的 name 为 string-width
+    #[serde(default)]
+    name: Option<String>,
+
+    #[serde(default)]
+    version: Option<String>,
+
+    #[serde(default)]
+    resolved: Option<String>,
+
+    #[serde(default)]
+    integrity: Option<String>,
+
+    // workspace 包在 node_modules 中的链接，resolved 为 workspace 路径
+    #[serde(default)]
+    link: bool,
+
+    #[serde(default)]
+    dependencies: BTreeMap<String, String>,
+
+    #[serde(default)]
+    #[serde(rename = "devDependencies")]
+    dev_dependencies: BTreeMap<String, String>,
+
+    #[serde(default)]
+    #[serde(rename = "optionalDependencies")]
+    optional_dependencies: BTreeMap<String, String>,
+
+    #[serde(default)]
+    #[serde(rename = "peerDependencies")]
+    peer_dependencies: BTreeMap<String, String>,
+
+    // npm 只在有安装脚本时写出 hasInstallScript: true
+    #[serde(default)]
+    #[serde(rename = "hasInstallScript")]
+    has_install_script: bool,
+}
+
+/// package-lock.json 与 npm-shrinkwrap.json
+pub struct Npm;
+
+impl Ecosystem for Npm {
+    fn name(&self) -> &'static str {
+        "npm"
+    }
+
+    fn file_names(&self) -> &'static [&'static str] {
+        &["package-lock.json", "npm-shrinkwrap.json"]
+    }
+
+    fn sniff(&self, content: &str) -> bool {
+        is_package_lock(content)
+    }
+
+    fn parse(&self, content: &str) -> Result<Lockfile> {
+        parse(content)
+    }
+}
+
+/// 内容是否为 JSON 格式的 package-lock.json（pnpm 锁文件为 YAML，不会以 { 开头）
+pub fn is_package_lock(content: &str) -> bool {
+    content.trim_start().starts_with('{')
+}
+
+pub fn parse(content: &str) -> Result<Lockfile> {
+    let raw: RawPackageLock = serde_json::from_str(content).context("解析 package-lock.json 文件失败")?;
+    if raw.packages.is_empty() {
+        bail!(
+            "package-lock.json (lockfileVersion {}) 中没有 packages 节点，请使用 npm 7 及以上版本重新生成",
+            raw.lockfile_version
+        );
+    }
+
+    let mut importers = BTreeMap::new();
+    let mut packages = BTreeMap::new();
+    for (path, entry) in &raw.packages {
+        if !path.contains("node_modules/") {
+            let importer = Importer {
+                dependencies: resolve_importer_deps(&raw.packages, path, &entry.dependencies),
+                dev_dependencies: resolve_importer_deps(&raw.packages, path, &entry.dev_dependencies),
+                optional_dependencies: resolve_importer_deps(&raw.packages, path, &entry.optional_dependencies),
+            };
+            let path = if path.is_empty() { "." } else { path.as_str() };
+            importers.insert(path.to_string(), importer);
+            continue;
+        }
+        if entry.link {
+            continue;
+        }
+        let Some(ref version) = entry.version else {
+            continue;
+        };
+        let name = entry.name.clone().unwrap_or_else(|| installed_name(path).to_string());
+        packages.entry(format!("{}@{}", name, version)).or_insert_with(|| PackageInfo {
+            resolution: resolution(entry.resolved.as_deref(), entry.integrity.clone()),
+            name: Some(name),
+            version: Some(version.clone()),
+            peer_dependencies: entry.peer_dependencies.clone(),
+            dependencies: resolve_deps(&raw.packages, path, &entry.dependencies),
+            optional_dependencies: resolve_deps(&raw.packages, path, &entry.optional_dependencies),
+            dev_dependencies: BTreeMap::new(),
+            requires_build: Some(entry.has_install_script),
+        });
+    }
+
+    Ok(Lockfile::new(format!("npm {}", raw.lockfile_version), importers, packages, BTreeMap::new()))
+}
+
+/// 安装路径中的包名：node_modules/a/node_modules/@scope/b → @scope/b
+fn installed_name(path: &str) -> &str {
+    path.rsplit_once("node_modules/").map_or(path, |(_, name)| name)
+}
+
+/// 从 base 开始逐级向上查找依赖 name 的安装位置，与 Node 的 require 查找顺序一致
+fn lookup<'a>(packages: &'a BTreeMap<String, RawEntry>, base: &str, name: &str) -> Option<&'a RawEntry> {
+    let mut base = base;
+    loop {
+        let candidate = if base.is_empty() {
+            format!("node_modules/{}", name)
+        } else {
+            format!("{}/node_modules/{}", base, name)
+        };
+        if let Some(entry) = packages.get(&candidate) {
+            return Some(entry);
+        }
+        if base.is_empty() {
+            return None;
+        }
+        // node_modules/a/node_modules/b → node_modules/a；workspace 路径的上一级是根项目
+        base = match base.rfind("/node_modules/") {
+            Some(pos) => &base[..pos],
+            None => "",
+        };
+    }
+}
+
+/// 依赖解析后的值，写法与 pnpm 锁文件一致：版本号、别名 name@version 或 link:路径；
+/// 可选依赖在当前平台未安装时返回 None
+fn resolved_value(packages: &BTreeMap<String, RawEntry>, base: &str, name: &str) -> Option<String> {
+    let entry = lookup(packages, base, name)?;
+    if entry.link {
+        return entry.resolved.as_ref().map(|path| format!("link:{}", path));
+    }
+    let version = entry.version.as_ref()?;
+    match entry.name {
+        Some(ref real) if real != name => Some(format!("{}@{}", real, version)),
+        _ => Some(version.clone()),
+    }
+}
+
+fn resolve_deps(
+    packages: &BTreeMap<String, RawEntry>,
+    base: &str,
+    deps: &BTreeMap<String, String>,
+) -> BTreeMap<String, String> {
+    deps.keys()
+        .filter_map(|name| Some((name.clone(), resolved_value(packages, base, name)?)))
+        .collect()
+}
+
+fn resolve_importer_deps(
+    packages: &BTreeMap<String, RawEntry>,
+    base: &str,
+    deps: &BTreeMap<String, String>,
+) -> BTreeMap<String, DependencyInfo> {
+    deps.iter()
+        .filter_map(|(name, specifier)| {
+            let version = resolved_value(packages, base, name)?;
+            Some((name.clone(), DependencyInfo { specifier: specifier.clone(), version }))
+        })
+        .collect()
+}
+
+/// 由 resolved 地址与 integrity 推断来源，package-lock.json 与 yarn.lock 的写法相同
+pub(crate) fn resolution(resolved: Option<&str>, integrity: Option<String>) -> Resolution {
+    let Some(resolved) = resolved else {
+        // 随父包一起发布的 bundledDependencies 没有 resolved
+        return Resolution::Registry { integrity: integrity.unwrap_or_default() };
+    };
+    if let Some(repo) = resolved.strip_prefix("git+") {
+        let (repo, commit) = repo.split_once('#').unwrap_or((repo, ""));
+        return Resolution::Git { repo: repo.to_string(), commit: commit.to_string() };
+    }
+    if let Some(directory) = resolved.strip_prefix("file:") {
+        return Resolution::Directory { directory: directory.to_string() };
+    }
+    // yarn.lock 的 tarball 地址以 #<sha1> 结尾
+    let tarball = resolved.split('#').next().unwrap_or(resolved);
+    let host = tarball.split("://").nth(1).and_then(|rest| rest.split('/').next());
+    match (host, integrity) {
+        (Some(host), Some(integrity)) if DEFAULT_REGISTRY_HOSTS.contains(&host) => Resolution::Registry { integrity },
+        (_, integrity) => Resolution::Tarball { tarball: tarball.to_string(), integrity },
+    }
+}
@@ -0,0 +1,332 @@
+// org-scan 子命令：npm 供应链事件响应时一次检查整个组织，不需要逐个检出仓库
+//
+// 通过 GitHub（组织或用户名下的仓库）或 GitLab（group 及其子 group 中的项目）API 列出仓库，
+// 读取各仓库默认分支最新提交的目录树与锁文件（规则与 --repo 相同），逐一执行单包查询或批量检查。
+// 默认跳过已归档的仓库与 fork；某个仓库无法读取（空仓库、没有权限）或没有锁文件时记录原因并继续。
+// 控制台按锁文件输出检查结果并汇总，列出各受影响版本所在的仓库；--output 写出汇总全部仓库的 JSON 报告，
+// 其中每个锁文件的结果与 --format json 的报告相同
+
+use anyhow::{Context, Result, bail};
+use clap::ValueEnum;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+
+use crate::github::{GitHub, OrgRepo};
+use crate::gitlab::{GitLab, Project};
+use crate::http::Client;
+use crate::multi::Summary;
+use crate::report::{JsonReport, canonical_json};
+use crate::{Args, ReportFormat, collation, reject_local_options, scan};
+
+/// 代码托管平台
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum Provider {
+    Github,
+    Gitlab,
+}
+
+enum Host {
+    GitHub(GitHub),
+    GitLab(GitLab),
+}
+
+enum Repository {
+    GitHub(OrgRepo),
+    GitLab(Project),
+}
+
+impl Repository {
+    fn name(&self) -> &str {
+        match self {
+            Repository::GitHub(repo) => &repo.full_name,
+            Repository::GitLab(project) => &project.path_with_namespace,
+        }
+    }
+
+    fn default_branch(&self) -> Option<&str> {
+        match self {
+            Repository::GitHub(repo) => Some(repo.default_branch.as_str()).filter(|branch| !branch.is_empty()),
+            Repository::GitLab(project) => project.default_branch.as_deref(),
+        }
+    }
+
+    fn archived(&self) -> bool {
+        match self {
+            Repository::GitHub(repo) => repo.archived,
+            Repository::GitLab(project) => project.archived,
+        }
+    }
+
+    fn fork(&self) -> bool {
+        match self {
+            Repository::GitHub(repo) => repo.fork,
+            Repository::GitLab(project) => project.forked_from_project.is_some(),
+        }
+    }
+}
+
+/// 仓库默认分支最新提交中的锁文件 (路径, blob SHA)
+struct Snapshot {
+    commit: String,
+    lockfiles: Vec<(String, String)>,
+}
+
+impl Host {
+    fn repositories(&self, org: &str) -> Result<Vec<Repository>> {
+        Ok(match self {
+            Host::GitHub(github) => github.org_repos(org)?.into_iter().map(Repository::GitHub).collect(),
+            Host::GitLab(gitlab) => gitlab.group_projects(org)?.into_iter().map(Repository::GitLab).collect(),
+        })
+    }
+
+    fn snapshot(&self, repo: &Repository, excludes: &[String]) -> Result<Snapshot> {
+        let Some(branch) = repo.default_branch() else {
+            bail!("空仓库");
+        };
+        let (commit, blobs): (String, Vec<(String, String)>) = match (self, repo) {
+            (Host::GitHub(github), Repository::GitHub(info)) => {
+                let (owner, name) = info.full_name.split_once('/').unwrap_or(("", &info.full_name));
+                let sha = github.commit_sha(owner, name, branch)?;
+                let tree = github.tree(owner, name, &sha)?;
+                if tree.truncated {
+                    eprintln!("⚠️ {} 的文件过多，GitHub 返回的目录树不完整，可能遗漏部分锁文件", info.full_name);
+                }
+                let blobs = tree.tree.into_iter().filter(|e| e.kind == "blob").map(|e| (e.path, e.sha)).collect();
+                (sha, blobs)
+            }
+            (Host::GitLab(gitlab), Repository::GitLab(project)) => {
+                let sha = gitlab.commit_sha(project, branch)?;
+                let tree = gitlab.tree(project, &sha)?;
+                let blobs = tree.into_iter().filter(|e| e.kind == "blob").map(|e| (e.path, e.id)).collect();
+                (sha, blobs)
+            }
+            _ => unreachable!("仓库与托管平台不匹配"),
+        };
+        let sha_of: BTreeMap<&str, &str> = blobs.iter().map(|(path, sha)| (path.as_str(), sha.as_str())).collect();
+        let paths: Vec<&str> = sha_of.keys().copied().collect();
+        let lockfiles = scan::select_lockfiles(&paths, excludes)?
+            .into_iter()
+            .map(|path| (path.to_string(), sha_of[path].to_string()))
+            .collect();
+        Ok(Snapshot { commit, lockfiles })
+    }
+
+    fn blob_text(&self, repo: &Repository, sha: &str) -> Result<String> {
+        match (self, repo) {
+            (Host::GitHub(github), Repository::GitHub(info)) => {
+                let (owner, name) = info.full_name.split_once('/').unwrap_or(("", &info.full_name));
+                github.blob_text(owner, name, sha)
+            }
+            (Host::GitLab(gitlab), Repository::GitLab(project)) => gitlab.blob_text(project, sha),
+            _ => unreachable!("仓库与托管平台不匹配"),
+        }
+    }
+}
+
+/// --output 写出的汇总报告
+#[derive(Serialize)]
+struct OrgReport {
+    generated_at: String,
+    provider: &'static str,
+    org: String,
+    /// 全部锁文件的风险评分之和
+    risk_score: u32,
+    repositories: Vec<RepoReport>,
+    /// 各受影响版本所在的仓库
+    affected: Vec<Affected>,
+}
+
+#[derive(Serialize)]
+struct RepoReport {
+    repository: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    commit: Option<String>,
+    lockfiles: Vec<JsonReport>,
+    /// 整个仓库被跳过的原因
+    #[serde(skip_serializing_if = "Option::is_none")]
+    skipped: Option<String>,
+    /// 无法读取或解析的锁文件
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    errors: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct Affected {
+    package: String,
+    version: String,
+    repositories: Vec<String>,
+}
+
+pub struct OrgScan<'a> {
+    pub org: &'a str,
+    pub provider: Provider,
+    pub include_archived: bool,
+    pub include_forks: bool,
+    pub gitlab_api: &'a str,
+    pub gitlab_token: Option<String>,
+}
+
+pub fn run_org_scan(args: &mut Args, client: &Client, scan: OrgScan) -> Result<()> {
+    if args.format != ReportFormat::Text || args.result_file.is_some() {
+        bail!("org-scan 的汇总报告通过 --output 写出（JSON），不能与 --result-file 或 --format json/osv 同时使用");
+    }
+    if args.batch.is_none() && args.package.is_none() {
+        bail!("必须指定包名或使用批量模式(-b/--batch)");
+    }
+    reject_local_options(args, "远程锁文件")?;
+
+    let host = match scan.provider {
+        Provider::Github => {
+            if args.github_token.is_none() {
+                eprintln!("⚠️ 未提供 --github-token（或 GITHUB_TOKEN），只能列出公开仓库，且受 GitHub API 的频率限制");
+            }
+            Host::GitHub(GitHub::new(&args.github_api, args.github_token.clone(), client))
+        }
+        Provider::Gitlab => Host::GitLab(GitLab::new(scan.gitlab_api, scan.gitlab_token.clone(), client)),
+    };
+    let provider = match scan.provider {
+        Provider::Github => "github",
+        Provider::Gitlab => "gitlab",
+    };
+
+    let mut repositories = host.repositories(scan.org)?;
+    collation::sort_by_key(&mut repositories, |repo| repo.name());
+    let count = repositories.len();
+    let archived = repositories.iter().filter(|repo| repo.archived()).count();
+    let forks = repositories.iter().filter(|repo| repo.fork() && !repo.archived()).count();
+    repositories.retain(|repo| (scan.include_archived || !repo.archived()) && (scan.include_forks || !repo.fork()));
+    println!(
+        "🏢 {}（{}）: {} 个仓库，其中已归档 {} 个、fork {} 个，检查 {} 个",
+        scan.org,
+        provider,
+        count,
+        archived,
+        forks,
+        repositories.len()
+    );
+
+    // 先读取全部仓库的目录树，确定要检查的锁文件
+    let mut reports: Vec<RepoReport> = Vec::new();
+    let mut snapshots: Vec<(&Repository, Snapshot)> = Vec::new();
+    for repo in &repositories {
+        match host.snapshot(repo, &args.exclude) {
+            Ok(snapshot) if snapshot.lockfiles.is_empty() => {
+                if args.verbose {
+                    println!("   ⚪ {}: 没有锁文件", repo.name());
+                }
+                reports.push(RepoReport {
+                    repository: repo.name().to_string(),
+                    commit: Some(snapshot.commit),
+                    lockfiles: Vec::new(),
+                    skipped: Some("没有锁文件".to_string()),
+                    errors: Vec::new(),
+                });
+            }
+            Ok(snapshot) => snapshots.push((repo, snapshot)),
+            Err(e) => {
+                println!("   ⚠️ {}: 已跳过（{:#}）", repo.name(), e);
+                reports.push(RepoReport {
+                    repository: repo.name().to_string(),
+                    commit: None,
+                    lockfiles: Vec::new(),
+                    skipped: Some(format!("{:#}", e)),
+                    errors: Vec::new(),
+                });
+            }
+        }
+    }
+    let labels: Vec<String> = snapshots
+        .iter()
+        .flat_map(|(repo, snapshot)| {
+            let short_sha = &snapshot.commit[..snapshot.commit.len().min(7)];
+            snapshot.lockfiles.iter().map(move |(path, _)| format!("{}@{}:{}", repo.name(), short_sha, path))
+        })
+        .collect();
+    println!("🔍 {} 个仓库中找到 {} 个锁文件\n", snapshots.len(), labels.len());
+
+    // 各锁文件的报告写入汇总报告，不单独写出
+    let output = args.output.take();
+    args.reports = Some(Mutex::new(Vec::new()));
+    let total = labels.len();
+    let mut summary = Summary::new(args, labels.clone());
+    let mut labels = labels.iter();
+    let mut index = 0;
+    for (repo, snapshot) in &snapshots {
+        let mut errors = Vec::new();
+        for ((path, sha), label) in snapshot.lockfiles.iter().zip(labels.by_ref()) {
+            index += 1;
+            println!("📦 [{}/{}] {}", index, total, label);
+            let checked = host
+                .blob_text(repo, sha)
+                .and_then(|content| summary.check(args, client, label, Path::new(path), &content, false));
+            if let Err(e) = checked {
+                println!("⚠️ {}: {:#}\n", label, e);
+                errors.push(format!("{}: {:#}", path, e));
+                summary.record_error(label);
+            }
+        }
+        let lockfiles = args.reports.as_ref().map_or_else(Vec::new, |reports| reports.lock().unwrap().drain(..).collect());
+        reports.push(RepoReport {
+            repository: repo.name().to_string(),
+            commit: Some(snapshot.commit.clone()),
+            lockfiles,
+            skipped: None,
+            errors,
+        });
+    }
+    args.reports = None;
+    reports.sort_by(|a, b| collation::compare(&a.repository, &b.repository));
+
+    let affected = affected(&reports);
+    if !affected.is_empty() {
+        println!("🏢 受影响的版本（{} 个）:", affected.len());
+        for entry in &affected {
+            println!("   ⚠️ {}@{}: {} 个仓库", entry.package, entry.version, entry.repositories.len());
+            for repository in &entry.repositories {
+                println!("      - {}", repository);
+            }
+        }
+        println!();
+    }
+
+    if let Some(ref path) = output {
+        let report = OrgReport {
+            generated_at: crate::dates::timestamp(args.timezone),
+            provider,
+            org: scan.org.to_string(),
+            risk_score: reports.iter().flat_map(|repo| &repo.lockfiles).map(|lockfile| lockfile.risk_score).sum(),
+            repositories: reports,
+            affected,
+        };
+        fs::write(path, canonical_json(&report, true)?).with_context(|| format!("无法写入文件 '{}'", path))?;
+        println!("📊 汇总报告已写入: {}\n", path);
+    }
+    summary.finish()
+}
+
+/// 命中（且未被抑制）的 name@version → 所在的仓库
+fn affected(reports: &[RepoReport]) -> Vec<Affected> {
+    let mut affected: BTreeMap<(&str, &str), BTreeSet<&str>> = BTreeMap::new();
+    for repo in reports {
+        for lockfile in &repo.lockfiles {
+            for result in lockfile.results.iter().filter(|result| result.suppressed.is_none()) {
+                for version in &result.matched_versions {
+                    affected.entry((&result.package, version)).or_default().insert(&repo.repository);
+                }
+            }
+        }
+    }
+    let mut affected: Vec<Affected> = affected
+        .into_iter()
+        .map(|((package, version), repositories)| {
+            let mut repositories: Vec<String> = repositories.into_iter().map(str::to_string).collect();
+            collation::sort_by_key(&mut repositories, |repository| repository);
+            Affected { package: package.to_string(), version: version.to_string(), repositories }
+        })
+        .collect();
+    collation::sort_by_key(&mut affected, |entry| &entry.package);
+    affected
+}
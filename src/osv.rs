@@ -0,0 +1,168 @@
+// 将批量清单（自定义拒绝列表）导出为 OSV 格式的公告
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::Path;
+
+use npm_package_check::purl;
+
+use crate::BatchResult;
+
+const SCHEMA_VERSION: &str = "1.6.0";
+
+#[derive(Debug, Serialize)]
+pub struct OsvAdvisory {
+    schema_version: &'static str,
+    id: String,
+    modified: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    published: Option<String>,
+    summary: String,
+    details: String,
+    affected: Vec<Affected>,
+    database_specific: DatabaseSpecific,
+}
+
+#[derive(Debug, Serialize)]
+struct Affected {
+    package: OsvPackage,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    versions: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    ranges: Vec<Range>,
+}
+
+#[derive(Debug, Serialize)]
+struct OsvPackage {
+    ecosystem: &'static str,
+    name: String,
+    purl: String,
+}
+
+#[derive(Debug, Serialize)]
+struct Range {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    events: Vec<Event>,
+}
+
+#[derive(Debug, Serialize)]
+struct Event {
+    introduced: String,
+}
+
+#[derive(Debug, Serialize)]
+struct DatabaseSpecific {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source_status: Option<String>,
+    lockfile_status: &'static str,
+    found_versions: Vec<String>,
+}
+
+impl OsvAdvisory {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+/// 每个批量条目生成一条公告，ID 由前缀与包名、版本的摘要组成，多次导出保持稳定
+pub fn build_advisories(results: &[BatchResult], id_prefix: &str) -> Vec<OsvAdvisory> {
+    let modified = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+
+    results
+        .iter()
+        .map(|result| {
+            let package = &result.package;
+
+            let mut hasher = Sha256::new();
+            hasher.update(package.name.as_bytes());
+            for version in &package.versions {
+                hasher.update(b"\0");
+                hasher.update(version.as_bytes());
+            }
+            let digest = format!("{:x}", hasher.finalize());
+
+            // 未指定版本表示所有版本均受影响
+            let ranges = if package.versions.is_empty() {
+                vec![Range {
+                    kind: "SEMVER",
+                    events: vec![Event {
+                        introduced: "0".to_string(),
+                    }],
+                }]
+            } else {
+                Vec::new()
+            };
+
+            let published = package.detection_date.as_deref().and_then(|date| {
+                chrono::NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+                    .ok()
+                    .map(|d| format!("{}T00:00:00Z", d))
+            });
+
+            let versions_text = if package.versions.is_empty() {
+                "all versions".to_string()
+            } else {
+                package.versions.join(", ")
+            };
+
+            OsvAdvisory {
+                schema_version: SCHEMA_VERSION,
+                id: format!("{}-{}", id_prefix, &digest[..12]),
+                modified: modified.clone(),
+                published,
+                summary: format!("Flagged npm package {}", package.name),
+                details: format!(
+                    "{} ({}) is listed in an internal deny list.",
+                    package.name, versions_text
+                ),
+                affected: vec![Affected {
+                    package: OsvPackage {
+                        ecosystem: "npm",
+                        name: package.name.clone(),
+                        purl: purl::npm(&package.name),
+                    },
+                    versions: sorted(&package.versions),
+                    ranges,
+                }],
+                database_specific: DatabaseSpecific {
+                    source_status: package.status.clone(),
+                    lockfile_status: result.status.report_label(),
+                    found_versions: crate::distinct_versions(&result.found_versions),
+                },
+            }
+        })
+        .collect()
+}
+
+fn sorted(versions: &[String]) -> Vec<String> {
+    let mut versions = versions.to_vec();
+    versions.sort();
+    versions.dedup();
+    versions
+}
+
+/// 输出路径为目录时每条公告写入单独的 <id>.json，否则写入一个 JSON 数组
+pub fn write_advisories(advisories: &[OsvAdvisory], output: Option<&str>) -> Result<()> {
+    let Some(output) = output else {
+        println!("{}", crate::report::canonical_json(&advisories, true)?);
+        return Ok(());
+    };
+
+    let path = Path::new(output);
+    if path.is_dir() || output.ends_with('/') {
+        fs::create_dir_all(path).with_context(|| format!("无法创建目录 '{}'", output))?;
+        for advisory in advisories {
+            let file = path.join(format!("{}.json", advisory.id()));
+            fs::write(&file, crate::report::canonical_json(advisory, true)?)
+                .with_context(|| format!("无法写入文件 '{}'", file.display()))?;
+        }
+    } else {
+        fs::write(path, crate::report::canonical_json(&advisories, true)?)
+            .with_context(|| format!("无法写入文件 '{}'", output))?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,246 @@
+// overrides 子命令：检查 package.json 中为有问题的包声明的版本覆盖是否在锁文件中全部生效
+//
+// 覆盖声明的来源：
+//   yarn 的 resolutions          "lodash"、"**/lodash"、"webpack/lodash"
+//   npm 的 overrides             "lodash": "4.17.21"、嵌套对象 "webpack": { "lodash": "..." }、"$lodash" 引用直接依赖的版本
+//   pnpm.overrides 与锁文件中的 overrides 节点   "lodash"、"lodash@<4.17.21"、"webpack>lodash"
+// 锁文件中该包的每一处引用（importer 的直接依赖、其他包的依赖）都应锁定为满足覆盖规格的版本，
+// 仍引用其他版本的位置说明覆盖没有生效（锁文件未重新生成、选择器写错或包管理器不支持该写法）。
+// 带父包限定的声明只检查父包的直接依赖；覆盖为 npm:、git、本地路径等非版本范围的声明无法按版本号验证，单独列出
+
+use anyhow::{Context, Result};
+use semver::Version;
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use npm_package_check::lockfile::{Lockfile, PackageIndex, Section};
+use npm_package_check::workspace;
+
+use crate::LockfileContext;
+use crate::depends::parse_query;
+use crate::matching::{self, MatchMode, version_matches};
+
+/// package.json 或锁文件中的一条覆盖声明
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Rule {
+    source: &'static str,
+    /// 声明中的原始写法，用于显示
+    selector: String,
+    /// 只覆盖该包的直接依赖
+    parent: Option<String>,
+    name: String,
+    spec: String,
+}
+
+impl Rule {
+    fn new(source: &'static str, selector: &str, parent: Option<&str>, name: &str, spec: &str) -> Self {
+        Rule {
+            source,
+            selector: selector.to_string(),
+            parent: parent.map(str::to_string),
+            name: name.to_string(),
+            spec: spec.trim().to_string(),
+        }
+    }
+}
+
+/// 一条声明的检查结果
+enum Outcome {
+    /// 全部引用均满足覆盖规格，记录引用数
+    Effective(usize),
+    /// 仍引用其他版本的位置 (引用方, 版本号, 依赖类型)
+    Ineffective(Vec<(String, String, &'static str)>),
+    /// 锁文件中没有该包
+    Absent,
+    /// 覆盖规格不是版本范围，无法验证
+    Unverifiable,
+}
+
+/// pnpm 的选择器：[父包[@范围]>]包名[@范围]
+fn pnpm_rule(source: &'static str, selector: &str, spec: &str) -> Rule {
+    let (parent, target) = match selector.rsplit_once('>') {
+        Some((parent, target)) => (Some(parse_query(parent).0), target),
+        None => (None, selector),
+    };
+    Rule::new(source, selector, parent, parse_query(target).0, spec)
+}
+
+/// yarn 的选择器按 / 分隔为包名路径（作用域包名占两段），** 匹配任意层级
+fn yarn_rule(selector: &str, spec: &str) -> Rule {
+    let mut names: Vec<String> = Vec::new();
+    let mut segments = selector.split('/').filter(|s| !s.is_empty() && *s != "**");
+    while let Some(segment) = segments.next() {
+        match segment.starts_with('@') {
+            true => names.push(format!("{}/{}", segment, segments.next().unwrap_or_default())),
+            false => names.push(segment.to_string()),
+        }
+    }
+    let name = names.pop().unwrap_or_default();
+    let name = parse_query(&name).0;
+    Rule::new("resolutions", selector, names.last().map(|parent| parse_query(parent).0), name, spec)
+}
+
+/// npm 的 overrides 可以嵌套：对象中的 "." 为该包本身的覆盖规格，其余键作用于该包的依赖
+fn npm_rules(map: &serde_json::Map<String, serde_json::Value>, parent: Option<&str>, rules: &mut Vec<Rule>) {
+    for (key, value) in map {
+        if key == "." {
+            continue;
+        }
+        let name = parse_query(key).0;
+        let selector = match parent {
+            Some(parent) => format!("{} > {}", parent, key),
+            None => key.clone(),
+        };
+        match value {
+            serde_json::Value::String(spec) => rules.push(Rule::new("overrides", &selector, parent, name, spec)),
+            serde_json::Value::Object(nested) => {
+                if let Some(spec) = nested.get(".").and_then(|v| v.as_str()) {
+                    rules.push(Rule::new("overrides", &selector, parent, name, spec));
+                }
+                npm_rules(nested, Some(name), rules);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// npm 的 "$name" 引用根项目直接依赖的版本规格
+fn direct_spec<'a>(manifest: &'a serde_json::Value, name: &str) -> Option<&'a str> {
+    ["dependencies", "devDependencies", "optionalDependencies", "peerDependencies"]
+        .iter()
+        .find_map(|field| manifest.get(field)?.get(name)?.as_str())
+}
+
+/// 收集 package.json 与锁文件中的覆盖声明，同一条声明（例如 pnpm.overrides 与锁文件 overrides 节点）只保留一次
+fn collect_rules(lock: &Lockfile, manifest: Option<&serde_json::Value>) -> Vec<Rule> {
+    let mut rules = Vec::new();
+    for (selector, spec) in &lock.overrides {
+        rules.push(pnpm_rule("锁文件 overrides", selector, spec));
+    }
+    if let Some(manifest) = manifest {
+        let string_map = |value: Option<&serde_json::Value>| {
+            value
+                .and_then(|v| v.as_object())
+                .into_iter()
+                .flatten()
+                .filter_map(|(key, value)| Some((key.clone(), value.as_str()?.to_string())))
+                .collect::<Vec<_>>()
+        };
+        for (selector, spec) in string_map(manifest.pointer("/pnpm/overrides")) {
+            rules.push(pnpm_rule("pnpm.overrides", &selector, &spec));
+        }
+        for (selector, spec) in string_map(manifest.get("resolutions")) {
+            rules.push(yarn_rule(&selector, &spec));
+        }
+        if let Some(map) = manifest.get("overrides").and_then(|v| v.as_object()) {
+            let mut npm = Vec::new();
+            npm_rules(map, None, &mut npm);
+            for rule in &mut npm {
+                if let Some(name) = rule.spec.strip_prefix('$')
+                    && let Some(spec) = direct_spec(manifest, name)
+                {
+                    rule.spec = spec.to_string();
+                }
+            }
+            rules.extend(npm);
+        }
+    }
+
+    let mut seen = BTreeSet::new();
+    rules.retain(|rule| seen.insert((rule.parent.clone(), rule.name.clone(), rule.spec.clone())));
+    rules
+}
+
+fn check(lock: &Lockfile, context: &LockfileContext, index: &PackageIndex, rule: &Rule) -> Outcome {
+    // pnpm 的 "-" 表示移除该依赖，此时不应有任何引用
+    let removed = rule.spec == "-";
+    if !removed && matching::validate(&rule.spec, MatchMode::Semver).is_err() {
+        return Outcome::Unverifiable;
+    }
+
+    let records = index.get(&rule.name);
+    if records.is_empty() {
+        return Outcome::Absent;
+    }
+    let mut references = 0;
+    let mut ineffective = BTreeSet::new();
+    for record in records {
+        let Some(kind) = record.dep_kind else {
+            continue;
+        };
+        let dependent = match record.section {
+            Section::Importers => {
+                if rule.parent.is_some() {
+                    continue;
+                }
+                format!("importer {}", context.importer_label(record.importer.unwrap_or(".")))
+            }
+            Section::Packages | Section::Snapshots => {
+                let parent = record.parent.unwrap_or_default();
+                let locked = lock.locked.get(parent);
+                if rule.parent.as_deref().is_some_and(|name| locked.is_none_or(|locked| locked.name != name)) {
+                    continue;
+                }
+                format!("包 {}", locked.map_or_else(|| parent.to_string(), |locked| locked.id()))
+            }
+        };
+        // git、本地目录等非 semver 版本无法与版本范围比较
+        if !removed && Version::parse(&record.version).is_err() {
+            continue;
+        }
+        references += 1;
+        if removed || !version_matches(&record.version, &rule.spec, MatchMode::Semver) {
+            ineffective.insert((dependent, record.version.clone(), kind.field_name()));
+        }
+    }
+
+    match (references, ineffective.is_empty()) {
+        (0, _) => Outcome::Absent,
+        (count, true) => Outcome::Effective(count),
+        (_, false) => Outcome::Ineffective(ineffective.into_iter().collect()),
+    }
+}
+
+/// lockfile_dir 为 None 时（远程锁文件）只检查锁文件中的 overrides 节点
+pub fn run_overrides(lock_data: &Lockfile, context: &LockfileContext, lockfile_dir: Option<&Path>, verbose: bool) -> Result<()> {
+    let manifest = match lockfile_dir {
+        Some(dir) => workspace::read_manifest(dir).context("读取根项目 package.json 失败")?,
+        None => None,
+    };
+    let rules = collect_rules(lock_data, manifest.as_ref());
+    if rules.is_empty() {
+        println!("ℹ️ package.json 与锁文件中没有 resolutions / overrides 声明");
+        return Ok(());
+    }
+
+    println!("🔍 检查 {} 条版本覆盖声明\n", rules.len());
+    let index = lock_data.index();
+    let mut failed = 0;
+    for rule in &rules {
+        let head = format!("{}: {} → {}", rule.source, rule.selector, rule.spec);
+        match check(lock_data, context, &index, rule) {
+            Outcome::Effective(count) => println!("✅ {}（{} 处引用均已生效）", head, count),
+            Outcome::Absent => {
+                if verbose {
+                    println!("ℹ️ {}（锁文件中没有该包的引用）", head);
+                }
+            }
+            Outcome::Unverifiable => println!("⚪ {}（不是版本范围，无法按版本号验证）", head),
+            Outcome::Ineffective(references) => {
+                failed += 1;
+                println!("❌ {}，{} 处引用仍为其他版本:", head, references.len());
+                for (dependent, version, kind) in &references {
+                    println!("   - {}: {}@{} ({})", dependent, rule.name, version, kind);
+                }
+            }
+        }
+    }
+
+    println!();
+    if failed == 0 {
+        println!("✅ 全部可验证的覆盖声明均已生效");
+        return Ok(());
+    }
+    println!("⚠️ {} 条覆盖声明没有完全生效，请确认选择器写法并重新生成锁文件", failed);
+    std::process::exit(1);
+}
@@ -0,0 +1,254 @@
+// packages/snapshots 节点 key 的解析
+//
+// 各版本锁文件的 key 按同一套语法拆分：
+//   key      = ["/"] name sep version suffix*
+//   name     = ["@" scope "/"] 包名
+//   sep      = "@"（v6/v9、deno.lock）或 "/"（v5）
+//   version  = sep 之后到第一个 "(" 为止；registry 版本号中的 "_" 之后为 v5/deno.lock 的 peer 组合
+//   suffix   = "(" peer 或 patch_hash=… ")"，括号可以任意深度嵌套（peer 自身的 peer 组合），只取最外层
+// 例如：
+//   /@scope/name/1.2.3_@peer+dep@2.0.0               v5，peer 之间以 + 连接，作用域包名中的 / 写作 +
+//   /@scope/name@1.2.3(@peer/dep@2.0.0)              v6
+//   @scope/name@1.2.3(@peer/dep@2.0.0)(patch_hash=…) v9
+//   @scope/name@1.2.3_@peer+dep@2.0.0_react@18.2.0   deno.lock，peer 之间以 _ 连接
+//   name@https://codeload.github.com/…               非 registry 来源，版本为地址本身
+// v5 在 peer 组合过长时以哈希代替，无法还原，与 patch_hash 一样忽略
+
+use crate::lockfile::LockedPackage;
+
+/// 拆分节点 key，不符合语法时返回 None
+pub fn parse(key: &str) -> Option<LockedPackage> {
+    let key = key.trim();
+    // "/ a@1" 去掉 / 之后再去一次空白，否则包名带前导空白，按 name@version 重新拼接后解析结果不同
+    let key = key.strip_prefix('/').unwrap_or(key).trim_start();
+    let (head, groups) = split_groups(key)?;
+    let (name, sep, rest) = split_name(head)?;
+
+    let (version, legacy) = match rest.split_once('_') {
+        Some((version, suffix)) if is_registry_version(version) => (version, Some(suffix)),
+        _ => (rest, None),
+    };
+    // v5 的 key 以 / 分隔版本号，版本号中不会再有 /
+    if version.is_empty() || (sep == '/' && version.contains('/')) {
+        return None;
+    }
+
+    let mut peers: Vec<(String, String)> = groups.into_iter().filter_map(peer).collect();
+    if let Some(suffix) = legacy {
+        peers.extend(legacy_peers(suffix));
+    }
+    Some(LockedPackage {
+        name: name.to_string(),
+        version: version.to_string(),
+        peers,
+    })
+}
+
+/// 拆分出括号之前的部分与各个最外层括号中的内容。一次扫描检查全部层级，
+/// 括号不配对或任意一层的括号之后有其他字符时返回 None
+fn split_groups(key: &str) -> Option<(&str, Vec<&str>)> {
+    // 从第一个括号开始扫描，出现在 ( 之前的 ) 同样视为不配对
+    let Some(pos) = key.find(['(', ')']) else {
+        return Some((key, Vec::new()));
+    };
+    let mut groups = Vec::new();
+    let mut depth = 0usize;
+    let mut start = pos;
+    let mut prev = None;
+    for (i, c) in key[pos..].char_indices().map(|(i, c)| (i + pos, c)) {
+        // 最外层的括号之间、嵌套的括号之后都只能紧接括号
+        if prev == Some(')') && c != '(' && c != ')' {
+            return None;
+        }
+        prev = Some(c);
+        match c {
+            '(' => {
+                if depth == 0 {
+                    start = i + 1;
+                }
+                depth += 1;
+            }
+            ')' => {
+                depth = depth.checked_sub(1)?;
+                if depth == 0 {
+                    groups.push(&key[start..i]);
+                }
+            }
+            _ => {}
+        }
+    }
+    (depth == 0).then_some((&key[..pos], groups))
+}
+
+/// 拆分包名、分隔符与其后的部分，作用域包名开头的 @ 不作为分隔符
+fn split_name(head: &str) -> Option<(&str, char, &str)> {
+    let start = match head.strip_prefix('@') {
+        Some(rest) => {
+            let scope_len = rest.find('/')?;
+            if scope_len == 0 {
+                return None;
+            }
+            scope_len + 2
+        }
+        None => 0,
+    };
+    let end = start + head[start..].find(['@', '/'])?;
+    if end == start {
+        return None;
+    }
+    let sep = head[end..].chars().next()?;
+    Some((&head[..end], sep, &head[end + 1..]))
+}
+
+/// registry 上的版本号以数字开头，不含 _，可以据此识别 v5/deno.lock 的 peer 组合后缀
+fn is_registry_version(version: &str) -> bool {
+    version.starts_with(|c: char| c.is_ascii_digit()) && !version.contains([':', '/', '@'])
+}
+
+/// 括号中的 peer 依赖，patch_hash=… 等非依赖的内容返回 None。
+/// 嵌套的括号已由 split_groups 检查，这里只拆分括号之前的部分而不逐层递归，嵌套再深也不会耗尽栈空间
+fn peer(text: &str) -> Option<(String, String)> {
+    let head = text.split_once('(').map_or(text, |(head, _)| head);
+    let package = parse(head)?;
+    Some((package.name, package.version))
+}
+
+/// v5 的 a@1.0.0+@scope+b@2.0.0 与 deno.lock 的 a@1.0.0_@scope+b@2.0.0
+fn legacy_peers(suffix: &str) -> Vec<(String, String)> {
+    let mut peers = Vec::new();
+    for group in suffix.split('_') {
+        let mut parts = group.split('+');
+        while let Some(part) = parts.next() {
+            // @scope+name@1.0.0 拆分后作用域与包名分属两段
+            let text = match part.starts_with('@') && !part[1..].contains('@') {
+                true => format!("{}/{}", part, parts.next().unwrap_or_default()),
+                false => part.to_string(),
+            };
+            peers.extend(peer(&text));
+        }
+    }
+    peers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn name_version(key: &str) -> Option<(String, String)> {
+        parse(key).map(|package| (package.name, package.version))
+    }
+
+    fn split(key: &str) -> Option<(String, String, Vec<String>)> {
+        parse(key).map(|package| {
+            let peers = package.peers.iter().map(|(name, version)| format!("{}@{}", name, version)).collect();
+            (package.name, package.version, peers)
+        })
+    }
+
+    fn expect(key: &str, name: &str, version: &str, peers: &[&str]) {
+        assert_eq!(
+            split(key),
+            Some((name.to_string(), version.to_string(), peers.iter().map(|p| p.to_string()).collect())),
+            "{key}"
+        );
+    }
+
+    #[test]
+    fn v5_keys() {
+        expect("/lodash/4.17.21", "lodash", "4.17.21", &[]);
+        expect("/@scope/name/1.2.3", "@scope/name", "1.2.3", &[]);
+        expect("/@scope/name/1.2.3_@peer+dep@2.0.0", "@scope/name", "1.2.3", &["@peer/dep@2.0.0"]);
+        expect(
+            "/styled-components/5.3.6_react-dom@18.2.0+react@18.2.0",
+            "styled-components",
+            "5.3.6",
+            &["react-dom@18.2.0", "react@18.2.0"],
+        );
+        // peer 组合过长时的哈希无法还原，忽略
+        expect("/name/1.0.0_5t6gwkjbmjjxlgkp3yo5vbkeie", "name", "1.0.0", &[]);
+        expect("/name_with_underscore/1.0.0", "name_with_underscore", "1.0.0", &[]);
+        assert_eq!(split("/github.com/user/repo/0123abc"), None);
+    }
+
+    #[test]
+    fn v6_keys() {
+        expect("/lodash@4.17.21", "lodash", "4.17.21", &[]);
+        expect("/@scope/name@1.2.3", "@scope/name", "1.2.3", &[]);
+        expect("/@scope/name@1.2.3(@peer/dep@2.0.0)", "@scope/name", "1.2.3", &["@peer/dep@2.0.0"]);
+    }
+
+    #[test]
+    fn v9_keys() {
+        expect("lodash@4.17.21", "lodash", "4.17.21", &[]);
+        expect("@scope/name@1.2.3", "@scope/name", "1.2.3", &[]);
+        expect("name@1.0.0(patch_hash=abc123)", "name", "1.0.0", &[]);
+        expect(
+            "@scope/name@1.2.3(@peer/dep@2.0.0)(patch_hash=abc123)",
+            "@scope/name",
+            "1.2.3",
+            &["@peer/dep@2.0.0"],
+        );
+        expect("name@1.0.0-beta.1+build.5(react@18.2.0)", "name", "1.0.0-beta.1+build.5", &["react@18.2.0"]);
+        expect(
+            "foo@https://codeload.github.com/user/foo/tar.gz/0123abc",
+            "foo",
+            "https://codeload.github.com/user/foo/tar.gz/0123abc",
+            &[],
+        );
+        expect("foo@file:packages/foo", "foo", "file:packages/foo", &[]);
+    }
+
+    #[test]
+    fn deno_keys() {
+        expect(
+            "@scope/name@1.2.3_@peer+dep@2.0.0_react@18.2.0",
+            "@scope/name",
+            "1.2.3",
+            &["@peer/dep@2.0.0", "react@18.2.0"],
+        );
+    }
+
+    #[test]
+    fn nested_peers() {
+        // 只取最外层的 peer，嵌套的 peer 组合属于 peer 自身
+        expect(
+            "@scope/name@1.2.3(@peer/dep@2.0.0(react@18.2.0))(react@18.2.0)",
+            "@scope/name",
+            "1.2.3",
+            &["@peer/dep@2.0.0", "react@18.2.0"],
+        );
+        expect(
+            "name@1.0.0(a@1.0.0(b@1.0.0(c@1.0.0(d@1.0.0(e@1.0.0)))))(f@2.0.0)",
+            "name",
+            "1.0.0",
+            &["a@1.0.0", "f@2.0.0"],
+        );
+        // 很深的嵌套不会耗尽栈空间
+        let deep = format!("name@1.0.0{}{}", "(a@1.0.0".repeat(100_000), ")".repeat(100_000));
+        expect(&deep, "name", "1.0.0", &["a@1.0.0"]);
+    }
+
+    #[test]
+    fn scoped_and_unscoped_names_with_the_same_tail() {
+        // 作用域包名中的 / 不是 v5 的版本分隔符，icons 与 @ant-design/icons 不能混淆
+        expect("@ant-design/icons@4.8.3_react@18.2.0", "@ant-design/icons", "4.8.3", &["react@18.2.0"]);
+        expect("/@ant-design/icons/4.8.3_react@18.2.0", "@ant-design/icons", "4.8.3", &["react@18.2.0"]);
+        expect("icons@1.0.0(@ant-design/icons@4.8.3(react@18.2.0))", "icons", "1.0.0", &["@ant-design/icons@4.8.3"]);
+        expect("/icons/1.0.0", "icons", "1.0.0", &[]);
+    }
+
+    #[test]
+    fn malformed_keys() {
+        for key in ["", "/", "@/name@1.0.0", "@scope", "name", "name@", "name@1.0.0(", "name@1.0.0)", "name@1.0.0(a@1)x"] {
+            assert_eq!(split(key), None, "{key:?}");
+        }
+    }
+
+    #[test]
+    fn whitespace_after_leading_slash() {
+        // 模糊测试发现的输入：去掉 / 之后的前导空白曾留在包名中
+        assert_eq!(name_version("/ @1"), None);
+        assert_eq!(name_version("/ a@1"), Some(("a".to_string(), "1".to_string())));
+        assert_eq!(name_version("a@1"), name_version("/ a@1"));
+    }
+}
@@ -0,0 +1,127 @@
+// 分页：标准输出为交互式终端时，与 git 一样把输出交给分页程序显示，避免大型 monorepo 的长输出冲掉终端的回滚记录
+//
+// 分页程序依次取 NPC_PAGER、PAGER，默认为 less；未设置 LESS 时使用 LESS=FRX，输出不足一屏时 less 直接输出后退出，
+// 与不分页时相同。--no-pager 或分页程序为空、cat 时不分页。
+// 标准输出（以及同为终端的标准错误）重定向到本进程的管道，由转发线程写给分页程序：分页程序提前退出（按 q）后
+// 其余输出直接丢弃，检查照常完成，报告等文件照常写出。进程退出时关闭管道并等待分页程序退出，再把终端交还给 shell
+
+#[cfg(unix)]
+mod imp {
+    use std::fs::File;
+    use std::io::{self, IsTerminal, Read, Write};
+    use std::os::fd::{AsRawFd, FromRawFd};
+    use std::process::{Child, Command, Stdio};
+    use std::sync::Mutex;
+    use std::thread::JoinHandle;
+
+    struct Running {
+        child: Child,
+        forwarder: JoinHandle<()>,
+        /// 标准错误同样重定向到了管道
+        stderr: bool,
+    }
+
+    static RUNNING: Mutex<Option<Running>> = Mutex::new(None);
+
+    fn pager_command() -> Option<String> {
+        let command = std::env::var("NPC_PAGER")
+            .or_else(|_| std::env::var("PAGER"))
+            .unwrap_or_else(|_| "less".to_string());
+        let command = command.trim();
+        (!command.is_empty() && command != "cat").then(|| command.to_string())
+    }
+
+    pub fn start() {
+        if !io::stdout().is_terminal() {
+            return;
+        }
+        let Some(command) = pager_command() else {
+            return;
+        };
+        let mut pager = Command::new("sh");
+        pager.arg("-c").arg(&command).stdin(Stdio::piped());
+        if std::env::var_os("LESS").is_none() {
+            pager.env("LESS", "FRX");
+        }
+        if std::env::var_os("LV").is_none() {
+            pager.env("LV", "-c");
+        }
+        let Ok(mut child) = pager.spawn() else {
+            // 分页程序不可用时直接输出
+            return;
+        };
+        let Some(mut input) = child.stdin.take() else {
+            return;
+        };
+
+        let mut fds = [0; 2];
+        // SAFETY: fds 为两个元素的数组，pipe 成功时写入读端与写端
+        if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+            let _ = child.kill();
+            return;
+        }
+        // SAFETY: 读端由 pipe 新建，只交给 File 持有；之后启动的子进程（git 等）不继承读端
+        let mut output = unsafe {
+            libc::fcntl(fds[0], libc::F_SETFD, libc::FD_CLOEXEC);
+            File::from_raw_fd(fds[0])
+        };
+        let forwarder = std::thread::spawn(move || {
+            let mut buffer = [0; 8192];
+            let mut open = true;
+            loop {
+                match output.read(&mut buffer) {
+                    Ok(0) | Err(_) => break,
+                    // 分页程序退出后继续读取并丢弃，写入方不会因管道关闭而出错
+                    Ok(n) => open = open && input.write_all(&buffer[..n]).is_ok(),
+                }
+            }
+        });
+
+        let _ = io::stdout().flush();
+        let stderr = io::stderr().is_terminal();
+        // SAFETY: 写端由 pipe 新建；复制到标准输出与标准错误后关闭原描述符
+        unsafe {
+            libc::dup2(fds[1], io::stdout().as_raw_fd());
+            if stderr {
+                libc::dup2(fds[1], io::stderr().as_raw_fd());
+            }
+            libc::close(fds[1]);
+        }
+        *RUNNING.lock().unwrap() = Some(Running { child, forwarder, stderr });
+        // SAFETY: finish 为没有参数的 extern "C" 函数
+        unsafe {
+            libc::atexit(finish);
+        }
+    }
+
+    /// 进程退出时（包括 std::process::exit）关闭管道，等待分页程序退出
+    extern "C" fn finish() {
+        let Some(running) = RUNNING.lock().ok().and_then(|mut running| running.take()) else {
+            return;
+        };
+        let _ = io::stdout().flush();
+        // SAFETY: 以 /dev/null 替换指向管道写端的描述符，管道的写端全部关闭后转发线程读到 EOF
+        unsafe {
+            let null = libc::open(c"/dev/null".as_ptr(), libc::O_WRONLY);
+            if null >= 0 {
+                libc::dup2(null, io::stdout().as_raw_fd());
+                if running.stderr {
+                    libc::dup2(null, io::stderr().as_raw_fd());
+                }
+                libc::close(null);
+            }
+        }
+        let Running { mut child, forwarder, .. } = running;
+        let _ = forwarder.join();
+        let _ = child.wait();
+    }
+}
+
+/// 按需启动分页程序；disabled 为 --no-pager
+pub fn start(disabled: bool) {
+    if disabled {
+        return;
+    }
+    #[cfg(unix)]
+    imp::start();
+}
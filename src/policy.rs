@@ -0,0 +1,82 @@
+// 批量文件 Status 列到处理级别的映射策略
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+
+/// 命中后的处理级别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Warn,
+    Fail,
+}
+
+impl Severity {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warn => "warn",
+            Severity::Fail => "fail",
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Severity::Info => "ℹ️",
+            Severity::Warn => "⚠️",
+            Severity::Fail => "🚨",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Policy {
+    // 未在 status 中列出（或批量文件没有 Status 列）时使用的级别
+    #[serde(default = "default_severity")]
+    default: Severity,
+
+    #[serde(default)]
+    status: HashMap<String, Severity>,
+}
+
+fn default_severity() -> Severity {
+    Severity::Fail
+}
+
+impl Policy {
+    pub fn load(path: &str) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("无法读取策略文件 '{}'", path))?;
+        let policy: Policy = serde_yaml::from_str(&content)
+            .with_context(|| format!("解析策略文件 '{}' 失败", path))?;
+
+        // 预先规范化键，匹配时忽略大小写和前缀符号
+        Ok(Policy {
+            default: policy.default,
+            status: policy
+                .status
+                .into_iter()
+                .map(|(k, v)| (normalize_status(&k), v))
+                .collect(),
+        })
+    }
+
+    pub fn severity_for(&self, status: Option<&str>) -> Severity {
+        status
+            .and_then(|s| self.status.get(&normalize_status(s)))
+            .copied()
+            .unwrap_or(self.default)
+    }
+}
+
+// "⚠️ Active" 与 "active" 视为同一状态
+fn normalize_status(status: &str) -> String {
+    status
+        .trim()
+        .trim_start_matches(|c: char| !c.is_alphanumeric())
+        .trim()
+        .to_lowercase()
+}
@@ -0,0 +1,71 @@
+// 检查进度事件：命令行工具在 --log-format json 时把事件逐行写到标准错误（JSON Lines），
+// GUI 封装与 CI 插件据此实时渲染进度，不必解析面向人的控制台输出
+//
+// 每行一个 JSON 对象，event 字段为事件类型：
+//   {"event":"files_discovered","files":["apps/web/pnpm-lock.yaml","backend/pnpm-lock.yaml"]}
+//   {"event":"file_started","file":"apps/web/pnpm-lock.yaml"}
+//   {"event":"entries_checked","file":"apps/web/pnpm-lock.yaml","checked":120,"total":480,"findings":2}
+//   {"event":"file_finished","file":"apps/web/pnpm-lock.yaml","findings":3,"exit_code":1}
+// 标准错误中的其他行（RESULT 结论行、警告）不是事件，ProgressEvent::from_line 返回 None。
+// 嵌入方启动命令行工具后用 forward 读取它的标准错误，事件经回调或 channel 交给渲染方
+
+use serde::{Deserialize, Serialize};
+use std::io::BufRead;
+use std::sync::mpsc::Sender;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ProgressEvent {
+    /// 找到的全部待检查锁文件（--repo、压缩包与多个 --file），单个锁文件时不发送；
+    /// --dir 边遍历边检查，每找到一个锁文件发送一次，files 中只有这一个
+    FilesDiscovered { files: Vec<String> },
+    /// 开始检查一个锁文件
+    FileStarted { file: String },
+    /// 批量检查的进度：已检查的条目数、条目总数与目前的命中数
+    EntriesChecked { file: String, checked: usize, total: usize, findings: usize },
+    /// 一个锁文件检查完成，exit_code 为该锁文件单独检查时的退出码
+    FileFinished { file: String, findings: usize, exit_code: i32 },
+}
+
+impl ProgressEvent {
+    /// 解析标准错误中的一行，不是进度事件时返回 None
+    pub fn from_line(line: &str) -> Option<ProgressEvent> {
+        let line = line.trim();
+        if !line.starts_with('{') {
+            return None;
+        }
+        serde_json::from_str(line).ok()
+    }
+}
+
+/// 进度事件的接收方
+pub trait ProgressSink: Send + Sync {
+    fn emit(&self, event: &ProgressEvent);
+}
+
+impl<F: Fn(&ProgressEvent) + Send + Sync> ProgressSink for F {
+    fn emit(&self, event: &ProgressEvent) {
+        self(event)
+    }
+}
+
+/// 通过 channel 转发给渲染线程，接收端已关闭时丢弃事件
+impl ProgressSink for Sender<ProgressEvent> {
+    fn emit(&self, event: &ProgressEvent) {
+        let _ = self.send(event.clone());
+    }
+}
+
+/// 逐行读取 --log-format json 时命令行工具的标准错误，把进度事件交给 sink，
+/// 返回其余的行（RESULT 结论行、警告与错误信息）
+pub fn forward<R: BufRead>(reader: R, sink: &dyn ProgressSink) -> std::io::Result<Vec<String>> {
+    let mut others = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        match ProgressEvent::from_line(&line) {
+            Some(event) => sink.emit(&event),
+            None => others.push(line),
+        }
+    }
+    Ok(others)
+}
@@ -0,0 +1,72 @@
+// purl（Package URL）的解析：pkg:<type>/<namespace>/<name>@<version>?<qualifiers>#<subpath>
+//
+// npm 的作用域写在 namespace 中，@ 按规范编码为 %40（pkg:npm/%40scope/name@1.2.3），也接受未编码的写法。
+// 各段按百分号编码解码；qualifiers 与 subpath 不影响包名与版本，忽略
+
+/// 解析出的 purl
+#[derive(Debug, Clone, PartialEq)]
+pub struct Purl {
+    /// 类型，统一为小写（npm、cargo 等）
+    pub kind: String,
+    /// 包名，npm 的作用域包为 @scope/name
+    pub name: String,
+    pub version: Option<String>,
+}
+
+/// 百分号编码的解码，无效的编码原样保留
+fn decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = (bytes[i] == b'%')
+            .then(|| text.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match hex {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// 解析 purl，不是 pkg: 开头或缺少包名时返回 None
+pub fn parse(purl: &str) -> Option<Purl> {
+    let purl = purl.trim();
+    let scheme = purl.get(..4)?;
+    if !scheme.eq_ignore_ascii_case("pkg:") {
+        return None;
+    }
+    let rest = purl[4..].trim_start_matches('/');
+    let rest = rest.split(['?', '#']).next().unwrap_or(rest);
+    let (kind, path) = rest.split_once('/')?;
+    let path = path.trim_end_matches('/');
+
+    // 版本在最后一段的 @ 之后；作用域未编码时 @ 出现在 namespace 中，不是版本的分隔符
+    let last = path.rfind('/').map_or(0, |pos| pos + 1);
+    let (path, version) = match path[last..].find('@') {
+        Some(pos) if pos > 0 => (&path[..last + pos], Some(decode(&path[last + pos + 1..]))),
+        _ => (path, None),
+    };
+    let name = path.split('/').map(decode).collect::<Vec<_>>().join("/");
+    if name.is_empty() || name.ends_with('/') {
+        return None;
+    }
+    Some(Purl {
+        kind: kind.to_ascii_lowercase(),
+        name,
+        version: version.filter(|version| !version.is_empty()),
+    })
+}
+
+/// npm 的 purl 生成：作用域中的 @ 编码为 %40
+pub fn npm(name: &str) -> String {
+    format!("pkg:npm/{}", name.replace('@', "%40"))
+}
@@ -0,0 +1,98 @@
+// redact 子命令：导出内部包名与私有 registry 地址经过哈希处理的锁文件副本
+
+use anyhow::{Context, Result};
+use regex::{Captures, Regex};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeSet;
+use std::fs;
+
+use npm_package_check::lockfile::Lockfile;
+
+// 公共 registry 的主机名，其余 http(s) 地址视为私有 registry
+const PUBLIC_HOSTS: &[&str] = &["registry.npmjs.org", "registry.yarnpkg.com", "registry.npmmirror.com"];
+
+pub struct Redactor {
+    scopes: Option<Regex>,
+    urls: Regex,
+    salt: String,
+}
+
+impl Redactor {
+    /// scopes 为内部作用域（例如 @acme），其下的全部包名都会被哈希
+    pub fn new(scopes: &[String], salt: Option<&str>) -> Result<Self> {
+        let scopes: Vec<String> = scopes
+            .iter()
+            .map(|scope| regex::escape(scope.trim_start_matches('@')))
+            .collect();
+        let scopes = if scopes.is_empty() {
+            None
+        } else {
+            let pattern = format!(r"@({})/[a-z0-9._~-]+", scopes.join("|"));
+            Some(Regex::new(&pattern).context("无效的作用域")?)
+        };
+        Ok(Self {
+            scopes,
+            urls: Regex::new(r"(https?://)([^/\s'\x22,}]+)([^\s'\x22,}]*)").context("无效的 URL 表达式")?,
+            salt: salt.unwrap_or_default().to_string(),
+        })
+    }
+
+    fn hash(&self, value: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.salt.as_bytes());
+        hasher.update(value.as_bytes());
+        hasher.finalize().iter().take(4).map(|b| format!("{:02x}", b)).collect()
+    }
+
+    /// 替换文本中的内部包名与私有 registry 地址，保留原有结构以便副本仍可被解析
+    pub fn redact(&self, text: &str) -> String {
+        let text = match self.scopes {
+            Some(ref scopes) => scopes.replace_all(text, |caps: &Captures| {
+                format!("@scope-{}/pkg-{}", self.hash(&caps[1]), self.hash(&caps[0]))
+            }),
+            None => text.into(),
+        };
+        // 私有 registry 的路径中通常也包含包名（tarball 文件名），一并哈希
+        self.urls
+            .replace_all(&text, |caps: &Captures| {
+                let host = &caps[2];
+                let hostname = host.split(':').next().unwrap_or(host);
+                if PUBLIC_HOSTS.contains(&hostname) {
+                    caps[0].to_string()
+                } else {
+                    format!("{}host-{}.invalid/{}", &caps[1], self.hash(host), self.hash(&caps[3]))
+                }
+            })
+            .into_owned()
+    }
+}
+
+pub fn run_redact(
+    content: &str,
+    lock_data: &Lockfile,
+    redactor: &Redactor,
+    list: bool,
+    output: Option<&str>,
+) -> Result<()> {
+    let redacted = if list {
+        // 规范化的包列表：每行一个 name@version，按字母排序去重
+        let packages: BTreeSet<String> = lock_data
+            .packages()
+            .map(|record| redactor.redact(&format!("{}@{}", record.name, record.version)))
+            .collect();
+        let mut text = packages.into_iter().collect::<Vec<_>>().join("\n");
+        text.push('\n');
+        text
+    } else {
+        redactor.redact(content)
+    };
+
+    match output {
+        Some(path) => {
+            fs::write(path, redacted).with_context(|| format!("无法写入文件 '{}'", path))?;
+            eprintln!("🔒 脱敏副本已写入: {}", path);
+        }
+        None => print!("{}", redacted),
+    }
+    Ok(())
+}
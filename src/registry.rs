@@ -0,0 +1,202 @@
+// npm registry 查询
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde::de::{DeserializeOwned, IgnoredAny};
+use std::collections::BTreeMap;
+
+use crate::http::{self, Client};
+
+pub const DEFAULT_REGISTRY: &str = "https://registry.npmjs.org";
+pub const DEFAULT_DOWNLOADS_API: &str = "https://api.npmjs.org";
+
+// 周下载量低于该值视为小众包
+pub const OBSCURE_WEEKLY_DOWNLOADS: u64 = 1_000;
+
+/// registry 返回的包元数据（packument），只保留需要的字段
+#[derive(Debug, Deserialize)]
+pub struct Packument {
+    // 包被整体撤下时 versions 为空
+    #[serde(default)]
+    versions: BTreeMap<String, IgnoredAny>,
+
+    // 各版本的发布时间，另含 created/modified（整体撤下时还有 unpublished 对象）
+    #[serde(default)]
+    time: BTreeMap<String, serde_json::Value>,
+
+    #[serde(default)]
+    maintainers: Vec<IgnoredAny>,
+
+    #[serde(default)]
+    repository: Option<Repository>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Repository {
+    Url(String),
+    Object {
+        #[serde(default)]
+        url: Option<String>,
+    },
+}
+
+/// 锁定版本在 registry 上的可用状态
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Availability {
+    Published,
+    // 版本已被撤下（unpublish）
+    Unpublished,
+    // 包已被 npm 安全团队替换为 0.0.1-security 占位包
+    SecurityHeld,
+    // registry 中不存在该包
+    Missing,
+}
+
+impl Availability {
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Availability::Published => "正常发布",
+            Availability::Unpublished => "已撤下 (unpublished)",
+            Availability::SecurityHeld => "已被 npm 安全接管 (security holding)",
+            Availability::Missing => "registry 中不存在该包",
+        }
+    }
+}
+
+impl Packument {
+    pub fn availability(&self, version: &str) -> Availability {
+        if self.versions.contains_key(version) {
+            Availability::Published
+        } else if self.versions.keys().any(|v| v.ends_with("-security")) {
+            Availability::SecurityHeld
+        } else {
+            Availability::Unpublished
+        }
+    }
+
+    /// 最近一次发布版本的时间
+    pub fn last_release(&self) -> Option<DateTime<Utc>> {
+        self.time
+            .iter()
+            .filter(|(key, _)| key.as_str() != "created" && key.as_str() != "modified")
+            .filter_map(|(_, value)| value.as_str())
+            .filter_map(|value| DateTime::parse_from_rfc3339(value).ok())
+            .map(|date| date.with_timezone(&Utc))
+            .max()
+    }
+
+    /// 当前可安装的全部版本号
+    pub fn versions(&self) -> impl Iterator<Item = &str> {
+        self.versions.keys().map(String::as_str)
+    }
+
+    pub fn maintainer_count(&self) -> usize {
+        self.maintainers.len()
+    }
+
+    pub fn repository_url(&self) -> Option<&str> {
+        match self.repository.as_ref()? {
+            Repository::Url(url) => Some(url),
+            Repository::Object { url } => url.as_deref(),
+        }
+    }
+}
+
+pub struct Registry {
+    base: String,
+    client: Client,
+}
+
+impl Registry {
+    pub fn new(base: &str, client: &Client) -> Self {
+        Self {
+            base: base.trim_end_matches('/').to_string(),
+            client: client.clone(),
+        }
+    }
+
+    // 带作用域的包名需要将 / 编码，例如 @scope%2fname
+    fn package_path(name: &str) -> String {
+        name.replace('/', "%2f")
+    }
+
+    fn get_json<T: DeserializeOwned>(&self, url: &str) -> Result<Option<T>> {
+        http::get_json(&self.client, url, &[])
+    }
+
+    /// 查询包当前的 dist-tags（例如 latest、next）
+    pub fn dist_tags(&self, name: &str) -> Result<BTreeMap<String, String>> {
+        let url = format!("{}/-/package/{}/dist-tags", self.base, Self::package_path(name));
+        self.get_json(&url)?
+            .with_context(|| format!("registry 中不存在包 '{}'", name))
+    }
+
+    /// 获取包的完整元数据，包不存在时返回 None
+    pub fn packument(&self, name: &str) -> Result<Option<Packument>> {
+        let url = format!("{}/{}", self.base, Self::package_path(name));
+        self.get_json(&url)
+    }
+
+    /// 检查各个锁定版本在 registry 上是否仍可安装
+    pub fn availability(&self, name: &str, versions: &[String]) -> Result<Vec<(String, Availability)>> {
+        let packument = self.packument(name)?;
+        Ok(versions
+            .iter()
+            .map(|v| {
+                let availability = match packument {
+                    Some(ref p) => p.availability(v),
+                    None => Availability::Missing,
+                };
+                (v.clone(), availability)
+            })
+            .collect())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct DownloadPoint {
+    downloads: u64,
+}
+
+/// npm 下载量统计 API
+pub struct DownloadsApi {
+    base: String,
+    client: Client,
+}
+
+impl DownloadsApi {
+    pub fn new(base: &str, client: &Client) -> Self {
+        Self {
+            base: base.trim_end_matches('/').to_string(),
+            client: client.clone(),
+        }
+    }
+
+    /// 最近一周的下载量
+    pub fn weekly_downloads(&self, name: &str) -> Result<u64> {
+        let url = format!("{}/downloads/point/last-week/{}", self.base, name);
+        let point: DownloadPoint = http::get_json(&self.client, &url, &[])?
+            .with_context(|| format!("没有包 '{}' 的下载量数据", name))?;
+        Ok(point.downloads)
+    }
+}
+
+/// 下载量的展示文本，例如 "12,345/周 (广泛使用)"
+pub fn describe_downloads(downloads: u64) -> String {
+    let digits = downloads.to_string();
+    let mut grouped = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(c);
+    }
+    let scope = if downloads < OBSCURE_WEEKLY_DOWNLOADS {
+        "小众包"
+    } else {
+        "广泛使用"
+    };
+    format!("{}/周 ({})", grouped, scope)
+}
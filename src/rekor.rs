@@ -0,0 +1,56 @@
+// 在 Sigstore Rekor 透明日志中查找包 tarball 摘要对应的记录
+
+use anyhow::{Context, Result, bail};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use serde::Serialize;
+
+use crate::http::{self, Client};
+
+pub const DEFAULT_REKOR: &str = "https://rekor.sigstore.dev";
+
+#[derive(Serialize)]
+struct SearchIndex {
+    hash: String,
+}
+
+pub struct Rekor {
+    base: String,
+    client: Client,
+}
+
+impl Rekor {
+    pub fn new(base: &str, client: &Client) -> Self {
+        Self {
+            base: base.trim_end_matches('/').to_string(),
+            client: client.clone(),
+        }
+    }
+
+    /// 按锁文件中的 integrity（例如 sha512-xxx）查找记录，返回匹配的记录数
+    pub fn entries_for(&self, integrity: &str) -> Result<usize> {
+        let hash = integrity_to_hex(integrity)?;
+        let url = format!("{}/api/v1/index/retrieve", self.base);
+        let uuids: Vec<String> = http::post_json(&self.client, &url, &SearchIndex { hash })?;
+        Ok(uuids.len())
+    }
+}
+
+/// 将 SRI 格式的 integrity 转为 Rekor 检索使用的 "算法:十六进制摘要"
+fn integrity_to_hex(integrity: &str) -> Result<String> {
+    // 可能包含多个以空格分隔的摘要，优先使用 sha512
+    let digests: Vec<&str> = integrity.split_whitespace().collect();
+    let digest = digests
+        .iter()
+        .find(|d| d.starts_with("sha512-"))
+        .or_else(|| digests.iter().find(|d| d.starts_with("sha256-")))
+        .or_else(|| digests.iter().find(|d| d.starts_with("sha1-")));
+    let Some((algorithm, encoded)) = digest.and_then(|d| d.split_once('-')) else {
+        bail!("不支持的 integrity 格式: {}", integrity);
+    };
+    let bytes = STANDARD
+        .decode(encoded)
+        .with_context(|| format!("无法解码 integrity: {}", integrity))?;
+    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    Ok(format!("{}:{}", algorithm, hex))
+}
@@ -0,0 +1,64 @@
+// --repo：不检出仓库，通过 GitHub API 查找并下载仓库中的全部锁文件，逐一执行单包查询或批量检查
+//
+// 分支与标签先解析为提交 SHA，所有锁文件读取自同一个提交；目录树与文件内容分别来自
+// git/trees 与 git/blobs 接口，私有仓库需要 --github-token（或 GITHUB_TOKEN）。
+// 每个锁文件单独输出检查结果与结论行，最后汇总，任一锁文件未通过时以其中最大的退出码结束
+
+use anyhow::{Result, bail};
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::github::GitHub;
+use crate::http::Client;
+use crate::multi::{self, Summary};
+use crate::{Args, reject_local_options, scan};
+
+/// 拆分 owner/name[@ref]
+fn parse_spec(spec: &str) -> Option<(&str, &str, Option<&str>)> {
+    let (repo, git_ref) = match spec.split_once('@') {
+        Some((repo, git_ref)) => (repo, Some(git_ref).filter(|r| !r.is_empty())),
+        None => (spec, None),
+    };
+    let (owner, name) = repo.split_once('/')?;
+    if owner.is_empty() || name.is_empty() || name.contains('/') {
+        return None;
+    }
+    Some((owner, name.trim_end_matches(".git"), git_ref))
+}
+
+pub fn run_repo(args: &mut Args, spec: &str, github: &GitHub, client: &Client) -> Result<()> {
+    let Some((owner, name, git_ref)) = parse_spec(spec) else {
+        bail!("无效的仓库 '{}'，格式为 owner/name 或 owner/name@ref", spec);
+    };
+    multi::reject_options(args, "--repo")?;
+    reject_local_options(args, "远程锁文件")?;
+
+    let git_ref = match git_ref {
+        Some(git_ref) => git_ref.to_string(),
+        None => github.repo(owner, name)?.default_branch,
+    };
+    let sha = github.commit_sha(owner, name, &git_ref)?;
+    let tree = github.tree(owner, name, &sha)?;
+    if tree.truncated {
+        eprintln!("⚠️ 仓库文件过多，GitHub 返回的目录树不完整，可能遗漏部分锁文件");
+    }
+    let blobs: HashMap<&str, &str> =
+        tree.tree.iter().filter(|e| e.kind == "blob").map(|e| (e.path.as_str(), e.sha.as_str())).collect();
+    let paths: Vec<&str> = blobs.keys().copied().collect();
+    let lockfiles = scan::select_lockfiles(&paths, &args.exclude)?;
+    if lockfiles.is_empty() {
+        bail!("仓库 {}/{}@{} 中没有找到锁文件（{}）", owner, name, git_ref, scan::LOCKFILE_NAMES.join(", "));
+    }
+    let short_sha = &sha[..sha.len().min(7)];
+    println!("🐙 {}/{}@{}（{}）: 找到 {} 个锁文件\n", owner, name, git_ref, short_sha, lockfiles.len());
+
+    // 报告与使用统计中的锁文件名称
+    let labels: Vec<String> = lockfiles.iter().map(|path| format!("{}/{}@{}:{}", owner, name, short_sha, path)).collect();
+    let mut summary = Summary::new(args, labels.clone());
+    for (i, (path, label)) in lockfiles.iter().zip(&labels).enumerate() {
+        println!("📦 [{}/{}] {}", i + 1, lockfiles.len(), path);
+        let content = github.blob_text(owner, name, blobs[path])?;
+        summary.check(args, client, label, Path::new(path), &content, false)?;
+    }
+    summary.finish()
+}
@@ -0,0 +1,250 @@
+// JSON 格式的批量检查报告
+
+use anyhow::{Context, Result};
+use chrono::FixedOffset;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+
+use crate::collation;
+use crate::{BatchResult, CheckStatus, PackageFound};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JsonReport {
+    pub generated_at: String,
+    pub lockfile: String,
+    /// 全部命中条目的风险评分之和
+    #[serde(default)]
+    pub risk_score: u32,
+    pub results: Vec<JsonResult>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JsonResult {
+    pub package: String,
+    pub status: String,
+    pub expected_versions: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fixed_in: Vec<String>,
+    // 命中期望版本的实际版本，即需要处置的发现项
+    pub matched_versions: Vec<String>,
+    pub found: Vec<JsonFinding>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_status: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detection_date: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub advisories: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub severity: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exposure: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dist_tags: Option<BTreeMap<String, String>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unavailable: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weekly_downloads: Option<u64>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub rekor_entries: BTreeMap<String, usize>,
+    #[serde(default)]
+    pub risk_score: u32,
+    // 命中的指纹，用于在抑制文件中逐条抑制
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fingerprint: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suppressed: Option<JsonSuppression>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JsonSuppression {
+    pub justification: String,
+    pub expires: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JsonFinding {
+    pub location: String,
+    pub version: String,
+    pub dependency_type: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub specifier: String,
+    /// 被锁文件 overrides 覆盖时为 "选择器 → 版本规格"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub overridden: Option<String>,
+    /// patchedDependencies 中的补丁文件
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub patched: Option<String>,
+    /// 安装脚本是否执行：never、not_allowed、allowed、none、runs、unknown
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub install_scripts: Option<String>,
+    /// 锁定版本的来源：registry、git、directory、tarball、link
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolution: Option<String>,
+    /// 以别名安装时为 "别名 → 实际的包名"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
+}
+
+impl From<&PackageFound> for JsonFinding {
+    fn from(pkg: &PackageFound) -> Self {
+        Self {
+            location: pkg.location.id(),
+            version: pkg.version.clone(),
+            dependency_type: pkg.dependency_type.clone(),
+            specifier: pkg.specifier.clone(),
+            overridden: pkg.overridden.clone(),
+            patched: pkg.patched.clone(),
+            install_scripts: pkg.install_scripts.map(|scripts| scripts.key().to_string()),
+            resolution: pkg.resolution.as_ref().map(|resolution| resolution.kind().to_string()),
+            alias: pkg.alias_text(),
+        }
+    }
+}
+
+/// 规范化的 JSON 文本：对象的键按字典序排列，同一份输入多次运行得到逐字节相同的输出
+pub fn canonical_json<T: Serialize>(value: &T, pretty: bool) -> Result<String> {
+    // serde_json::Value 的对象以 BTreeMap 存储，转换一次即可让结构体字段与映射的键都有序
+    let value = serde_json::to_value(value)?;
+    let json = if pretty {
+        serde_json::to_string_pretty(&value)?
+    } else {
+        serde_json::to_string(&value)?
+    };
+    Ok(json)
+}
+
+impl JsonReport {
+    pub fn from_results(results: &[BatchResult], lockfile: &str, timezone: Option<FixedOffset>) -> Self {
+        Self {
+            generated_at: crate::dates::timestamp(timezone),
+            lockfile: lockfile.to_string(),
+            risk_score: crate::risk::total(results),
+            results: results.iter().map(JsonResult::from_result).collect(),
+        }
+    }
+
+    pub fn load(path: &str) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("无法读取报告文件 '{}'", path))?;
+        serde_json::from_str(&content).with_context(|| format!("解析报告文件 '{}' 失败", path))
+    }
+
+    pub fn write(&self, output: Option<&str>) -> Result<()> {
+        let json = canonical_json(self, true)?;
+        match output {
+            Some(path) => {
+                fs::write(path, json).with_context(|| format!("无法写入文件 '{}'", path))
+            }
+            None => {
+                println!("{}", json);
+                Ok(())
+            }
+        }
+    }
+}
+
+impl JsonResult {
+    fn from_result(result: &BatchResult) -> Self {
+        let package = &result.package;
+
+        let matched_versions = if result.status == CheckStatus::NotFound {
+            Vec::new()
+        } else {
+            crate::matched_versions(result)
+        };
+
+        Self {
+            package: package.name.clone(),
+            status: result.status.report_label().to_string(),
+            expected_versions: package.versions.clone(),
+            fixed_in: package.fixed_in.clone(),
+            matched_versions,
+            found: sorted_findings(&result.found_versions),
+            source_status: package.status.clone(),
+            detection_date: package.detection_date.clone(),
+            advisories: package.advisories.clone(),
+            severity: result.severity.map(|s| s.label().to_string()),
+            exposure: result.exposure.iter().map(|w| w.report_text()).collect(),
+            dist_tags: result.dist_tags.clone(),
+            unavailable: result
+                .unavailable
+                .iter()
+                .map(|(version, availability)| format!("{}: {}", version, availability.describe()))
+                .collect(),
+            weekly_downloads: result.weekly_downloads,
+            rekor_entries: result.rekor_entries.clone(),
+            risk_score: result.risk_score,
+            fingerprint: result.status.is_hit().then(|| crate::result_fingerprint(result)),
+            suppressed: result.suppressed.as_ref().map(|suppression| JsonSuppression {
+                justification: suppression.justification.clone(),
+                expires: suppression.expires.to_string(),
+            }),
+        }
+    }
+}
+
+/// 发现项按位置、依赖类型、版本排序，与锁文件中条目的书写顺序无关
+fn sorted_findings(found: &[PackageFound]) -> Vec<JsonFinding> {
+    let mut findings: Vec<JsonFinding> = found.iter().map(JsonFinding::from).collect();
+    findings.sort_by(|a, b| {
+        collation::compare(&a.location, &b.location)
+            .then_with(|| (&a.dependency_type, &a.version).cmp(&(&b.dependency_type, &b.version)))
+    });
+    findings
+}
+
+/// --result-file 写出的简短结论：CI 丢弃失败步骤的标准输出时，下游任务仍可读取
+#[derive(Debug, Serialize)]
+pub struct Verdict {
+    /// ok、failed（以非零状态结束）或 error（运行出错）
+    pub status: &'static str,
+    pub exit_code: i32,
+    pub lockfile: String,
+    pub total: usize,
+    /// 各检查状态的条目数
+    pub counts: BTreeMap<&'static str, usize>,
+    /// 风险评分（见 risk.rs），便于在多个项目之间排序
+    pub risk_score: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub report: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl Verdict {
+    pub fn from_results(results: &[BatchResult], lockfile: &str, report: Option<&str>, exit_code: i32) -> Self {
+        let mut counts = BTreeMap::new();
+        for result in results {
+            *counts.entry(result.status.key()).or_insert(0) += 1;
+        }
+        Self {
+            status: if exit_code == 0 { "ok" } else { "failed" },
+            exit_code,
+            lockfile: lockfile.to_string(),
+            total: results.len(),
+            counts,
+            risk_score: crate::risk::total(results),
+            report: report.map(str::to_string),
+            error: None,
+        }
+    }
+
+    pub fn from_error(error: &anyhow::Error, lockfile: &str) -> Self {
+        Self {
+            status: "error",
+            exit_code: 1,
+            lockfile: lockfile.to_string(),
+            total: 0,
+            counts: BTreeMap::new(),
+            risk_score: 0,
+            report: None,
+            error: Some(format!("{:#}", error)),
+        }
+    }
+
+    pub fn write(&self, path: &str) -> Result<()> {
+        let json = canonical_json(self, true)?;
+        fs::write(path, json).with_context(|| format!("无法写入结果文件 '{}'", path))
+    }
+}
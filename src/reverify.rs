@@ -0,0 +1,109 @@
+// reverify 子命令：对照当前锁文件复核历史报告中的发现项
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::fs;
+
+use npm_package_check::lockfile::Lockfile;
+
+use crate::collation;
+use crate::find_package_in_lock;
+use crate::report::JsonReport;
+
+#[derive(Debug, Serialize)]
+struct ProgressReport {
+    source_report: String,
+    source_generated_at: String,
+    reverified_at: String,
+    lockfile: String,
+    total: usize,
+    fixed: usize,
+    findings: Vec<ReverifiedFinding>,
+}
+
+#[derive(Debug, Serialize)]
+struct ReverifiedFinding {
+    package: String,
+    version: String,
+    state: &'static str,
+    // 仍存在时的当前位置
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    locations: Vec<String>,
+}
+
+pub fn run_reverify(
+    lock_data: &Lockfile,
+    lockfile: &str,
+    report_path: &str,
+    output: Option<&str>,
+) -> Result<()> {
+    let report = JsonReport::load(report_path)?;
+    let index = lock_data.index();
+    let mut findings = Vec::new();
+
+    for result in &report.results {
+        if result.matched_versions.is_empty() {
+            continue;
+        }
+        let current = find_package_in_lock(&index, &result.package);
+
+        for version in &result.matched_versions {
+            let mut locations: Vec<_> = current
+                .iter()
+                .filter(|p| &p.version == version)
+                .map(|p| format!("{} ({})", p.location.id(), p.dependency_type))
+                .collect();
+            collation::sort_by_key(&mut locations, |location| location);
+            findings.push(ReverifiedFinding {
+                package: result.package.clone(),
+                version: version.clone(),
+                state: if locations.is_empty() { "fixed" } else { "still-present" },
+                locations,
+            });
+        }
+    }
+
+    let fixed = findings.iter().filter(|f| f.state == "fixed").count();
+    let total = findings.len();
+
+    println!("🔁 复核报告: {} (生成于 {})", report_path, report.generated_at);
+    for finding in &findings {
+        if finding.locations.is_empty() {
+            println!("✅ 已修复: {} @ {}", finding.package, finding.version);
+        } else {
+            println!(
+                "❌ 仍存在: {} @ {} ({})",
+                finding.package,
+                finding.version,
+                finding.locations.join("; ")
+            );
+        }
+    }
+
+    println!();
+    println!("📈 修复进度: {}/{}", fixed, total);
+    if total > 0 {
+        println!("   完成率: {:.0}%", fixed as f64 * 100.0 / total as f64);
+    }
+
+    if let Some(output) = output {
+        let progress = ProgressReport {
+            source_report: report_path.to_string(),
+            source_generated_at: report.generated_at.clone(),
+            reverified_at: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            lockfile: lockfile.to_string(),
+            total,
+            fixed,
+            findings,
+        };
+        fs::write(output, crate::report::canonical_json(&progress, true)?)
+            .with_context(|| format!("无法写入文件 '{}'", output))?;
+        println!("\n📊 复核结果已写入: {}", output);
+    }
+
+    if fixed < total {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
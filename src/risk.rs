@@ -0,0 +1,77 @@
+// 批量检查的风险评分：按命中的处理级别、是否随生产环境安装、直接还是间接依赖加权，
+// 汇总为每个锁文件一个数值，便于在多个项目之间排序
+//
+// 每个命中条目（找到、部分匹配、已有修复版本）的得分 = 级别权重 × 可达性权重 × 深度权重，
+// 锁文件的得分为全部命中条目之和：
+//   级别     fail 10、warn 5、info 1（未指定策略文件时按 fail 计）
+//   可达性   存在生产依赖链 3、仅通过开发依赖引入 1
+//   深度     被 importer 直接引用 2、仅为间接依赖 1
+
+use std::collections::HashSet;
+
+use npm_package_check::lockfile::Lockfile;
+
+use crate::policy::Severity;
+use crate::{BatchResult, why};
+
+/// 随生产环境安装的包 (包名, 版本号)
+pub struct Reach {
+    production: HashSet<(String, String)>,
+    /// 锁文件中没有 importer（例如旁边没有 package.json 的 yarn.lock）时无法区分，一律按生产依赖计
+    known: bool,
+}
+
+impl Reach {
+    pub fn new(lock: &Lockfile) -> Self {
+        let mut production = HashSet::new();
+        for importer in lock.importers.keys() {
+            for key in why::shortest_chains(lock, importer, false).keys() {
+                if let Some((name, version)) = why::node_package(lock, key) {
+                    production.insert((name, version));
+                }
+            }
+        }
+        Self {
+            production,
+            known: !lock.importers.is_empty(),
+        }
+    }
+}
+
+fn severity_weight(severity: Severity) -> u32 {
+    match severity {
+        Severity::Fail => 10,
+        Severity::Warn => 5,
+        Severity::Info => 1,
+    }
+}
+
+/// 单个检查结果的得分，未命中时为 0
+pub fn score(result: &BatchResult, reach: &Reach) -> u32 {
+    if !result.status.is_hit() {
+        return 0;
+    }
+    let versions = crate::matched_versions(result);
+    let matched: Vec<_> = result
+        .found_versions
+        .iter()
+        .filter(|p| versions.contains(&p.version))
+        .collect();
+
+    let direct = matched.iter().any(|p| p.location.is_importer());
+    let production = !reach.known
+        || matched.iter().any(|p| p.location.is_importer() && p.dependency_type != "devDependencies")
+        || versions
+            .iter()
+            .any(|version| reach.production.contains(&(result.package.name.clone(), version.clone())));
+
+    let severity = severity_weight(result.severity.unwrap_or(Severity::Fail));
+    let reach = if production { 3 } else { 1 };
+    let depth = if direct { 2 } else { 1 };
+    severity * reach * depth
+}
+
+/// 锁文件的总得分
+pub fn total(results: &[BatchResult]) -> u32 {
+    results.iter().map(|r| r.risk_score).sum()
+}
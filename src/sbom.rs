@@ -0,0 +1,325 @@
+// sbom 子命令：对照 CycloneDX / SPDX 格式的 SBOM 检查锁文件中的组件与版本是否一致
+//
+// CycloneDX 读取 JSON 格式；SPDX 读取 JSON 与 tag-value（SPDXVersion: SPDX-2.3 开头的文本）两种格式
+//
+// --batch-sbom 以 SBOM 中的组件为批量模式的检查清单：每个组件一个条目，期望版本为 SBOM 中列出的版本（精确匹配），
+// 组件缺失或版本不一致按批量模式的判定输出；锁文件中存在但 SBOM 未列出的组件与版本在结果之后单独列出
+
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fs;
+
+use npm_package_check::lockfile::{Lockfile, Resolution};
+use npm_package_check::purl;
+
+use crate::BatchPackage;
+use crate::matching::MatchMode;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CycloneDx {
+    #[serde(default)]
+    components: Vec<CycloneDxComponent>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CycloneDxComponent {
+    name: String,
+    #[serde(default)]
+    group: Option<String>,
+    #[serde(default)]
+    version: Option<String>,
+    #[serde(default)]
+    purl: Option<String>,
+    // 嵌套的子组件
+    #[serde(default)]
+    components: Vec<CycloneDxComponent>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Spdx {
+    #[serde(default)]
+    packages: Vec<SpdxPackage>,
+    #[serde(default)]
+    document_describes: Vec<String>,
+    #[serde(default)]
+    relationships: Vec<SpdxRelationship>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SpdxRelationship {
+    spdx_element_id: String,
+    relationship_type: String,
+    related_spdx_element: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SpdxPackage {
+    #[serde(default, rename = "SPDXID")]
+    spdx_id: Option<String>,
+    name: String,
+    #[serde(default)]
+    version_info: Option<String>,
+    #[serde(default)]
+    external_refs: Vec<SpdxExternalRef>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SpdxExternalRef {
+    reference_type: String,
+    reference_locator: String,
+}
+
+/// 包名 → 版本集合
+type Inventory = BTreeMap<String, BTreeSet<String>>;
+
+/// 读取 SBOM 中的 npm 组件，按内容自动识别 CycloneDX 与 SPDX
+fn load_sbom(path: &str) -> Result<Inventory> {
+    let content = fs::read_to_string(path).with_context(|| format!("无法读取 SBOM 文件 '{}'", path))?;
+    let content = content.trim_start_matches('\u{feff}');
+    if !content.trim_start().starts_with('{') {
+        if !content.lines().any(|line| line.trim_start().starts_with("SPDXVersion:")) {
+            bail!("无法识别的 SBOM 格式：'{}' 既不是 JSON，也不是 SPDX tag-value 格式", path);
+        }
+        let (packages, described) = parse_tag_value(content);
+        return Ok(spdx_inventory(&packages, &described));
+    }
+    let value: serde_json::Value =
+        serde_json::from_str(content).with_context(|| format!("解析 SBOM 文件 '{}' 失败", path))?;
+
+    let mut inventory = Inventory::new();
+    if value.get("bomFormat").and_then(|v| v.as_str()) == Some("CycloneDX") {
+        let bom: CycloneDx = serde_json::from_value(value)?;
+        let mut stack: Vec<&CycloneDxComponent> = bom.components.iter().collect();
+        while let Some(component) = stack.pop() {
+            stack.extend(&component.components);
+            let entry = match component.purl.as_deref() {
+                Some(purl) => parse_npm_purl(purl),
+                None => component.version.as_ref().map(|version| {
+                    let name = match component.group {
+                        Some(ref group) if !group.is_empty() => format!("{}/{}", group, component.name),
+                        _ => component.name.clone(),
+                    };
+                    (name, version.clone())
+                }),
+            };
+            if let Some((name, version)) = entry {
+                inventory.entry(name).or_default().insert(version);
+            }
+        }
+    } else if value.get("spdxVersion").is_some() {
+        let doc: Spdx = serde_json::from_value(value)?;
+        let mut described: HashSet<String> = doc.document_describes.into_iter().collect();
+        described.extend(
+            doc.relationships
+                .into_iter()
+                .filter(|r| r.spdx_element_id == "SPDXRef-DOCUMENT" && r.relationship_type == "DESCRIBES")
+                .map(|r| r.related_spdx_element),
+        );
+        inventory = spdx_inventory(&doc.packages, &described);
+    } else {
+        bail!("无法识别的 SBOM 格式：'{}' 既不是 CycloneDX 也不是 SPDX", path);
+    }
+
+    Ok(inventory)
+}
+
+/// SPDX 文档描述的包（项目本身，相当于 CycloneDX 的 metadata.component）不是依赖，不参与比对
+fn spdx_inventory(packages: &[SpdxPackage], described: &HashSet<String>) -> Inventory {
+    let mut inventory = Inventory::new();
+    for package in packages {
+        if package.spdx_id.as_ref().is_some_and(|id| described.contains(id)) {
+            continue;
+        }
+        let purl = package
+            .external_refs
+            .iter()
+            .find(|r| r.reference_type == "purl")
+            .map(|r| r.reference_locator.as_str());
+        let entry = match purl {
+            Some(purl) => parse_npm_purl(purl),
+            None => package.version_info.as_ref().map(|v| (package.name.clone(), v.clone())),
+        };
+        if let Some((name, version)) = entry {
+            inventory.entry(name).or_default().insert(version);
+        }
+    }
+    inventory
+}
+
+/// SPDX tag-value 格式中的包与文档描述的包：每个 PackageName 开始一个包，读取 SPDXID、PackageVersion 与 ExternalRef；
+/// <text>…</text> 包围的多行值（许可证全文、注释等）跳过，其中形如 "Tag: value" 的行不会被误读
+fn parse_tag_value(content: &str) -> (Vec<SpdxPackage>, HashSet<String>) {
+    let mut packages: Vec<SpdxPackage> = Vec::new();
+    let mut described = HashSet::new();
+    let mut in_text = false;
+    for line in content.lines() {
+        if in_text {
+            in_text = !line.contains("</text>");
+            continue;
+        }
+        let Some((tag, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        if value.starts_with("<text>") {
+            in_text = !value.contains("</text>");
+            continue;
+        }
+        match tag.trim() {
+            "PackageName" => packages.push(SpdxPackage {
+                spdx_id: None,
+                name: value.to_string(),
+                version_info: None,
+                external_refs: Vec::new(),
+            }),
+            // 文档本身的 SPDXID 出现在第一个 PackageName 之前
+            "SPDXID" => {
+                if let Some(package) = packages.last_mut() {
+                    package.spdx_id = Some(value.to_string());
+                }
+            }
+            // Relationship: SPDXRef-DOCUMENT DESCRIBES SPDXRef-app
+            "Relationship" => {
+                if let ["SPDXRef-DOCUMENT", "DESCRIBES", element] = value.split_whitespace().collect::<Vec<_>>()[..] {
+                    described.insert(element.to_string());
+                }
+            }
+            "PackageVersion" => {
+                if let Some(package) = packages.last_mut() {
+                    package.version_info = Some(value.to_string());
+                }
+            }
+            // ExternalRef: PACKAGE-MANAGER purl pkg:npm/lodash@4.17.21
+            "ExternalRef" => {
+                let mut fields = value.split_whitespace().skip(1);
+                if let (Some(package), Some(reference_type), Some(reference_locator)) =
+                    (packages.last_mut(), fields.next(), fields.next())
+                {
+                    package.external_refs.push(SpdxExternalRef {
+                        reference_type: reference_type.to_string(),
+                        reference_locator: reference_locator.to_string(),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+    (packages, described)
+}
+
+/// 带版本的 npm purl 对应的包名与版本，其他 purl 返回 None
+fn parse_npm_purl(purl: &str) -> Option<(String, String)> {
+    let purl = purl::parse(purl).filter(|purl| purl.kind == "npm")?;
+    Some((purl.name, purl.version?))
+}
+
+/// 锁文件中实际安装的组件，不含 workspace 内部链接与本地目录
+fn lockfile_inventory(lock_data: &Lockfile) -> Inventory {
+    let mut inventory = Inventory::new();
+    for record in lock_data.packages() {
+        let local = matches!(record.resolution, Some(Resolution::Link { .. } | Resolution::Directory { .. }))
+            || record.version.starts_with("link:")
+            || record.version.starts_with("file:");
+        if !local {
+            inventory.entry(record.name).or_default().insert(record.version);
+        }
+    }
+    inventory
+}
+
+pub fn run_sbom(lock_data: &Lockfile, sbom_path: &str, verbose: bool) -> Result<()> {
+    let sbom = load_sbom(sbom_path)?;
+    let locked = lockfile_inventory(lock_data);
+
+    let mut missing_in_sbom = Vec::new();
+    let mut missing_in_lock = Vec::new();
+    let mut drifted = Vec::new();
+
+    for (name, versions) in &locked {
+        match sbom.get(name) {
+            None => missing_in_sbom.push(format!("{}@{}", name, join(versions))),
+            Some(sbom_versions) if sbom_versions != versions => {
+                drifted.push(format!("{}: 锁文件 {} / SBOM {}", name, join(versions), join(sbom_versions)))
+            }
+            Some(_) => {}
+        }
+    }
+    for (name, versions) in &sbom {
+        if !locked.contains_key(name) {
+            missing_in_lock.push(format!("{}@{}", name, join(versions)));
+        }
+    }
+
+    println!("📦 SBOM 比对: 锁文件 {} 个组件，SBOM {} 个组件\n", locked.len(), sbom.len());
+    print_section("🔀 版本不一致", &drifted);
+    print_section("➕ 锁文件中存在但 SBOM 未列出", &missing_in_sbom);
+    print_section("➖ SBOM 中列出但锁文件中不存在", &missing_in_lock);
+
+    if drifted.is_empty() && missing_in_sbom.is_empty() && missing_in_lock.is_empty() {
+        println!("✅ SBOM 与锁文件一致");
+        return Ok(());
+    }
+    if verbose {
+        println!("一致的组件: {}", locked.len() - drifted.len() - missing_in_sbom.len());
+    }
+    std::process::exit(1);
+}
+
+/// --batch-sbom 的检查清单
+pub fn batch_packages(sbom_path: &str) -> Result<Vec<BatchPackage>> {
+    Ok(load_sbom(sbom_path)?
+        .into_iter()
+        .map(|(name, versions)| BatchPackage {
+            name,
+            versions: versions.into_iter().collect(),
+            status: None,
+            detection_date: None,
+            fixed_in: Vec::new(),
+            advisories: Vec::new(),
+            // SBOM 中的版本是确切的版本号，不受 --match-mode 影响
+            match_mode: MatchMode::Exact,
+        })
+        .collect())
+}
+
+/// 锁文件中存在但 SBOM（即检查清单）未列出的组件与版本，name@version
+pub fn unlisted(lock_data: &Lockfile, packages: &[BatchPackage]) -> Vec<String> {
+    let listed: BTreeSet<(&str, &str)> = packages
+        .iter()
+        .flat_map(|package| package.versions.iter().map(|version| (package.name.as_str(), version.as_str())))
+        .collect();
+    lockfile_inventory(lock_data)
+        .into_iter()
+        .flat_map(|(name, versions)| versions.into_iter().map(move |version| (name.clone(), version)))
+        .filter(|(name, version)| !listed.contains(&(name.as_str(), version.as_str())))
+        .map(|(name, version)| format!("{}@{}", name, version))
+        .collect()
+}
+
+pub fn print_unlisted(unlisted: &[String]) {
+    if !unlisted.is_empty() {
+        println!();
+    }
+    print_section("➕ 锁文件中存在但 SBOM 未列出", unlisted);
+}
+
+fn print_section(title: &str, items: &[String]) {
+    if items.is_empty() {
+        return;
+    }
+    println!("{} ({}):", title, items.len());
+    for item in items {
+        println!("   - {}", item);
+    }
+    println!();
+}
+
+fn join(versions: &BTreeSet<String>) -> String {
+    versions.iter().cloned().collect::<Vec<_>>().join(", ")
+}
@@ -0,0 +1,264 @@
+// 在目录中查找锁文件：遵循 .gitignore，默认跳过 node_modules，支持 --exclude 排除
+//
+// 目录树由多个线程并行遍历，在网络文件系统上大型 monorepo 的遍历耗时主要在逐个目录的元数据请求，
+// 并行可以明显缩短等待；每找到一个锁文件就立即交给调用方处理，不必等整个目录树遍历完
+//
+// 默认不跟随符号链接（指向锁文件的链接同样跳过），避免检查来源不可信的目录时读到目录之外的文件；
+// --follow-symlinks 时跟随指向目录内部的链接，指向目录之外的链接与失效的链接仍然跳过
+//
+// 精简构建（未启用部分锁文件格式的 cargo feature）同样会找到这些格式的锁文件：与支持的锁文件同时存在时跳过并提示，
+// 只有不支持的锁文件时报错，列出找到的格式与需要启用的 feature，而不是报告“没有找到锁文件”
+
+use anyhow::{Context, Result, bail};
+use ignore::overrides::OverrideBuilder;
+use ignore::{WalkBuilder, WalkState};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
+
+use npm_package_check::lockfile::LockfileFormat;
+
+use crate::collation;
+
+/// 支持的锁文件名，由各锁文件格式的文件名汇总（包括当前构建未启用的格式，找到后再按 feature 跳过或报错）
+pub static LOCKFILE_NAMES: LazyLock<Vec<&'static str>> = LazyLock::new(|| {
+    LockfileFormat::ALL
+        .iter()
+        .flat_map(|format| format.ecosystem().file_names())
+        .copied()
+        .collect()
+});
+
+/// 递归查找 root 下的锁文件，按路径排序。
+/// 同一目录中同时存在 npm-shrinkwrap.json 与 package-lock.json 时，与 npm 一样只使用 npm-shrinkwrap.json
+pub fn find_lockfiles(root: &Path, excludes: &[String], follow_symlinks: bool) -> Result<Vec<PathBuf>> {
+    let lockfiles = Mutex::new(Vec::new());
+    for_each_lockfile(root, excludes, follow_symlinks, |path| lockfiles.lock().unwrap().push(path))?;
+    let mut lockfiles = lockfiles.into_inner().unwrap();
+    collation::sort_paths(&mut lockfiles);
+    drop_unsupported(lockfiles)
+}
+
+/// 当前构建不支持的格式（对应的 cargo feature 未启用）
+fn unsupported_format(path: &Path) -> Option<LockfileFormat> {
+    LockfileFormat::from_file_name(path).filter(|format| !format.is_enabled())
+}
+
+fn describe_unsupported(path: &Path) -> String {
+    let format = unsupported_format(path).expect("已按格式筛选");
+    format!("{}（{}，cargo feature \"{}\"）", path.display(), format.name(), format.feature().unwrap_or_default())
+}
+
+/// 边遍历边检查时逐个判断：当前构建不支持时给出提示并返回 true
+pub fn skip_unsupported(path: &Path) -> bool {
+    if unsupported_format(path).is_none() {
+        return false;
+    }
+    eprintln!("⚠️ 跳过当前构建不支持的锁文件: {}", describe_unsupported(path));
+    true
+}
+
+/// 去掉当前构建不支持的锁文件；全部不支持时报错，说明需要启用的 feature
+pub fn drop_unsupported<P: AsRef<Path>>(lockfiles: Vec<P>) -> Result<Vec<P>> {
+    let (unsupported, supported): (Vec<P>, Vec<P>) =
+        lockfiles.into_iter().partition(|path| unsupported_format(path.as_ref()).is_some());
+    let describe = |path: &P| describe_unsupported(path.as_ref());
+    if supported.is_empty() && !unsupported.is_empty() {
+        let mut features: Vec<&str> = unsupported
+            .iter()
+            .filter_map(|path| unsupported_format(path.as_ref())?.feature())
+            .collect();
+        features.sort_unstable();
+        features.dedup();
+        let found: Vec<String> = unsupported.iter().map(|path| format!("   - {}", describe(path))).collect();
+        let enabled: Vec<&str> = LockfileFormat::ALL.iter().filter(|f| f.is_enabled()).map(|f| f.name()).collect();
+        bail!(
+            "只找到当前构建不支持的锁文件:\n{}\n当前构建支持: {}。请启用对应的 feature 后重新编译: cargo build --release --features {}",
+            found.join("\n"),
+            enabled.join("、"),
+            features.join(",")
+        );
+    }
+    for path in &unsupported {
+        eprintln!("⚠️ 跳过当前构建不支持的锁文件: {}", describe(path));
+    }
+    Ok(supported)
+}
+
+/// 锁文件不存在时的说明：同一目录中有其他格式的锁文件时一并列出，并说明如何检查或需要启用的 feature
+pub fn missing_lockfile_message(path: &Path) -> String {
+    let dir = path.parent().unwrap_or(Path::new(""));
+    let others: Vec<String> = LOCKFILE_NAMES
+        .iter()
+        .map(|name| dir.join(name))
+        .filter(|other| other.is_file())
+        .filter_map(|other| {
+            let format = LockfileFormat::from_file_name(&other)?;
+            Some(match format.feature().filter(|_| !format.is_enabled()) {
+                Some(feature) => format!("   - {}（{}，当前构建不支持，需要启用 cargo feature \"{}\"）", other.display(), format.name(), feature),
+                None => format!("   - {}（{}，可用 -f {} 检查）", other.display(), format.name(), other.display()),
+            })
+        })
+        .collect();
+    if others.is_empty() {
+        format!("文件 '{}' 不存在", path.display())
+    } else {
+        format!("文件 '{}' 不存在，同一目录中有其他格式的锁文件:\n{}", path.display(), others.join("\n"))
+    }
+}
+
+/// 并行遍历 root，每找到一个锁文件就调用一次 on_found（在遍历线程中调用，顺序不固定）。
+/// 遍历出错时停止并返回第一个错误
+pub fn for_each_lockfile<F>(root: &Path, excludes: &[String], follow_symlinks: bool, on_found: F) -> Result<()>
+where
+    F: Fn(PathBuf) + Sync,
+{
+    // 覆盖规则中以 ! 开头的 glob 表示排除
+    let mut overrides = OverrideBuilder::new(root);
+    for pattern in excludes {
+        overrides
+            .add(&format!("!{}", pattern))
+            .with_context(|| format!("无效的排除规则 '{}'", pattern))?;
+    }
+    let overrides = overrides.build().context("构建排除规则失败")?;
+
+    let canonical_root = root.canonicalize().with_context(|| format!("无法访问目录 '{}'", root.display()))?;
+    let walker = WalkBuilder::new(root)
+        .overrides(overrides)
+        // 不在 git 仓库中时同样遵循 .gitignore
+        .require_git(false)
+        .follow_links(follow_symlinks)
+        .filter_entry(move |entry| {
+            entry.file_name() != "node_modules" && (!follow_symlinks || link_within(entry.path(), &canonical_root))
+        })
+        .build_parallel();
+
+    let error = Mutex::new(None);
+    walker.run(|| {
+        Box::new(|entry| {
+            let entry = match entry {
+                Ok(entry) => entry,
+                // 跟随符号链接时，指向上级目录的链接形成的循环只跳过该链接
+                Err(e) if is_loop(&e) => {
+                    eprintln!("⚠️ 跳过形成循环的符号链接: {}", e);
+                    return WalkState::Continue;
+                }
+                Err(e) => {
+                    error.lock().unwrap().get_or_insert(e);
+                    return WalkState::Quit;
+                }
+            };
+            let is_file = entry.file_type().is_some_and(|t| t.is_file());
+            if is_file && LOCKFILE_NAMES.iter().any(|name| entry.file_name() == *name) {
+                let path = entry.into_path();
+                if !shadowed_by_shrinkwrap(&path) {
+                    on_found(path);
+                }
+            }
+            WalkState::Continue
+        })
+    });
+
+    match error.into_inner().unwrap() {
+        Some(e) => Err(e).with_context(|| format!("遍历目录 '{}' 失败", root.display())),
+        None => Ok(()),
+    }
+}
+
+/// 不是符号链接，或链接的目标在 root 之内；指向 root 之外的链接给出警告
+pub fn link_within(path: &Path, root: &Path) -> bool {
+    if !path.is_symlink() {
+        return true;
+    }
+    match path.canonicalize() {
+        Ok(target) if target.starts_with(root) => true,
+        Ok(target) => {
+            eprintln!("⚠️ 跳过指向扫描目录之外的符号链接: {} -> {}", path.display(), target.display());
+            false
+        }
+        Err(_) => false,
+    }
+}
+
+/// glob 模式展开的路径按目录扫描的规则处理符号链接，root 为模式中第一个通配符之前的目录：
+/// 不跟随时跳过经过符号链接的路径，跟随时只保留目标在 root 之内的路径
+pub fn glob_path_allowed(path: &Path, root: &Path, follow_symlinks: bool) -> bool {
+    let through_link = path
+        .ancestors()
+        .take_while(|ancestor| *ancestor != root && !ancestor.as_os_str().is_empty())
+        .find(|ancestor| ancestor.is_symlink());
+    let Some(link) = through_link else {
+        return true;
+    };
+    if !follow_symlinks {
+        eprintln!("⚠️ 跳过符号链接（--follow-symlinks 跟随指向目录内部的链接）: {}", link.display());
+        return false;
+    }
+    match (path.canonicalize(), root.canonicalize()) {
+        (Ok(target), Ok(root)) if target.starts_with(&root) => true,
+        (Ok(target), Ok(_)) => {
+            eprintln!("⚠️ 跳过指向扫描目录之外的符号链接: {} -> {}", path.display(), target.display());
+            false
+        }
+        _ => false,
+    }
+}
+
+fn is_loop(error: &ignore::Error) -> bool {
+    match error {
+        ignore::Error::Loop { .. } => true,
+        ignore::Error::WithPath { err, .. } | ignore::Error::WithDepth { err, .. } | ignore::Error::WithLineNumber { err, .. } => {
+            is_loop(err)
+        }
+        _ => false,
+    }
+}
+
+/// 批量输入目录（例如 OSV 公告库的检出）中的全部 .json 文件，按路径排序；与查找锁文件相同，遵循 .gitignore、不跟随符号链接
+pub fn json_files(root: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in WalkBuilder::new(root).require_git(false).build() {
+        let entry = entry.with_context(|| format!("遍历目录 '{}' 失败", root.display()))?;
+        let is_file = entry.file_type().is_some_and(|t| t.is_file());
+        if is_file && entry.path().extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json")) {
+            files.push(entry.into_path());
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn shadowed_by_shrinkwrap(path: &Path) -> bool {
+    path.file_name().is_some_and(|name| name == "package-lock.json") && path.with_file_name("npm-shrinkwrap.json").is_file()
+}
+
+/// 从不在本地磁盘上的文件列表（GitHub 仓库的目录树、压缩包的条目）中选出锁文件，按路径排序。
+/// 规则与目录遍历相同：跳过 node_modules 与 --exclude 排除的路径，npm-shrinkwrap.json 优先于同一目录的 package-lock.json
+pub fn select_lockfiles<'a>(paths: &[&'a str], excludes: &[String]) -> Result<Vec<&'a str>> {
+    let mut overrides = OverrideBuilder::new("");
+    for pattern in excludes {
+        overrides
+            .add(&format!("!{}", pattern))
+            .with_context(|| format!("无效的排除规则 '{}'", pattern))?;
+    }
+    let overrides = overrides.build().context("构建排除规则失败")?;
+
+    let all: BTreeSet<&str> = paths.iter().copied().collect();
+    let shadowed = |path: &str| {
+        let dir = path.strip_suffix("package-lock.json");
+        dir.is_some_and(|dir| all.contains(format!("{}npm-shrinkwrap.json", dir).as_str()))
+    };
+    let mut lockfiles: Vec<&str> = all
+        .iter()
+        .copied()
+        .filter(|entry| {
+            let path = Path::new(entry);
+            path.file_name().is_some_and(|name| LOCKFILE_NAMES.iter().any(|lockfile| name == *lockfile))
+                && !path.components().any(|c| c.as_os_str() == "node_modules")
+                && !overrides.matched(path, false).is_ignore()
+                && !shadowed(entry)
+        })
+        .collect();
+    collation::sort_paths(&mut lockfiles);
+    drop_unsupported(lockfiles)
+}
@@ -0,0 +1,76 @@
+// selftest 子命令：检查内置样本与用户提供的锁文件能否被完整解析
+
+use anyhow::Result;
+use std::path::Path;
+
+use npm_package_check::fixtures::{self, Fixture};
+
+pub fn run_selftest(paths: &[String], verbose: bool) -> Result<()> {
+    let mut corpus = fixtures::bundled();
+    for path in paths {
+        corpus.extend(fixtures::load(Path::new(path))?);
+    }
+
+    println!("🧪 锁文件兼容性自检: {} 个样本\n", corpus.len());
+
+    let mut incomplete = 0;
+    for fixture in &corpus {
+        if !report_fixture(fixture, verbose) {
+            incomplete += 1;
+        }
+    }
+
+    let (total, mismatches) = fixtures::check_package_keys();
+    let icon = if mismatches.is_empty() { "✅" } else { "⚠️" };
+    println!("{} 节点 key 解析样本: {}/{} 条符合期望", icon, total - mismatches.len(), total);
+    for mismatch in &mismatches {
+        println!("   - {}", mismatch);
+    }
+    if !mismatches.is_empty() {
+        incomplete += 1;
+    }
+
+    println!();
+    if incomplete == 0 {
+        println!("✅ 全部 {} 个样本均被完整解析", corpus.len());
+        Ok(())
+    } else {
+        println!("⚠️ {} 个样本未被完整解析，相关条目的检查结果可能不可靠", incomplete);
+        std::process::exit(1);
+    }
+}
+
+// 输出单个样本的覆盖率，返回是否完整解析
+fn report_fixture(fixture: &Fixture, verbose: bool) -> bool {
+    let coverage = match fixtures::coverage(fixture) {
+        Ok(coverage) => coverage,
+        Err(e) => {
+            println!("❌ {}: 解析失败: {:#}", fixture.name, e);
+            return false;
+        }
+    };
+
+    let icon = if coverage.is_complete() { "✅" } else { "⚠️" };
+    println!(
+        "{} {} (lockfileVersion {}): {}/{} 条目 ({:.1}%)",
+        icon,
+        fixture.name,
+        coverage.lockfile_version,
+        coverage.understood,
+        coverage.entries,
+        coverage.percent()
+    );
+
+    if !coverage.unknown_sections.is_empty() {
+        println!("   未识别的节点: {}", coverage.unknown_sections.join(", "));
+    }
+    let shown = if verbose { coverage.unparsed.len() } else { coverage.unparsed.len().min(5) };
+    for entry in &coverage.unparsed[..shown] {
+        println!("   - {}", entry);
+    }
+    if shown < coverage.unparsed.len() {
+        println!("   … 另有 {} 条，使用 --verbose 查看全部", coverage.unparsed.len() - shown);
+    }
+
+    coverage.is_complete()
+}
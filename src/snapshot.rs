@@ -0,0 +1,223 @@
+// snapshots 节点中依赖值的解析
+//
+// 依赖值有多种写法：
+//   4.1.0                          普通版本
+//   4.1.0(react@18.3.1)            带 peer 依赖后缀（v6/v9）
+//   4.1.0_react@18.3.1             带 peer 依赖后缀（v5）
+//   foo@1.2.3 / npm:foo@1.2.3      别名，实际安装的是 foo@1.2.3（v9）
+//   /foo@1.2.3 / /foo/1.2.3        别名的旧写法（v6 / v5）
+//   link:../x / file:../x          本地链接与本地目录
+
+use crate::package_key;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DepValue {
+    Version {
+        version: String,
+    },
+    Alias {
+        name: String,
+        version: String,
+    },
+    Link(String),
+    File(String),
+}
+
+impl DepValue {
+    /// 实际安装的包名：别名返回目标包名，其余返回依赖键本身
+    pub fn resolved_name<'a>(&'a self, key: &'a str) -> &'a str {
+        match self {
+            DepValue::Alias { name, .. } => name,
+            _ => key,
+        }
+    }
+
+    /// 别名安装（npm:preact@10）时的依赖键，即别名本身；依赖键与目标包名相同时返回 None
+    pub fn alias<'a>(&self, key: &'a str) -> Option<&'a str> {
+        match self {
+            DepValue::Alias { name, .. } if name != key => Some(key),
+            _ => None,
+        }
+    }
+
+    /// 纯版本号，本地链接和目录没有版本号
+    pub fn version(&self) -> Option<&str> {
+        match self {
+            DepValue::Version { version } | DepValue::Alias { version, .. } => Some(version),
+            DepValue::Link(_) | DepValue::File(_) => None,
+        }
+    }
+}
+
+pub fn parse_dep_value(value: &str) -> DepValue {
+    let value = value.trim();
+
+    if let Some(path) = value.strip_prefix("link:") {
+        return DepValue::Link(path.to_string());
+    }
+    if let Some(path) = value.strip_prefix("file:") {
+        return DepValue::File(path.to_string());
+    }
+
+    let value = value.strip_prefix("npm:").unwrap_or(value);
+    // 包名和版本号都不会包含 (，可以直接去掉括号形式的 peer 后缀
+    let value = match value.find('(') {
+        Some(pos) => &value[..pos],
+        None => value,
+    };
+
+    // v6/v5 的别名以 / 开头，与节点 key 的写法相同
+    if value.starts_with('/')
+        && let Some(package) = package_key::parse(value)
+    {
+        return DepValue::Alias {
+            name: package.name,
+            version: package.version,
+        };
+    }
+
+    // v5 的普通版本：4.1.0_react@18.3.1，_ 之前即是版本号
+    if let Some((version, _)) = value.split_once('_')
+        && version.starts_with(|c: char| c.is_ascii_digit())
+        && version.contains('.')
+        && !version.contains('@')
+    {
+        return DepValue::Version {
+            version: version.to_string(),
+        };
+    }
+
+    if let Some((name, version)) = split_name_version(value) {
+        return alias(name, version);
+    }
+
+    DepValue::Version {
+        version: value.to_string(),
+    }
+}
+
+fn alias(name: &str, version: &str) -> DepValue {
+    DepValue::Alias {
+        name: name.to_string(),
+        version: strip_legacy_peers(version).to_string(),
+    }
+}
+
+// v5 以 _ 连接 peer 依赖后缀；包名可以包含 _，所以只能对版本部分处理
+fn strip_legacy_peers(version: &str) -> &str {
+    match version.find('_') {
+        Some(pos) => &version[..pos],
+        None => version,
+    }
+}
+
+// 拆分 name@version，作用域包名开头的 @ 不作为分隔符
+fn split_name_version(value: &str) -> Option<(&str, &str)> {
+    let scoped = value.starts_with('@');
+    let pos = value[usize::from(scoped)..].find('@')? + usize::from(scoped);
+    let (name, version) = (&value[..pos], &value[pos + 1..]);
+    // 包名最多包含作用域后的一个 /，否则是 v5 的 name/version_peer@x 写法
+    let max_slashes = usize::from(scoped);
+    if name.is_empty() || name == "@" || version.is_empty() || name.matches('/').count() > max_slashes {
+        return None;
+    }
+    Some((name, version))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(version: &str) -> DepValue {
+        DepValue::Version { version: version.to_string() }
+    }
+
+    fn alias(name: &str, version: &str) -> DepValue {
+        DepValue::Alias {
+            name: name.to_string(),
+            version: version.to_string(),
+        }
+    }
+
+    #[test]
+    fn plain_versions() {
+        // v6 与 v9 的写法相同
+        assert_eq!(parse_dep_value("4.1.0"), version("4.1.0"));
+        assert_eq!(parse_dep_value("1.0.0-beta.1"), version("1.0.0-beta.1"));
+        assert_eq!(parse_dep_value(" 4.1.0 "), version("4.1.0"));
+    }
+
+    #[test]
+    fn peer_suffixes() {
+        assert_eq!(parse_dep_value("4.1.0(react@18.3.1)"), version("4.1.0"));
+        assert_eq!(parse_dep_value("4.1.0(@types/react@18.3.1)(react@18.3.1)"), version("4.1.0"));
+        assert_eq!(parse_dep_value("4.1.0(react-dom@18.3.1(react@18.3.1))"), version("4.1.0"));
+        // v5
+        assert_eq!(parse_dep_value("4.1.0_react@18.3.1"), version("4.1.0"));
+    }
+
+    #[test]
+    fn v6_aliases() {
+        assert_eq!(parse_dep_value("/preact@10.19.3"), alias("preact", "10.19.3"));
+        assert_eq!(parse_dep_value("/@scope/foo@1.2.3(react@18.3.1)"), alias("@scope/foo", "1.2.3"));
+        // v5
+        assert_eq!(parse_dep_value("/@scope/foo/1.2.3_react@18.3.1"), alias("@scope/foo", "1.2.3"));
+    }
+
+    #[test]
+    fn v9_aliases() {
+        assert_eq!(parse_dep_value("preact@10.19.3"), alias("preact", "10.19.3"));
+        assert_eq!(parse_dep_value("@scope/foo@1.2.3(react@18.3.1)"), alias("@scope/foo", "1.2.3"));
+        assert_eq!(parse_dep_value("npm:preact@10.19.3"), alias("preact", "10.19.3"));
+        assert_eq!(parse_dep_value("npm:@scope/foo@1.2.3"), alias("@scope/foo", "1.2.3"));
+    }
+
+    #[test]
+    fn local_paths() {
+        assert_eq!(parse_dep_value("link:../packages/ui"), DepValue::Link("../packages/ui".to_string()));
+        assert_eq!(parse_dep_value("file:../vendor/foo"), DepValue::File("../vendor/foo".to_string()));
+        assert_eq!(parse_dep_value("link:../packages/ui").version(), None);
+    }
+
+    #[test]
+    fn resolved_names() {
+        let value = parse_dep_value("npm:preact@10.19.3");
+        assert_eq!(value.resolved_name("react"), "preact");
+        assert_eq!(value.alias("react"), Some("react"));
+        assert_eq!(parse_dep_value("4.1.0").resolved_name("react"), "react");
+        assert_eq!(parse_dep_value("preact@10.19.3").alias("preact"), None);
+    }
+
+    /// 取出 fixture 中某个 importer 依赖的 version 字段
+    fn importer_value(lockfile: &str, importer: &str, dep: &str) -> DepValue {
+        let doc: serde_yaml::Value = serde_yaml::from_str(lockfile).unwrap();
+        let importer = &doc["importers"][importer];
+        let entry = ["dependencies", "devDependencies", "optionalDependencies"]
+            .iter()
+            .find_map(|field| importer[*field].get(dep))
+            .unwrap();
+        parse_dep_value(entry["version"].as_str().unwrap())
+    }
+
+    #[test]
+    fn v6_fixture() {
+        let lockfile = include_str!("../fixtures/pnpm-v6.yaml");
+        assert_eq!(importer_value(lockfile, ".", "lodash"), version("4.17.20"));
+        assert_eq!(importer_value(lockfile, "packages/ui", "@ant-design/icons"), version("4.8.3"));
+        assert_eq!(
+            importer_value(lockfile, "packages/ui", "shared"),
+            DepValue::Link("../shared".to_string())
+        );
+    }
+
+    #[test]
+    fn v9_fixture() {
+        let lockfile = include_str!("../fixtures/pnpm-v9.yaml");
+        assert_eq!(importer_value(lockfile, ".", "@ant-design/icons"), version("4.8.3"));
+        assert_eq!(importer_value(lockfile, ".", "string-width-cjs"), alias("string-width", "4.2.3"));
+        assert_eq!(
+            importer_value(lockfile, "packages/web", "local-utils"),
+            DepValue::File("packages/utils".to_string())
+        );
+    }
+}
@@ -0,0 +1,106 @@
+// stats 子命令：按 importer 统计直接依赖、开发依赖与传递依赖规模
+
+use anyhow::Result;
+use clap::ValueEnum;
+use std::collections::BTreeSet;
+
+use npm_package_check::lockfile::{self, Importer, Lockfile};
+
+use crate::collation;
+
+/// 统计表的排序列，数值列按从大到小排序
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum SortKey {
+    Importer,
+    Direct,
+    Dev,
+    Transitive,
+    Versions,
+}
+
+#[derive(Debug)]
+struct ImporterStats {
+    path: String,
+    direct: usize,
+    dev: usize,
+    /// 传递闭包中的不同包名数（含直接依赖）
+    transitive: usize,
+    /// 传递闭包中不同的 name@version 数，大于包名数说明存在同一个包的多个版本
+    versions: usize,
+}
+
+pub fn run_stats(lock_data: &Lockfile, sort: SortKey) -> Result<()> {
+    let mut rows: Vec<ImporterStats> = lock_data
+        .importers
+        .iter()
+        .map(|(path, importer)| {
+            let packages = closure_packages(lock_data, roots(importer));
+            let names: BTreeSet<&str> = packages.iter().map(|(name, _)| name.as_str()).collect();
+            ImporterStats {
+                path: path.clone(),
+                direct: importer.dependencies.len() + importer.optional_dependencies.len(),
+                dev: importer.dev_dependencies.len(),
+                transitive: names.len(),
+                versions: packages.len(),
+            }
+        })
+        .collect();
+
+    rows.sort_by(|a, b| {
+        let order = match sort {
+            SortKey::Importer => collation::compare(&a.path, &b.path),
+            SortKey::Direct => b.direct.cmp(&a.direct),
+            SortKey::Dev => b.dev.cmp(&a.dev),
+            SortKey::Transitive => b.transitive.cmp(&a.transitive),
+            SortKey::Versions => b.versions.cmp(&a.versions),
+        };
+        order.then_with(|| collation::compare(&a.path, &b.path))
+    });
+
+    println!("📊 依赖统计: {} 个 importer\n", rows.len());
+
+    let headers = ["Importer", "Direct", "Dev", "Transitive", "Versions"];
+    let width = rows
+        .iter()
+        .map(|row| row.path.chars().count())
+        .chain([headers[0].len()])
+        .max()
+        .unwrap_or(0);
+    println!(
+        "{:<width$}  {:>8}  {:>8}  {:>10}  {:>8}",
+        headers[0], headers[1], headers[2], headers[3], headers[4]
+    );
+    for row in &rows {
+        println!(
+            "{:<width$}  {:>8}  {:>8}  {:>10}  {:>8}",
+            row.path, row.direct, row.dev, row.transitive, row.versions
+        );
+    }
+
+    let total = closure_packages(lock_data, lock_data.importers.values().flat_map(roots));
+    println!("\n整个工作区共 {} 个不同的 name@version", total.len());
+
+    Ok(())
+}
+
+/// importer 的全部直接依赖（含开发依赖与可选依赖）
+fn roots(importer: &Importer) -> impl Iterator<Item = (&str, &str)> {
+    importer
+        .dependencies
+        .iter()
+        .chain(&importer.dev_dependencies)
+        .chain(&importer.optional_dependencies)
+        .map(|(name, dep_info)| (name.as_str(), dep_info.version.as_str()))
+}
+
+/// 传递闭包中的 (包名, 版本号)，同一版本的不同 peer 组合只计一次
+fn closure_packages<'a>(
+    lock_data: &Lockfile,
+    roots: impl IntoIterator<Item = (&'a str, &'a str)>,
+) -> BTreeSet<(String, String)> {
+    lock_data
+        .closure(roots)
+        .iter()
+        .filter_map(|key| lockfile::parse_package_key(key))
+        .collect()
+}
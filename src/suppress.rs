@@ -0,0 +1,90 @@
+// 逐条抑制命中：锁文件旁的 .npmpkgcheck-suppressions.yaml 按指纹抑制已经评估过的命中，
+// 每条抑制都要写明理由与到期日期，到期后命中自动重新报告
+//
+// suppressions:
+//   - fingerprint: 1f3b2c0d9e8a7f65   # -v 或 JSON 报告中命中的 fingerprint
+//     package: lodash@4.17.20         # 可选，仅供阅读
+//     justification: 只在构建脚本中使用，不进入产物
+//     expires: 2025-06-30             # 到期日当天仍然有效
+//
+// 指纹由包名与命中的实际版本计算：锁文件中出现新的受影响版本时指纹随之变化，命中同样会重新报告。
+// 被抑制的命中仍按原状态列出与计数，但不参与策略判定（退出码）与风险评分
+
+use anyhow::{Context, Result, bail};
+use chrono::NaiveDate;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const FILE_NAME: &str = ".npmpkgcheck-suppressions.yaml";
+
+#[derive(Deserialize)]
+struct RawFile {
+    #[serde(default)]
+    suppressions: Vec<RawSuppression>,
+}
+
+#[derive(Deserialize)]
+struct RawSuppression {
+    fingerprint: String,
+    #[serde(default)]
+    justification: String,
+    expires: String,
+}
+
+/// 一条抑制
+#[derive(Debug, Clone)]
+pub struct Suppression {
+    pub fingerprint: String,
+    pub justification: String,
+    pub expires: NaiveDate,
+}
+
+pub struct Suppressions {
+    pub path: PathBuf,
+    entries: Vec<Suppression>,
+}
+
+impl Suppressions {
+    /// 读取锁文件所在目录中的抑制文件，不存在时返回 None
+    pub fn load(lockfile: &Path) -> Result<Option<Self>> {
+        let path = crate::lockfile_dir(lockfile).join(FILE_NAME);
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(&path).with_context(|| format!("无法读取抑制文件 '{}'", path.display()))?;
+        let raw: RawFile =
+            serde_yaml::from_str(&content).with_context(|| format!("解析抑制文件 '{}' 失败", path.display()))?;
+
+        let mut entries = Vec::new();
+        for item in raw.suppressions {
+            if item.justification.trim().is_empty() {
+                bail!("抑制文件 '{}' 中 {} 没有填写 justification", path.display(), item.fingerprint);
+            }
+            let expires = NaiveDate::parse_from_str(item.expires.trim(), "%Y-%m-%d").with_context(|| {
+                format!("抑制文件 '{}' 中 {} 的到期日期 '{}' 无效，格式为 YYYY-MM-DD", path.display(), item.fingerprint, item.expires)
+            })?;
+            entries.push(Suppression {
+                fingerprint: item.fingerprint.trim().to_ascii_lowercase(),
+                justification: item.justification.trim().to_string(),
+                expires,
+            });
+        }
+        Ok(Some(Suppressions { path, entries }))
+    }
+
+    /// 指纹对应的抑制，同一指纹有多条时取到期日期最晚的一条
+    pub fn get(&self, fingerprint: &str) -> Option<&Suppression> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.fingerprint == fingerprint)
+            .max_by_key(|entry| entry.expires)
+    }
+}
+
+/// 命中的指纹：包名与排序后的命中版本的 SHA-256 前 16 位十六进制
+pub fn fingerprint(name: &str, versions: &[String]) -> String {
+    let digest = Sha256::digest(format!("{}@{}", name, versions.join(",")).as_bytes());
+    digest.iter().take(8).map(|b| format!("{:02x}", b)).collect()
+}
@@ -0,0 +1,187 @@
+// trend 子命令：汇总 --usage-stats 记录的历次批量检查，按项目（锁文件）与周期列出命中数、
+// 命中包数与风险评分的变化，便于按月查看整体方向，而不是只看某一次检查的结果
+//
+// 每个周期取该周期内最后一次检查的结果，代表周期结束时的状态；单包查询不参与统计
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike};
+use clap::ValueEnum;
+use std::collections::BTreeMap;
+use std::fs;
+
+use crate::collation;
+use crate::usage::{self, UsageRecord};
+
+const SPARK_CHARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// 汇总周期
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum Period {
+    Day,
+    Week,
+    Month,
+    /// 每次检查单独作为一个周期
+    Run,
+}
+
+impl Period {
+    fn label(&self) -> &'static str {
+        match self {
+            Period::Day => "按日",
+            Period::Week => "按周",
+            Period::Month => "按月",
+            Period::Run => "按次",
+        }
+    }
+
+    /// 记录所属的周期，时间无法解析时返回 None
+    fn key(&self, time: &str) -> Option<String> {
+        let time = DateTime::parse_from_rfc3339(time).ok()?;
+        Some(match self {
+            Period::Day => time.format("%Y-%m-%d").to_string(),
+            Period::Week => {
+                let week = time.iso_week();
+                format!("{}-W{:02}", week.year(), week.week())
+            }
+            Period::Month => time.format("%Y-%m").to_string(),
+            Period::Run => time.format("%Y-%m-%d %H:%M:%S").to_string(),
+        })
+    }
+}
+
+/// 一个周期的数据点
+struct Point<'a> {
+    period: String,
+    runs: usize,
+    /// 周期内最后一次检查
+    last: &'a UsageRecord,
+}
+
+pub fn run_trend(path: &str, by: Period, project: Option<&str>, csv: Option<&str>) -> Result<()> {
+    let records = usage::load(path)?;
+
+    // 记录按运行先后追加，同一周期的记录相邻，后面的记录代表周期结束时的状态
+    let mut projects: BTreeMap<&str, Vec<Point>> = BTreeMap::new();
+    for record in records.iter().filter(|r| r.mode == "batch") {
+        if project.is_some_and(|project| project != record.lockfile) {
+            continue;
+        }
+        let Some(period) = by.key(&record.time) else {
+            continue;
+        };
+        let points = projects.entry(&record.lockfile).or_default();
+        match points.last_mut() {
+            Some(point) if point.period == period && by != Period::Run => {
+                point.runs += 1;
+                point.last = record;
+            }
+            _ => points.push(Point { period, runs: 1, last: record }),
+        }
+    }
+
+    if projects.is_empty() {
+        println!("ℹ️ 使用统计文件中没有批量检查的记录");
+        return Ok(());
+    }
+
+    let mut sorted: Vec<_> = projects.iter().collect();
+    collation::sort_by_key(&mut sorted, |(project, _)| project);
+    for (project, points) in sorted {
+        print_project(project, points, by);
+    }
+
+    if let Some(csv) = csv {
+        write_csv(&projects, csv)?;
+        eprintln!("📊 趋势数据已写入: {}", csv);
+    }
+    Ok(())
+}
+
+fn print_project(project: &str, points: &[Point], by: Period) {
+    let runs: usize = points.iter().map(|p| p.runs).sum();
+    println!("📈 {}（{}，{} 个周期，{} 次检查）\n", project, by.label(), points.len(), runs);
+
+    let headers = ["Period", "Findings", "Packages", "Risk Score"];
+    let width = points
+        .iter()
+        .map(|p| p.period.chars().count())
+        .chain([headers[0].len()])
+        .max()
+        .unwrap_or(0);
+    println!("   {:<width$}  {:>8}  {:>8}  {:>10}", headers[0], headers[1], headers[2], headers[3]);
+    for point in points {
+        println!(
+            "   {:<width$}  {:>8}  {:>8}  {:>10}",
+            point.period, point.last.findings, point.last.unique_packages, point.last.risk_score
+        );
+    }
+
+    println!();
+    let series: [(&str, Vec<u64>); 3] = [
+        ("命中", points.iter().map(|p| p.last.findings as u64).collect()),
+        ("命中包数", points.iter().map(|p| p.last.unique_packages as u64).collect()),
+        ("风险评分", points.iter().map(|p| u64::from(p.last.risk_score)).collect()),
+    ];
+    for (label, values) in &series {
+        println!("   {}  {}  {}", pad(label, 8), sparkline(values), change(values));
+    }
+    println!();
+}
+
+/// 按显示宽度补齐中文标签（每个汉字占两列）
+fn pad(label: &str, width: usize) -> String {
+    let used = label.chars().map(|c| if c.is_ascii() { 1 } else { 2 }).sum::<usize>();
+    format!("{}{}", label, " ".repeat(width.saturating_sub(used)))
+}
+
+/// 按最小值到最大值的区间把数值映射为字符高度
+fn sparkline(values: &[u64]) -> String {
+    let min = values.iter().copied().min().unwrap_or(0);
+    let max = values.iter().copied().max().unwrap_or(0);
+    values
+        .iter()
+        .map(|&value| match max - min {
+            0 => SPARK_CHARS[0],
+            range => SPARK_CHARS[((value - min) * (SPARK_CHARS.len() as u64 - 1) / range) as usize],
+        })
+        .collect()
+}
+
+/// 首个周期到最后一个周期的变化，如 12 → 8（-4）
+fn change(values: &[u64]) -> String {
+    let (Some(&first), Some(&last)) = (values.first(), values.last()) else {
+        return String::new();
+    };
+    let delta = last as i64 - first as i64;
+    match delta {
+        0 => format!("{} → {}（持平）", first, last),
+        _ => format!("{} → {}（{:+}）", first, last, delta),
+    }
+}
+
+fn write_csv(projects: &BTreeMap<&str, Vec<Point>>, path: &str) -> Result<()> {
+    let mut text = String::from("project,period,runs,findings,unique_packages,risk_score\n");
+    for (project, points) in projects {
+        for point in points {
+            text.push_str(&format!(
+                "{},{},{},{},{},{}\n",
+                csv_field(project),
+                point.period,
+                point.runs,
+                point.last.findings,
+                point.last.unique_packages,
+                point.last.risk_score
+            ));
+        }
+    }
+    fs::write(path, text).with_context(|| format!("无法写入文件 '{}'", path))
+}
+
+/// 含逗号、引号或换行的字段加引号
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,86 @@
+// 本地使用统计（--usage-stats）：每次检查结束后把概要追加到本地的 JSON Lines 文件，
+// 供团队自行统计运行次数、检查的锁文件数与命中随时间的变化。只写本地文件，不向任何服务发送数据，
+// 未指定路径时不记录；trend 子命令读取该文件汇总各项目的变化趋势
+
+use anyhow::{Context, Result, bail};
+use chrono::FixedOffset;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+use crate::{BatchResult, dates, report};
+
+/// 一次运行的概要，对应文件中的一行
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UsageRecord {
+    /// 运行结束的时间（RFC 3339）
+    pub time: String,
+    /// single（单包查询）或 batch（批量检查）
+    pub mode: String,
+    pub lockfile: String,
+    /// 检查的锁文件数
+    pub lockfiles: usize,
+    /// 检查的条目数
+    pub checked: usize,
+    /// 命中的条目数（找到、部分匹配、已有修复版本）
+    pub findings: usize,
+    /// 命中的不同包名数
+    pub unique_packages: usize,
+    pub risk_score: u32,
+    pub exit_code: i32,
+}
+
+impl UsageRecord {
+    pub fn from_results(
+        results: &[BatchResult],
+        mode: &str,
+        lockfile: &str,
+        exit_code: i32,
+        timezone: Option<FixedOffset>,
+    ) -> Self {
+        let hits: Vec<&BatchResult> = results.iter().filter(|r| r.status.is_hit()).collect();
+        let unique: BTreeSet<&str> = hits.iter().map(|r| r.package.name.as_str()).collect();
+        Self {
+            time: dates::timestamp(timezone),
+            mode: mode.to_string(),
+            lockfile: lockfile.to_string(),
+            lockfiles: 1,
+            checked: results.len(),
+            findings: hits.len(),
+            unique_packages: unique.len(),
+            risk_score: crate::risk::total(results),
+            exit_code,
+        }
+    }
+
+    /// 追加到统计文件末尾，文件不存在时创建
+    pub fn append(&self, path: &str) -> Result<()> {
+        let mut line = report::canonical_json(self, false)?;
+        line.push('\n');
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("无法打开使用统计文件 '{}'", path))?;
+        file.write_all(line.as_bytes())
+            .with_context(|| format!("无法写入使用统计文件 '{}'", path))
+    }
+}
+
+/// 读取统计文件中的全部记录，按文件中的顺序（即运行的先后）返回
+pub fn load(path: &str) -> Result<Vec<UsageRecord>> {
+    if !Path::new(path).exists() {
+        bail!("使用统计文件 '{}' 不存在，请先在检查时通过 --usage-stats 记录", path);
+    }
+    let content = fs::read_to_string(path).with_context(|| format!("无法读取使用统计文件 '{}'", path))?;
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str(line).with_context(|| format!("使用统计文件 '{}' 第 {} 行格式有误", path, i + 1))
+        })
+        .collect()
+}
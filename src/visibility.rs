@@ -0,0 +1,96 @@
+// 按 package.json 的 private 字段筛选 importer（--only-public / --only-private）
+//
+// 作为库发布的 workspace（没有 "private": true）与只用于部署的应用适用不同的许可证与来源证明策略，
+// 筛选后只保留选中 importer 可以到达的 packages/snapshots 节点，其他 importer 独有的依赖不再参与检查
+
+use anyhow::{Result, bail};
+use std::collections::HashSet;
+use std::path::Path;
+
+use npm_package_check::lockfile::Lockfile;
+use npm_package_check::workspace;
+
+use crate::why;
+
+/// 要保留的 importer
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Visibility {
+    /// 可以发布的 importer（package.json 中没有 "private": true）
+    Public,
+    /// 声明了 "private": true 的 importer
+    Private,
+}
+
+impl Visibility {
+    fn label(&self) -> &'static str {
+        match self {
+            Visibility::Public => "可发布",
+            Visibility::Private => "声明 private",
+        }
+    }
+}
+
+/// importer 的 package.json 是否声明了 "private": true，没有 package.json 时返回 None
+fn is_private(dir: &Path) -> Result<Option<bool>> {
+    let manifest = workspace::read_manifest(dir)?;
+    Ok(manifest.map(|manifest| manifest.get("private").and_then(|v| v.as_bool()).unwrap_or(false)))
+}
+
+/// 只保留符合条件的 importer 及其可以到达的节点；lockfile_dir 为锁文件所在目录，importer 路径相对于该目录
+pub fn retain_importers(lock: &mut Lockfile, lockfile_dir: &Path, visibility: Visibility, verbose: bool) -> Result<()> {
+    if lock.importers.is_empty() {
+        bail!("锁文件中没有 importer（例如旁边没有 package.json 的 yarn.lock），无法按 private 字段筛选");
+    }
+
+    let total = lock.importers.len();
+    let mut retained = Vec::new();
+    for path in lock.importers.keys() {
+        let private = match is_private(&lockfile_dir.join(path))? {
+            Some(private) => private,
+            None => {
+                eprintln!("⚠️ importer '{}' 没有 package.json，按可发布处理", path);
+                false
+            }
+        };
+        if private == (visibility == Visibility::Private) {
+            retained.push(path.clone());
+        }
+    }
+    let before = lock.packages.len();
+    retain_reachable(lock, &retained);
+
+    if verbose {
+        println!(
+            "只检查{}的 importer: {}/{} 个，packages 节点 {}/{} 个",
+            visibility.label(),
+            retained.len(),
+            total,
+            lock.packages.len(),
+            before
+        );
+    }
+    if retained.is_empty() {
+        eprintln!("⚠️ 没有{}的 importer", visibility.label());
+    }
+    Ok(())
+}
+
+/// 只保留 retained 中的 importer 及其可以到达的 packages/snapshots 节点
+pub fn retain_reachable(lock: &mut Lockfile, retained: &[String]) {
+    lock.importers.retain(|path, _| retained.contains(path));
+
+    // 选中 importer 可以到达的节点（包括开发依赖）
+    let reachable: HashSet<String> = retained
+        .iter()
+        .flat_map(|importer| why::shortest_chains(lock, importer, true).into_keys())
+        .collect();
+    // v9 的 snapshot key 去掉 peer 组合后为 packages 节点的 key
+    let package_ids: HashSet<String> = reachable
+        .iter()
+        .filter_map(|key| lock.locked.get(key).map(|locked| locked.id()))
+        .collect();
+    let keep = |key: &String| reachable.contains(key) || package_ids.contains(key);
+    lock.snapshots.retain(|key, _| keep(key));
+    lock.packages.retain(|key, _| keep(key));
+    lock.locked.retain(|key, _| keep(key));
+}
@@ -0,0 +1,316 @@
+// watch-feed 子命令：定期拉取远程风险清单，清单或锁文件变化时重新检查并通知新的命中
+//
+// 通知与去重同样用于单次检查的 --webhook：已通知的命中记录在状态文件中，两者的格式相同
+
+use anyhow::{Context, Result};
+use chrono::Local;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+use npm_package_check::batch::Columns;
+use npm_package_check::lockfile::{Lockfile, LockfileFormat};
+
+use crate::http::Client;
+use crate::matching::{MatchMode, version_matches};
+use crate::{find_package_in_lock, http, parse_batch_content, scan};
+
+/// 新增命中时的通知粒度
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum NotifyMode {
+    /// 每轮检查发送一条汇总通知，适合定期的依赖卫生扫描
+    #[default]
+    PerRun,
+    /// 每个新增命中单独发送一条通知，适合需要逐条处置的高危清单
+    PerFinding,
+}
+
+pub struct WatchOptions<'a> {
+    /// 风险清单地址（http/https）或本地文件路径，格式与批量文件相同
+    pub feed: &'a str,
+    /// 锁文件或目录，目录中递归查找锁文件
+    pub lockfiles: &'a [String],
+    pub excludes: &'a [String],
+    /// 在目录中查找锁文件时跟随指向目录内部的符号链接
+    pub follow_symlinks: bool,
+    pub interval: Duration,
+    pub webhook: Option<&'a str>,
+    pub notify: NotifyMode,
+    /// 已通知命中的记录文件，跨进程保留去重状态（例如由 cron 定期执行 --once）
+    pub state_file: Option<&'a str>,
+    /// 只检查一次，有命中时以退出码 1 结束
+    pub once: bool,
+    pub match_mode: MatchMode,
+    /// 风险清单的列映射
+    pub columns: Option<&'a Columns>,
+    /// 下载风险清单时以 Bearer 发送的 token
+    pub token: Option<&'a str>,
+    pub client: &'a Client,
+    pub verbose: bool,
+    /// 只输出将要发送的通知与写入的状态文件，不实际执行
+    pub dry_run: bool,
+}
+
+#[derive(Serialize)]
+struct Notification {
+    text: String,
+}
+
+/// webhook 通知的发送目标
+pub struct Webhook<'a> {
+    pub url: &'a str,
+    pub notify: NotifyMode,
+    pub client: &'a Client,
+    /// 只输出将要发送的请求，不实际发送
+    pub dry_run: bool,
+}
+
+/// 两次检查之间保留的状态
+#[derive(Default, Serialize, Deserialize)]
+struct WatchState {
+    // 清单与锁文件内容的摘要，未变化时跳过检查
+    #[serde(skip)]
+    fingerprint: Option<String>,
+    // 已经通知过的命中：命中的摘要 → 命中内容与通知时间
+    notified: BTreeMap<String, NotifiedFinding>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct NotifiedFinding {
+    /// 格式为 "锁文件: name@version"
+    finding: String,
+    notified_at: String,
+}
+
+impl WatchState {
+    fn load(path: Option<&str>) -> Result<Self> {
+        match path {
+            Some(path) if Path::new(path).exists() => {
+                let content = fs::read_to_string(path).with_context(|| format!("无法读取状态文件 '{}'", path))?;
+                serde_json::from_str(&content).with_context(|| format!("解析状态文件 '{}' 失败", path))
+            }
+            _ => Ok(Self::default()),
+        }
+    }
+
+    fn save(&self, path: Option<&str>, dry_run: bool) -> Result<()> {
+        let Some(path) = path else {
+            return Ok(());
+        };
+        if dry_run {
+            println!("   🔍 [dry-run] 将写入状态文件 {}（{} 条已通知命中）", path, self.notified.len());
+            return Ok(());
+        }
+        let json = crate::report::canonical_json(self, true)?;
+        fs::write(path, json).with_context(|| format!("无法写入状态文件 '{}'", path))
+    }
+
+    fn mark_notified(&mut self, finding: &str) {
+        self.notified
+            .entry(finding_fingerprint(finding))
+            .or_insert_with(|| NotifiedFinding {
+                finding: finding.to_string(),
+                notified_at: now(),
+            });
+    }
+}
+
+/// 命中的摘要，用于与已通知的记录去重
+fn finding_fingerprint(finding: &str) -> String {
+    let digest = Sha256::digest(finding.as_bytes());
+    digest.iter().take(8).map(|b| format!("{:02x}", b)).collect()
+}
+
+pub fn run_watch_feed(options: &WatchOptions) -> Result<()> {
+    let client = options.client;
+    let mut state = WatchState::load(options.state_file)?;
+    println!(
+        "👀 监视风险清单: {}（间隔 {} 秒）",
+        options.feed,
+        options.interval.as_secs()
+    );
+
+    loop {
+        match poll(client, options, &mut state) {
+            Ok(_) if !options.once => {}
+            Ok(matches) => {
+                if !matches.is_empty() {
+                    std::process::exit(1);
+                }
+                return Ok(());
+            }
+            // 长期运行时单次失败（网络抖动、锁文件正在写入）不退出，下一轮重试
+            Err(e) if !options.once => eprintln!("[{}] ⚠️ 检查失败: {:#}", now(), e),
+            Err(e) => return Err(e),
+        }
+        thread::sleep(options.interval);
+    }
+}
+
+/// 执行一轮检查，返回当前全部命中
+fn poll(client: &Client, options: &WatchOptions, state: &mut WatchState) -> Result<BTreeSet<String>> {
+    let feed = fetch_feed(client, options.feed, options.token)?;
+    let lockfiles = resolve_lockfiles(options.lockfiles, options.excludes, options.follow_symlinks)?;
+    let contents = lockfiles
+        .iter()
+        .map(|path| fs::read_to_string(path).with_context(|| format!("无法读取文件 '{}'", path.display())))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut hasher = Sha256::new();
+    hasher.update(feed.as_bytes());
+    for (path, content) in lockfiles.iter().zip(&contents) {
+        hasher.update(path.display().to_string().as_bytes());
+        hasher.update(content.as_bytes());
+    }
+    let fingerprint: String = hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect();
+    if state.fingerprint.as_deref() == Some(fingerprint.as_str()) {
+        if options.verbose {
+            println!("[{}] 清单与锁文件均未变化", now());
+        }
+        return Ok(state.notified.values().map(|n| n.finding.clone()).collect());
+    }
+
+    let packages = parse_batch_content(&feed, options.columns, options.match_mode).context("解析风险清单失败")?;
+    let mut matches = BTreeSet::new();
+    for (path, content) in lockfiles.iter().zip(&contents) {
+        let lock_data = Lockfile::parse_as(content, LockfileFormat::detect(path, content))
+            .with_context(|| format!("解析锁文件 '{}' 失败", path.display()))?;
+        let index = lock_data.index();
+        for package in &packages {
+            for found in find_package_in_lock(&index, &package.name) {
+                let hit = package.versions.is_empty()
+                    || package
+                        .versions
+                        .iter()
+                        .any(|v| version_matches(&found.version, v, package.match_mode));
+                if hit {
+                    matches.insert(format!("{}: {}@{}", path.display(), package.name, found.version));
+                }
+            }
+        }
+    }
+
+    let new_matches: Vec<&String> = matches
+        .iter()
+        .filter(|item| !state.notified.contains_key(&finding_fingerprint(item)))
+        .collect();
+    println!(
+        "[{}] 清单 {} 条，锁文件 {} 个，命中 {} 项，新增 {} 项",
+        now(),
+        packages.len(),
+        lockfiles.len(),
+        matches.len(),
+        new_matches.len()
+    );
+    for item in &new_matches {
+        println!("   🚨 {}", item);
+    }
+
+    // 已消失的命中不再保留，再次出现时重新通知
+    state.notified.retain(|_, notified| matches.contains(&notified.finding));
+    // 通知成功后再记录，失败时下一轮会重新检查并通知尚未送达的命中
+    if let Some(url) = options.webhook {
+        let webhook = Webhook { url, notify: options.notify, client, dry_run: options.dry_run };
+        if let Err(e) = deliver(&webhook, state, &new_matches, "风险清单") {
+            state.save(options.state_file, options.dry_run)?;
+            return Err(e);
+        }
+    }
+
+    for item in &new_matches {
+        state.mark_notified(item);
+    }
+    state.fingerprint = Some(fingerprint);
+    state.save(options.state_file, options.dry_run)?;
+    Ok(matches)
+}
+
+/// 单次检查（--webhook）的命中通知：与状态文件中该锁文件已通知的命中去重，只通知新增的命中。
+/// findings 为该锁文件当前的全部命中，格式为 "锁文件: name@version"；同一个状态文件可以记录多个锁文件
+pub fn notify_findings(webhook: &Webhook, state_file: Option<&str>, lockfile: &str, findings: &BTreeSet<String>) -> Result<()> {
+    let mut state = WatchState::load(state_file)?;
+    // 该锁文件中已消失的命中不再保留，再次出现时重新通知；其他锁文件的记录不变
+    let prefix = format!("{}: ", lockfile);
+    state
+        .notified
+        .retain(|_, notified| !notified.finding.starts_with(&prefix) || findings.contains(&notified.finding));
+    let new_findings: Vec<&String> = findings
+        .iter()
+        .filter(|item| !state.notified.contains_key(&finding_fingerprint(item)))
+        .collect();
+    let delivered = deliver(webhook, &mut state, &new_findings, "检查");
+    if delivered.is_ok() {
+        for item in &new_findings {
+            state.mark_notified(item);
+        }
+    }
+    state.save(state_file, webhook.dry_run)?;
+    delivered
+}
+
+/// 按通知粒度发送新增命中，source 为通知中的来源说明；per-finding 时每条送达后立即记录，
+/// 部分失败时已送达的命中不会重复发送
+fn deliver(webhook: &Webhook, state: &mut WatchState, new_matches: &[&String], source: &str) -> Result<()> {
+    match webhook.notify {
+        NotifyMode::PerRun if !new_matches.is_empty() => {
+            let lines: Vec<&str> = new_matches.iter().map(|item| item.as_str()).collect();
+            let notification = Notification {
+                text: format!("npm_package_check: {}新增 {} 项命中\n{}", source, lines.len(), lines.join("\n")),
+            };
+            send(webhook, &notification).context("发送通知失败")?;
+        }
+        NotifyMode::PerRun => {}
+        NotifyMode::PerFinding => {
+            for item in new_matches {
+                let notification = Notification {
+                    text: format!("npm_package_check: {}命中 {}", source, item),
+                };
+                send(webhook, &notification).with_context(|| format!("发送通知失败: {}", item))?;
+                state.mark_notified(item);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 发送 webhook 通知，--dry-run 时只输出将要发送的请求
+fn send(webhook: &Webhook, notification: &Notification) -> Result<()> {
+    if webhook.dry_run {
+        let body = serde_json::to_string(notification)?;
+        println!("   🔍 [dry-run] 将 POST {}: {}", webhook.url, body);
+        return Ok(());
+    }
+    http::post(webhook.client, webhook.url, notification)
+}
+
+fn fetch_feed(client: &Client, feed: &str, token: Option<&str>) -> Result<String> {
+    if feed.starts_with("http://") || feed.starts_with("https://") {
+        http::get_text_with(client, feed, &http::bearer(token))
+    } else {
+        fs::read_to_string(feed).with_context(|| format!("无法读取风险清单 '{}'", feed))
+    }
+}
+
+/// 展开锁文件参数：文件直接使用，目录中递归查找
+fn resolve_lockfiles(paths: &[String], excludes: &[String], follow_symlinks: bool) -> Result<Vec<PathBuf>> {
+    let mut lockfiles = Vec::new();
+    for path in paths {
+        let path = Path::new(path);
+        if path.is_dir() {
+            lockfiles.extend(scan::find_lockfiles(path, excludes, follow_symlinks)?);
+        } else {
+            lockfiles.push(path.to_path_buf());
+        }
+    }
+    Ok(lockfiles)
+}
+
+fn now() -> String {
+    Local::now().format("%Y-%m-%d %H:%M:%S").to_string()
+}
@@ -0,0 +1,209 @@
+// why 子命令：追踪包被安装的原因，列出从各 importer 到该包的依赖链，并标注每一步的依赖类型
+//
+// 依赖链上只要有一步是 dev，整条链就只在开发环境中安装；存在不含 dev 的依赖链时，
+// 该包会随生产环境一起安装，命中风险清单时需要优先处理
+
+use anyhow::{Context, Result};
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+
+use npm_package_check::lockfile::{Lockfile, LockedPackage};
+
+use crate::{LockfileContext, collation};
+use crate::depends::parse_query;
+use crate::matching::{self, MatchMode, version_matches};
+
+/// 依赖链上一步的依赖类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum EdgeKind {
+    Prod,
+    Dev,
+    Optional,
+    Peer,
+}
+
+impl EdgeKind {
+    fn label(&self) -> &'static str {
+        match self {
+            EdgeKind::Prod => "prod",
+            EdgeKind::Dev => "dev",
+            EdgeKind::Optional => "optional",
+            EdgeKind::Peer => "peer",
+        }
+    }
+}
+
+/// 依赖链：起点 importer 之后依次经过的 (依赖类型, 节点 key)
+pub(crate) type Chain = Vec<(EdgeKind, String)>;
+
+/// 节点的直接依赖 (依赖类型, 依赖名, 依赖值)。v9 的 peer 依赖与普通依赖一起记录在 snapshots 中，
+/// 依赖名出现在包的 peerDependencies 中时记为 peer
+fn edges<'a>(lock: &'a Lockfile, key: &str) -> Vec<(EdgeKind, &'a str, &'a str)> {
+    let package = lock.package_info(key);
+    let maps = match (lock.snapshots.get(key), package) {
+        (Some(snapshot_info), _) => vec![
+            (EdgeKind::Prod, &snapshot_info.dependencies),
+            (EdgeKind::Optional, &snapshot_info.optional_dependencies),
+        ],
+        (None, Some(package_info)) => vec![
+            (EdgeKind::Prod, &package_info.dependencies),
+            (EdgeKind::Optional, &package_info.optional_dependencies),
+            (EdgeKind::Dev, &package_info.dev_dependencies),
+        ],
+        (None, None) => Vec::new(),
+    };
+    let is_peer = |name: &str| package.is_some_and(|p| p.peer_dependencies.contains_key(name));
+    maps.into_iter()
+        .flat_map(|(kind, deps)| deps.iter().map(move |(name, value)| (kind, name.as_str(), value.as_str())))
+        .map(|(kind, name, value)| (if is_peer(name) { EdgeKind::Peer } else { kind }, name, value))
+        .collect()
+}
+
+/// 节点 key 对应的包名与版本号
+pub(crate) fn node_package(lock: &Lockfile, key: &str) -> Option<(String, String)> {
+    lock.locked.get(key).map(|locked| (locked.name.clone(), locked.version.clone()))
+}
+
+/// 从 importer 出发按广度优先搜索到达各节点的最短依赖链；include_dev 为 false 时不经过 dev 依赖
+pub(crate) fn shortest_chains(lock: &Lockfile, importer: &str, include_dev: bool) -> HashMap<String, Chain> {
+    let mut chains: HashMap<String, Chain> = HashMap::new();
+    let mut queue = VecDeque::new();
+    let Some(info) = lock.importers.get(importer) else {
+        return chains;
+    };
+    let roots = [
+        (EdgeKind::Prod, &info.dependencies),
+        (EdgeKind::Optional, &info.optional_dependencies),
+        (EdgeKind::Dev, &info.dev_dependencies),
+    ];
+    for (kind, deps) in roots {
+        if kind == EdgeKind::Dev && !include_dev {
+            continue;
+        }
+        for (name, dep_info) in deps {
+            if let Some(key) = lock.node_key(name, &dep_info.version)
+                && !chains.contains_key(&key)
+            {
+                chains.insert(key.clone(), vec![(kind, key.clone())]);
+                queue.push_back(key);
+            }
+        }
+    }
+
+    while let Some(key) = queue.pop_front() {
+        for (kind, name, value) in edges(lock, &key) {
+            if kind == EdgeKind::Dev && !include_dev {
+                continue;
+            }
+            let Some(child) = lock.node_key(name, value) else {
+                continue;
+            };
+            if chains.contains_key(&child) {
+                continue;
+            }
+            let mut chain = chains[&key].clone();
+            chain.push((kind, child.clone()));
+            chains.insert(child.clone(), chain);
+            queue.push_back(child);
+        }
+    }
+    chains
+}
+
+fn format_chain(lock: &Lockfile, context: &LockfileContext, importer: &str, chain: &Chain) -> String {
+    let mut text = context.importer_label(importer);
+    for (kind, key) in chain {
+        let label = match node_package(lock, key) {
+            Some((name, version)) => format!("{}@{}", name, version),
+            None => key.clone(),
+        };
+        text.push_str(&format!(" ─{}→ {}", kind.label(), label));
+    }
+    text
+}
+
+/// 满足查询 name@range 的节点 key，按 name@version 归并不同的 peer 组合
+pub(crate) fn find_targets(lock: &Lockfile, query: &str) -> Result<BTreeMap<String, BTreeSet<String>>> {
+    let (name, range) = parse_query(query);
+    if let Some(range) = range {
+        matching::validate(range, MatchMode::Semver).with_context(|| format!("无效的查询 '{}'", query))?;
+    }
+
+    let mut targets: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    for key in lock.snapshots.keys().chain(lock.packages.keys()) {
+        let Some((node_name, version)) = node_package(lock, key) else {
+            continue;
+        };
+        if node_name != name || range.is_some_and(|range| !version_matches(&version, range, MatchMode::Semver)) {
+            continue;
+        }
+        targets.entry(format!("{}@{}", node_name, version)).or_default().insert(key.clone());
+    }
+    Ok(targets)
+}
+
+pub fn run_why(lock_data: &Lockfile, context: &LockfileContext, query: &str, verbose: bool) -> Result<()> {
+    let targets = find_targets(lock_data, query)?;
+    if targets.is_empty() {
+        println!("✅ 锁文件中没有 {}", query);
+        return Ok(());
+    }
+
+    // 每个 importer 分别搜索：先只走非 dev 依赖，找不到时再允许经过 dev 依赖
+    let mut importers: Vec<&String> = lock_data.importers.keys().collect();
+    collation::sort_by_key(&mut importers, |importer| importer);
+    let searches: Vec<_> = importers
+        .into_iter()
+        .map(|importer| {
+            let production = shortest_chains(lock_data, importer, false);
+            let all = shortest_chains(lock_data, importer, true);
+            (importer, production, all)
+        })
+        .collect();
+
+    let mut any_production = false;
+    let mut any_chain = false;
+    for (package, keys) in &targets {
+        let mut lines = Vec::new();
+        let mut production = false;
+        for (importer, prod_chains, all_chains) in &searches {
+            // 同一个 importer 只展示一条最短的依赖链，生产依赖链优先
+            let prod_chain = keys.iter().filter_map(|key| prod_chains.get(key)).min_by_key(|chain| chain.len());
+            let chain = prod_chain.or_else(|| keys.iter().filter_map(|key| all_chains.get(key)).min_by_key(|chain| chain.len()));
+            let Some(chain) = chain else {
+                continue;
+            };
+            production |= prod_chain.is_some();
+            let suffix = if prod_chain.is_some() { "" } else { "（仅开发依赖）" };
+            lines.push(format!("{}{}", format_chain(lock_data, context, importer, chain), suffix));
+        }
+        any_production |= production;
+        any_chain |= !lines.is_empty();
+
+        if production {
+            println!("🔗 {}: 存在生产依赖链，会随生产环境安装", package);
+        } else if lines.is_empty() {
+            println!("🔗 {}: 没有 importer 依赖该包（锁文件中的孤立条目）", package);
+        } else {
+            println!("🔗 {}: 只通过开发依赖引入，不会随生产环境安装", package);
+        }
+        for line in &lines {
+            println!("   - {}", line);
+        }
+        if verbose {
+            // 同一个 name@version 的不同 peer 组合
+            let nodes: Vec<String> = keys
+                .iter()
+                .map(|key| lock_data.locked.get(key).map_or_else(|| key.clone(), LockedPackage::label))
+                .collect();
+            println!("   节点: {}", nodes.join(", "));
+        }
+        println!();
+    }
+
+    if any_production {
+        println!("🚨 {} 存在生产依赖链", query);
+    } else if any_chain {
+        println!("ℹ️ {} 只出现在开发依赖中", query);
+    }
+    Ok(())
+}
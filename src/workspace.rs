@@ -0,0 +1,140 @@
+// package.json 的 workspaces 字段：npm 与 yarn 共用的 monorepo 子项目声明
+//
+// 两种写法：
+//   "workspaces": ["packages/*", "apps/web"]
+//   "workspaces": { "packages": ["packages/*"], "nohoist": [...] }   （yarn classic）
+// pnpm 的 workspace 声明在 pnpm-workspace.yaml 的 packages 字段中，写法相同。
+// 模式按路径逐级匹配，支持 * 与 **，以 ! 开头的模式用于排除。
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+
+/// 读取 package.json，文件不存在时返回 None
+pub fn read_manifest(dir: &Path) -> Result<Option<serde_json::Value>> {
+    let path = dir.join("package.json");
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(&path).with_context(|| format!("无法读取文件 '{}'", path.display()))?;
+    let manifest = serde_json::from_str(&content).with_context(|| format!("解析 '{}' 失败", path.display()))?;
+    Ok(Some(manifest))
+}
+
+/// 根项目 package.json 中声明的 workspace 模式
+pub fn patterns(manifest: &serde_json::Value) -> Vec<String> {
+    let Some(workspaces) = manifest.get("workspaces") else {
+        return Vec::new();
+    };
+    let list = workspaces.get("packages").unwrap_or(workspaces);
+    list.as_array()
+        .map(|items| items.iter().filter_map(|item| item.as_str()).map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+/// pnpm-workspace.yaml 中声明的 workspace 模式，文件不存在时返回 None
+pub fn pnpm_patterns(dir: &Path) -> Result<Option<Vec<String>>> {
+    let path = dir.join("pnpm-workspace.yaml");
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(&path).with_context(|| format!("无法读取文件 '{}'", path.display()))?;
+    let value: serde_yaml::Value =
+        serde_yaml::from_str(&content).with_context(|| format!("解析 '{}' 失败", path.display()))?;
+    let patterns = value
+        .get("packages")
+        .and_then(|packages| packages.as_sequence())
+        .map(|items| items.iter().filter_map(|item| item.as_str()).map(str::to_string).collect())
+        .unwrap_or_default();
+    Ok(Some(patterns))
+}
+
+/// 展开 workspace 模式，返回相对于 root 的 workspace 目录（以 / 分隔、已排序），只保留含 package.json 的目录
+pub fn expand(root: &Path, patterns: &[String]) -> Vec<String> {
+    let mut dirs = Vec::new();
+    for pattern in patterns.iter().filter(|p| !p.starts_with('!')) {
+        let segments: Vec<&str> = normalize(pattern).split('/').filter(|s| !s.is_empty()).collect();
+        collect(root, "", &segments, &mut dirs);
+    }
+
+    let excludes: Vec<Vec<&str>> = patterns
+        .iter()
+        .filter_map(|p| p.strip_prefix('!'))
+        .map(|p| normalize(p).split('/').filter(|s| !s.is_empty()).collect())
+        .collect();
+    dirs.retain(|dir| {
+        let parts: Vec<&str> = dir.split('/').collect();
+        !excludes.iter().any(|exclude| path_matches(exclude, &parts))
+    });
+    dirs.sort();
+    dirs.dedup();
+    dirs
+}
+
+fn normalize(pattern: &str) -> &str {
+    let pattern = pattern.trim_start_matches("./");
+    pattern.trim_end_matches('/')
+}
+
+fn collect(root: &Path, relative: &str, segments: &[&str], dirs: &mut Vec<String>) {
+    let Some((segment, rest)) = segments.split_first() else {
+        if !relative.is_empty() && root.join(relative).join("package.json").is_file() {
+            dirs.push(relative.to_string());
+        }
+        return;
+    };
+    let join = |name: &str| if relative.is_empty() { name.to_string() } else { format!("{}/{}", relative, name) };
+
+    if !segment.contains('*') {
+        let next = join(segment);
+        if root.join(&next).is_dir() {
+            collect(root, &next, rest, dirs);
+        }
+        return;
+    }
+    // ** 匹配零级或多级目录
+    if *segment == "**" {
+        collect(root, relative, rest, dirs);
+    }
+    let Ok(entries) = fs::read_dir(root.join(relative)) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name == "node_modules" || name.starts_with('.') || !entry.path().is_dir() {
+            continue;
+        }
+        if *segment == "**" {
+            collect(root, &join(&name), segments, dirs);
+        } else if wildcard_matches(segment, &name) {
+            collect(root, &join(&name), rest, dirs);
+        }
+    }
+}
+
+fn path_matches(pattern: &[&str], parts: &[&str]) -> bool {
+    match (pattern.split_first(), parts.split_first()) {
+        (None, None) => true,
+        (Some((&"**", rest)), _) => {
+            path_matches(rest, parts) || (!parts.is_empty() && path_matches(pattern, &parts[1..]))
+        }
+        (Some((segment, rest)), Some((part, parts))) => wildcard_matches(segment, part) && path_matches(rest, parts),
+        _ => false,
+    }
+}
+
+/// 单级路径的通配符匹配，* 匹配任意字符
+fn wildcard_matches(pattern: &str, name: &str) -> bool {
+    let Some((prefix, rest)) = pattern.split_once('*') else {
+        return pattern == name;
+    };
+    let Some(name) = name.strip_prefix(prefix) else {
+        return false;
+    };
+    if !rest.contains('*') {
+        return name.ends_with(rest);
+    }
+    (0..=name.len())
+        .filter(|&i| name.is_char_boundary(i))
+        .any(|i| wildcard_matches(rest, &name[i..]))
+}
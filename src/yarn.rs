@@ -0,0 +1,243 @@
+// yarn.lock（Yarn classic 的 v1 格式）转换为与 pnpm 相同的锁文件模型
+//
+// 每个条目以逗号分隔的依赖声明（name@range）为标题，下面缩进记录实际安装的版本与依赖：
+//   "@scope/a@^1.0.0", "@scope/a@^1.1.0":
+//     version "1.2.0"
+//     resolved "https://registry.yarnpkg.com/@scope/a/-/a-1.2.0.tgz#<sha1>"
+//     integrity sha512-...
+//     dependencies:
+//       b "^2.0.0"
+// 条目转换为 packages 节点中以 name@version 为 key 的条目，依赖范围按依赖声明解析为实际版本。
+// yarn.lock 不记录哪些是直接依赖，根项目与各 workspace 的 importer 需要结合 package.json 生成（见 importers）
+
+use anyhow::{Context, Result, bail};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use crate::ecosystem::Ecosystem;
+use crate::lockfile::{DependencyInfo, Importer, Lockfile, PackageInfo};
+use crate::{npm, workspace};
+
+#[derive(Default)]
+struct Entry {
+    specs: Vec<String>,
+    version: Option<String>,
+    resolved: Option<String>,
+    integrity: Option<String>,
+    dependencies: BTreeMap<String, String>,
+    optional_dependencies: BTreeMap<String, String>,
+}
+
+struct YarnLock {
+    entries: Vec<Entry>,
+    /// 依赖声明 name@range → (实际包名, 版本)
+    specs: HashMap<String, (String, String)>,
+}
+
+/// Yarn classic 的 yarn.lock (v1)
+pub struct Yarn;
+
+impl Ecosystem for Yarn {
+    fn name(&self) -> &'static str {
+        "yarn"
+    }
+
+    fn file_names(&self) -> &'static [&'static str] {
+        &["yarn.lock"]
+    }
+
+    fn sniff(&self, content: &str) -> bool {
+        is_yarn_lock(content)
+    }
+
+    fn parse(&self, content: &str) -> Result<Lockfile> {
+        parse(content)
+    }
+}
+
+/// 内容是否为 Yarn classic 的锁文件
+pub fn is_yarn_lock(content: &str) -> bool {
+    content.lines().take(5).any(|line| line.trim() == "# yarn lockfile v1")
+}
+
+pub fn parse(content: &str) -> Result<Lockfile> {
+    let lock = YarnLock::parse(content)?;
+
+    let mut packages = BTreeMap::new();
+    for entry in &lock.entries {
+        let (Some(version), Some(spec)) = (&entry.version, entry.specs.first()) else {
+            continue;
+        };
+        let Some((name, range)) = split_spec(spec) else {
+            continue;
+        };
+        let name = real_name(name, range).to_string();
+        packages.entry(format!("{}@{}", name, version)).or_insert_with(|| PackageInfo {
+            resolution: npm::resolution(entry.resolved.as_deref(), entry.integrity.clone()),
+            name: Some(name),
+            version: Some(version.clone()),
+            peer_dependencies: BTreeMap::new(),
+            dependencies: lock.resolve_all(&entry.dependencies),
+            optional_dependencies: lock.resolve_all(&entry.optional_dependencies),
+            dev_dependencies: BTreeMap::new(),
+            requires_build: None,
+        });
+    }
+
+    Ok(Lockfile::new("yarn v1".to_string(), BTreeMap::new(), packages, BTreeMap::new()))
+}
+
+/// 按 package.json 生成 importers：根项目记为 "."，workspaces 字段声明的子项目以相对路径为 key，
+/// 版本取自 yarn.lock 中对应的依赖声明；dir 下没有 package.json 时返回空表
+pub fn importers(content: &str, dir: &Path) -> Result<BTreeMap<String, Importer>> {
+    let Some(manifest) = workspace::read_manifest(dir)? else {
+        return Ok(BTreeMap::new());
+    };
+    let lock = YarnLock::parse(content)?;
+
+    let mut importers = BTreeMap::new();
+    importers.insert(".".to_string(), lock.importer(&manifest));
+    for path in workspace::expand(dir, &workspace::patterns(&manifest)) {
+        if let Some(manifest) = workspace::read_manifest(&dir.join(&path))? {
+            importers.insert(path, lock.importer(&manifest));
+        }
+    }
+    Ok(importers)
+}
+
+impl YarnLock {
+    fn parse(content: &str) -> Result<Self> {
+        if content.contains("__metadata:") {
+            bail!("仅支持 Yarn classic (v1) 的 yarn.lock，Yarn 2 及以上版本的锁文件格式不同");
+        }
+
+        let mut entries: Vec<Entry> = Vec::new();
+        // 当前所在的嵌套字段，例如 dependencies
+        let mut section: Option<String> = None;
+        for (number, line) in content.lines().enumerate() {
+            let text = line.trim();
+            if text.is_empty() || text.starts_with('#') {
+                continue;
+            }
+            let indent = line.len() - line.trim_start().len();
+            if indent == 0 {
+                let header = text
+                    .strip_suffix(':')
+                    .with_context(|| format!("yarn.lock 第 {} 行不是有效的条目标题", number + 1))?;
+                entries.push(Entry {
+                    specs: header.split(',').map(|spec| unquote(spec.trim()).to_string()).collect(),
+                    ..Entry::default()
+                });
+                section = None;
+                continue;
+            }
+
+            let entry = entries
+                .last_mut()
+                .with_context(|| format!("yarn.lock 第 {} 行不属于任何条目", number + 1))?;
+            if indent <= 2 {
+                if let Some(key) = text.strip_suffix(':') {
+                    section = Some(unquote(key).to_string());
+                    continue;
+                }
+                section = None;
+                let (key, value) = split_field(text);
+                match key {
+                    "version" => entry.version = Some(value.to_string()),
+                    "resolved" => entry.resolved = Some(value.to_string()),
+                    "integrity" => entry.integrity = Some(value.to_string()),
+                    _ => {}
+                }
+            } else {
+                let (name, range) = split_field(text);
+                let deps = match section.as_deref() {
+                    Some("dependencies") => &mut entry.dependencies,
+                    Some("optionalDependencies") => &mut entry.optional_dependencies,
+                    _ => continue,
+                };
+                deps.insert(name.to_string(), range.to_string());
+            }
+        }
+
+        let mut specs = HashMap::new();
+        for entry in &entries {
+            let Some(ref version) = entry.version else {
+                continue;
+            };
+            for spec in &entry.specs {
+                if let Some((name, range)) = split_spec(spec) {
+                    specs.insert(spec.clone(), (real_name(name, range).to_string(), version.clone()));
+                }
+            }
+        }
+
+        Ok(Self { entries, specs })
+    }
+
+    fn importer(&self, manifest: &serde_json::Value) -> Importer {
+        let deps = |field: &str| -> BTreeMap<String, DependencyInfo> {
+            let Some(declared) = manifest.get(field).and_then(|v| v.as_object()) else {
+                return BTreeMap::new();
+            };
+            declared
+                .iter()
+                .filter_map(|(name, range)| {
+                    let specifier = range.as_str()?;
+                    // workspace 之间的依赖以链接安装，不记录在 yarn.lock 中
+                    let version = self.resolve(name, specifier)?;
+                    Some((name.clone(), DependencyInfo { specifier: specifier.to_string(), version }))
+                })
+                .collect()
+        };
+
+        Importer {
+            dependencies: deps("dependencies"),
+            dev_dependencies: deps("devDependencies"),
+            optional_dependencies: deps("optionalDependencies"),
+        }
+    }
+
+    /// 依赖声明解析后的值，写法与 pnpm 锁文件一致：版本号，或别名的 name@version
+    fn resolve(&self, name: &str, range: &str) -> Option<String> {
+        let (real, version) = self.specs.get(&format!("{}@{}", name, range))?;
+        if real == name {
+            Some(version.clone())
+        } else {
+            Some(format!("{}@{}", real, version))
+        }
+    }
+
+    fn resolve_all(&self, deps: &BTreeMap<String, String>) -> BTreeMap<String, String> {
+        deps.iter()
+            .filter_map(|(name, range)| Some((name.clone(), self.resolve(name, range)?)))
+            .collect()
+    }
+}
+
+/// 拆分依赖声明 name@range，作用域包名以 @ 开头
+fn split_spec(spec: &str) -> Option<(&str, &str)> {
+    let scoped = usize::from(spec.starts_with('@'));
+    let pos = spec[scoped..].find('@')? + scoped;
+    Some((&spec[..pos], &spec[pos + 1..]))
+}
+
+/// 别名声明 alias@npm:real@range 实际安装的是 real
+fn real_name<'a>(name: &'a str, range: &'a str) -> &'a str {
+    range
+        .strip_prefix("npm:")
+        .and_then(split_spec)
+        .map_or(name, |(real, _)| real)
+}
+
+/// 拆分 `key value` 形式的字段，key 与 value 都可能带引号
+fn split_field(text: &str) -> (&str, &str) {
+    let (key, rest) = match text.strip_prefix('"').and_then(|rest| rest.split_once('"')) {
+        Some((key, rest)) => (key, rest),
+        None => text.split_once(char::is_whitespace).unwrap_or((text, "")),
+    };
+    (key, unquote(rest.trim()))
+}
+
+fn unquote(text: &str) -> &str {
+    text.strip_prefix('"').and_then(|t| t.strip_suffix('"')).unwrap_or(text)
+}